@@ -1,36 +1,51 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::mpsc::sync_channel;
 
-use crate::benchmarking::ControllerBench;
 use crate::builder::launch::RobotLauncher;
+use crate::builder::launch::RobotServices;
 use crate::comms;
 use crate::mechatronics::bucket_ladder::Intake;
 use crate::mechatronics::commands::RobotCommandFactory;
 use crate::mechatronics::controller::RobotController;
+use crate::mechatronics::input_shaping::InputShaper;
+use crate::mechatronics::load_shedding::LoadShedder;
 use crate::mechatronics::drive_train::DriveTrain;
 use crate::mechatronics::dumper::Dumper;
 use crate::mechatronics::RobotMessenger;
+use crate::recording::journal::CommandJournal;
 use crate::status::robot_state::GlobalRobotState;
 use crate::framework::CompositeRunnable;
 
+/// How the controller limits and shapes its outputs, where it journals commands, and the services
+/// which run alongside it.
+pub struct RobotOptions {
+    pub shedder: LoadShedder,
+    pub shaper: InputShaper,
+    /// The directory to journal commands into, if they should be journaled.
+    pub journal: Option<PathBuf>,
+    pub services: RobotServices,
+}
+
 pub struct RobotAssembler {
     dumper: Dumper,
     drive: DriveTrain,
     intake: Intake,
     state: Arc<GlobalRobotState>,
-    bench: Option<ControllerBench>,
-    monitor: CompositeRunnable
+    monitor: CompositeRunnable,
+    options: RobotOptions,
 }
 
 impl RobotAssembler {
-    pub fn new(dumper: Dumper, drive: DriveTrain, intake: Intake, state: Arc<GlobalRobotState>, bench: Option<ControllerBench>, monitor: CompositeRunnable) -> Self {
+    pub fn new(dumper: Dumper, drive: DriveTrain, intake: Intake, state: Arc<GlobalRobotState>, monitor: CompositeRunnable,
+               options: RobotOptions) -> Self {
         Self {
             dumper,
             drive,
             intake,
             state,
-            bench,
-            monitor
+            monitor,
+            options,
         }
     }
 
@@ -39,12 +54,23 @@ impl RobotAssembler {
 
         let command_factory = RobotCommandFactory::new();
 
-        let robot_view = RobotMessenger::new(controller_sender);
+        let robot_view = RobotMessenger::new(controller_sender.clone());
         let bfr = comms::stage(robot_view, self.state.clone(), command_factory);
 
-        let robot_controller = RobotController::new(controller_receiver, self.drive, self.dumper, self.intake, self.state.get_life(), self.state.get_cycle_counter());
+        let RobotOptions { shedder, shaper, journal, services } = self.options;
+        let mut robot_controller = RobotController::new(controller_receiver, self.drive, self.dumper, self.intake, self.state.clone(),
+                                                        shedder, shaper);
 
-        RobotLauncher::new(robot_controller, bfr, self.bench, self.monitor)
+        if let Some(path) = journal {
+            match CommandJournal::create(&path) {
+                Ok(journal) => {
+                    info!("Journaling commands to {}", journal.get_path().display());
+                    robot_controller.set_journal(journal);
+                }
+                Err(e) => error!("Failed to create command journal in {}: {}", path.display(), e),
+            }
+        }
+
+        RobotLauncher::new(robot_controller, controller_sender, bfr, self.monitor, self.state, services)
     }
 }
-
@@ -1,11 +1,16 @@
+use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
+use std::time::Duration;
 
 use libbeaglebone::pins::Pin;
 
 use crate::benchmarking::ControllerBench;
 use crate::builder::assembly::RobotAssembler;
+use crate::builder::assembly::RobotOptions;
+use crate::builder::factories::current_monitor::CurrentMonitorFactory;
 use crate::builder::factories::digital_monitor::DigitalMonitorFactory;
 use crate::builder::factories::drive::PrintDriveFactory;
 use crate::builder::factories::drive::ProductionDriveFactory;
@@ -13,18 +18,32 @@ use crate::builder::factories::drive::TestDriveFactory;
 use crate::builder::factories::dumper::PrintDumperFactory;
 use crate::builder::factories::dumper::ProductionDumperFactory;
 use crate::builder::factories::dumper::TestDumperFactory;
+use crate::builder::factories::encoder_monitor::EncoderMonitorFactory;
 use crate::builder::factories::intake::PrintIntakeFactory;
 use crate::builder::factories::intake::ProductionIntakeFactory;
 use crate::builder::factories::intake::TestIntakeFactory;
 use crate::builder::factories::SubsystemFactory;
+use crate::builder::launch::RobotServices;
+use crate::comms::telemetry::TelemetryServer;
 use crate::framework::{CompositeRunnable, Runnable};
 use crate::mechatronics::bucket_ladder::Intake;
 use crate::mechatronics::drive_train::DriveTrain;
 use crate::mechatronics::dumper::Dumper;
+use crate::mechatronics::input_shaping::InputShaper;
+use crate::mechatronics::load_shedding::LoadShedder;
+use crate::pinouts::analog::input::AnalogInput;
 use crate::pinouts::digital::input::DigitalInput;
 use crate::pinouts::enable_pins;
+use crate::pinouts::encoder::GlobalEncoderState;
 use crate::pinouts::factories::IoFactory;
-use crate::robot_map::*;
+use crate::recording::StateRecorder;
+use crate::robot_config::EncoderConfig;
+use crate::robot_config::RobotConfig;
+use crate::robot_map::SYSFS_ROOT;
+use crate::simulation::PhysicsSimulation;
+use crate::status::faults::Severity;
+use crate::status::faults::Subsystem;
+use crate::status::limits::GlobalInputState;
 use crate::status::robot_state::GlobalRobotState;
 
 pub struct RobotAssemblyBuilder {
@@ -37,73 +56,136 @@ pub struct RobotAssemblyBuilder {
     left_lower_limit: Option<Box<SubsystemFactory<Box<Runnable>>>>,
     dumper_upper_limit: Option<Box<SubsystemFactory<Box<Runnable>>>>,
     dumper_lower_limit: Option<Box<SubsystemFactory<Box<Runnable>>>>,
+    current_sensor: Option<Box<SubsystemFactory<Box<Runnable>>>>,
+    left_encoder: Option<Box<SubsystemFactory<Box<Runnable>>>>,
+    right_encoder: Option<Box<SubsystemFactory<Box<Runnable>>>>,
     state: Arc<GlobalRobotState>,
     bench: Option<ControllerBench>,
+    simulation: Option<PhysicsSimulation>,
+    controller_period: Option<Duration>,
+    monitor_period: Option<Duration>,
+    telemetry: Option<TelemetryServer>,
+    recorder: Option<StateRecorder>,
+    journal: Option<PathBuf>,
     io: Rc<IoFactory>,
     pin_enabled_status: bool,
+    config: RobotConfig,
 }
 
 impl RobotAssemblyBuilder {
     pub fn new() -> Self {
-        let state = Arc::new(GlobalRobotState::new());
+        Self::from_config(RobotConfig::default())
+    }
 
-        Self {
-            dumper: Box::new(PrintDumperFactory::new(state.clone())),
-            intake: Box::new(PrintIntakeFactory::new(state.clone())),
-            drive: Box::new(PrintDriveFactory::new(state.clone())),
+    /// Creates a builder whose mechanisms, sensors, and thresholds are taken from `config`.
+    pub fn from_config(config: RobotConfig) -> Self {
+        let state = Arc::new(GlobalRobotState::new());
+        state.get_current().set_thresholds(config.get_current().get_brownout(), config.get_current().get_critical());
+        state.get_watchdog().set_timeout(Duration::from_millis(config.get_controller().get_watchdog_timeout_millis()));
+        state.get_limits().configure(config.get_limits());
+        state.get_faults().set_kill_on_critical(config.get_controller().get_kill_on_critical_fault());
+        let io = Rc::new(IoFactory::new(state.get_faults()));
+
+        let mut builder = Self {
+            dumper: Box::new(PrintDumperFactory::new(state.clone(), config.get_dumper().clone())),
+            intake: Box::new(PrintIntakeFactory::new(state.clone(), config.get_intake().clone())),
+            drive: Box::new(PrintDriveFactory::new(state.clone(), config.get_drive().clone())),
             right_upper_limit: None,
             right_lower_limit: None,
             left_upper_limit: None,
             left_lower_limit: None,
             dumper_upper_limit: None,
             dumper_lower_limit: None,
+            current_sensor: None,
+            left_encoder: None,
+            right_encoder: None,
             state,
             bench: None,
-            io: Rc::new(IoFactory::new()),
+            simulation: None,
+            controller_period: config.get_controller().get_loop_period(),
+            monitor_period: config.get_controller().get_monitor_period(),
+            telemetry: None,
+            recorder: None,
+            journal: None,
+            io,
             pin_enabled_status: false,
+            config,
+        };
+
+        if builder.config.get_telemetry().get_enabled() {
+            let telemetry = builder.config.get_telemetry().clone();
+            builder.with_telemetry(telemetry.get_port(), telemetry.get_period());
         }
+
+        if builder.config.get_recorder().get_enabled() {
+            let recorder = builder.config.get_recorder().clone();
+            builder.with_recorder(recorder.get_path(), recorder.get_period(), recorder.get_csv());
+        }
+
+        if builder.config.get_journal().get_enabled() {
+            let path = builder.config.get_journal().get_path().to_owned();
+            builder.with_journal(path);
+        }
+
+        builder
     }
 
     pub fn get_state(&self) -> Arc<GlobalRobotState> {
         self.state.clone()
     }
 
+    pub fn get_config(&self) -> &RobotConfig {
+        &self.config
+    }
+
     pub fn with_test(&mut self) -> &mut Self {
         self.with_test_drive().with_test_dumper().with_test_ladder()
     }
 
     pub fn with_test_upper_left_intake_limit(&mut self, limit: Box<DigitalInput>) -> &mut Self {
-        self.left_upper_limit = self.make_test_limit(self.state.get_intake().get_left_actuator().get_upper().clone(), limit);
+        self.left_upper_limit = self.make_test_limit(self.state.get_intake().get_left_actuator().get_upper().clone(), limit, self.state.get_limits().get_intake_left_upper());
         self
     }
 
     pub fn with_test_lower_left_intake_limit(&mut self, limit: Box<DigitalInput>) -> &mut Self {
-        self.left_lower_limit = self.make_test_limit(self.state.get_intake().get_left_actuator().get_lower().clone(), limit);
+        self.left_lower_limit = self.make_test_limit(self.state.get_intake().get_left_actuator().get_lower().clone(), limit, self.state.get_limits().get_intake_left_lower());
         self
     }
 
     pub fn with_test_upper_right_intake_limit(&mut self, limit: Box<DigitalInput>) -> &mut Self {
-        self.right_upper_limit = self.make_test_limit(self.state.get_intake().get_right_actuator().get_upper().clone(), limit);
+        self.right_upper_limit = self.make_test_limit(self.state.get_intake().get_right_actuator().get_upper().clone(), limit, self.state.get_limits().get_intake_right_upper());
         self
     }
 
     pub fn with_test_lower_right_intake_limit(&mut self, limit: Box<DigitalInput>) -> &mut Self {
-        self.right_lower_limit = self.make_test_limit(self.state.get_intake().get_right_actuator().get_lower().clone(), limit);
+        self.right_lower_limit = self.make_test_limit(self.state.get_intake().get_right_actuator().get_lower().clone(), limit, self.state.get_limits().get_intake_right_lower());
         self
     }
 
     pub fn with_test_lower_dumper_limit(&mut self, limit: Box<DigitalInput>) -> &mut Self {
-        self.dumper_lower_limit = self.make_test_limit(self.state.get_dumper().get_lower_limit().clone(), limit);
+        self.dumper_lower_limit = self.make_test_limit(self.state.get_dumper().get_lower_limit().clone(), limit, self.state.get_limits().get_dumper_lower());
         self
     }
 
     pub fn with_test_upper_dumper_limit(&mut self, limit: Box<DigitalInput>) -> &mut Self {
-        self.dumper_upper_limit = self.make_test_limit(self.state.get_dumper().get_upper_limit().clone(), limit);
+        self.dumper_upper_limit = self.make_test_limit(self.state.get_dumper().get_upper_limit().clone(), limit, self.state.get_limits().get_dumper_upper());
         self
     }
 
     pub fn with_production(&mut self) -> &mut Self {
-        self.with_production_drive().with_production_dumper().with_production_ladder()
+        self.with_production_drive().with_production_dumper().with_production_ladder().with_production_current_sensor()
+    }
+
+    /// Uses the test mechanisms, driven by a physics simulation which moves the robot and trips
+    /// the limit switches as the commanded motors would on the real robot.
+    pub fn with_simulation(&mut self) -> &mut Self {
+        self.state.get_simulation().set_enabled(true);
+        self.simulation = Some(PhysicsSimulation::new(
+            self.state.clone(),
+            self.config.get_drive().get_odometry().clone(),
+            self.config.get_intake().get_right_actuator_pwm().is_some(),
+        ));
+        self.with_test()
     }
 
     pub fn with_bench(&mut self) {
@@ -111,38 +193,115 @@ impl RobotAssemblyBuilder {
         self.bench = Some(bench);
     }
 
+    /// Runs the controller once every `period` instead of its configured period.
+    pub fn with_controller_period(&mut self, period: Duration) -> &mut Self {
+        self.controller_period = Some(period);
+        self
+    }
+
+    /// Runs the sensor monitor once every `period` instead of its configured period.
+    pub fn with_monitor_period(&mut self, period: Duration) -> &mut Self {
+        self.monitor_period = Some(period);
+        self
+    }
+
+    /// Streams the robot state over a WebSocket on `port`, at least once every `period`. A port of
+    /// 0 lets the system pick a free one, which is reported in the robot state once it is bound.
+    pub fn with_telemetry(&mut self, port: u16, period: Duration) -> &mut Self {
+        self.telemetry = Some(TelemetryServer::new(self.state.clone(), port, period));
+        self
+    }
+
+    /// Records the robot state once every `period` into a new run directory under `path`,
+    /// optionally writing a CSV file alongside the JSON-lines file.
+    pub fn with_recorder<P: Into<PathBuf>>(&mut self, path: P, period: Duration, csv: bool) -> &mut Self {
+        self.recorder = Some(StateRecorder::new(self.state.clone(), path.into(), period, csv));
+        self
+    }
+
+    /// Journals every command handled by the controller into a new file in the folder at `path`.
+    pub fn with_journal<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.journal = Some(path.into());
+        self
+    }
+
+    /// Sets how long the controller waits for a command before the watchdog stops the robot.
+    pub fn with_watchdog_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.state.get_watchdog().set_timeout(timeout);
+        self
+    }
+
+    /// Controls the production pinouts through the sysfs tree at `root` instead of `/sys/class`.
+    ///
+    /// This must be called before any of the production mechanisms are selected.
+    pub fn with_sysfs_root<P: AsRef<Path>>(&mut self, root: P) -> &mut Self {
+        self.io = Rc::new(IoFactory::with_roots(root, self.io.get_iio_root(), self.io.get_faults()));
+        self
+    }
+
+    /// Reads the ADC through the IIO tree at `root` instead of `/sys/bus/iio/devices`.
+    ///
+    /// This must be called before the production current sensor is selected.
+    pub fn with_iio_root<P: AsRef<Path>>(&mut self, root: P) -> &mut Self {
+        self.io = Rc::new(IoFactory::with_roots(self.io.get_root(), root, self.io.get_faults()));
+        self
+    }
+
+    /// Monitors the current drawn by the robot through the ADC channel in the current config.
+    pub fn with_production_current_sensor(&mut self) -> &mut Self {
+        let config = self.config.get_current().clone();
+        let input = self.io.generate_analog_input(config.get_adc_device(), config.get_adc_channel());
+        self.current_sensor = Some(Box::new(CurrentMonitorFactory::new(self.state.get_current(), input, config)));
+        self
+    }
+
+    pub fn with_test_current_sensor(&mut self, input: Box<AnalogInput>) -> &mut Self {
+        let config = self.config.get_current().clone();
+        self.current_sensor = Some(Box::new(CurrentMonitorFactory::new(self.state.get_current(), input, config)));
+        self
+    }
+
     pub fn with_production_drive(&mut self) -> &mut Self {
-        self.drive = Box::new(ProductionDriveFactory::new(self.state.clone(), self.io.clone()));
+        let config = self.config.get_drive().clone();
+        self.drive = Box::new(ProductionDriveFactory::new(self.state.clone(), self.io.clone(), config.clone()));
+        if let (Some(left), Some(right)) = (config.get_left_encoder(), config.get_right_encoder()) {
+            self.left_encoder = self.make_encoder(self.state.get_drive().get_left_encoder(), left);
+            self.right_encoder = self.make_encoder(self.state.get_drive().get_right_encoder(), right);
+        }
         self.with_pinouts()
     }
 
     pub fn with_test_drive(&mut self) -> &mut Self {
-        self.drive = Box::new(TestDriveFactory::new(self.state.clone()));
+        self.drive = Box::new(TestDriveFactory::new(self.state.clone(), self.config.get_drive().clone()));
         self
     }
 
     pub fn with_production_dumper(&mut self) -> &mut Self {
-        self.dumper = Box::new(ProductionDumperFactory::new(self.state.clone(), self.io.clone()));
+        let config = self.config.get_dumper().clone();
+        self.dumper = Box::new(ProductionDumperFactory::new(self.state.clone(), self.io.clone(), config.clone()));
+        self.dumper_upper_limit = self.make_production_limit(self.state.get_dumper().get_upper_limit().clone(), config.get_upper_limit(), self.state.get_limits().get_dumper_upper());
+        self.dumper_lower_limit = self.make_production_limit(self.state.get_dumper().get_lower_limit().clone(), config.get_lower_limit(), self.state.get_limits().get_dumper_lower());
         self.with_pinouts()
     }
 
     pub fn with_test_dumper(&mut self) -> &mut Self {
-        self.dumper = Box::new(TestDumperFactory::new(self.state.clone()));
+        self.dumper = Box::new(TestDumperFactory::new(self.state.clone(), self.config.get_dumper().clone()));
         self
     }
 
     pub fn with_production_ladder(&mut self) -> &mut Self {
+        let config = self.config.get_intake().clone();
         self.intake = Box::new(ProductionIntakeFactory::new(
-            self.state.clone(), self.io.clone()));
-        self.left_upper_limit = self.make_production_limit(self.state.get_intake().get_left_actuator().get_upper().clone(), LEFT_UPPER_ACTUATOR_LIMIT);
-        self.right_upper_limit = self.make_production_limit(self.state.get_intake().get_right_actuator().get_upper().clone(), RIGHT_UPPER_ACTUATOR_LIMIT);
-        self.left_lower_limit = self.make_production_limit(self.state.get_intake().get_left_actuator().get_lower().clone(), LEFT_LOWER_ACTUATOR_LIMIT);
-        self.right_lower_limit = self.make_production_limit(self.state.get_intake().get_right_actuator().get_lower().clone(), RIGHT_LOWER_ACTUATOR_LIMIT);
+            self.state.clone(), self.io.clone(), config.clone()));
+        self.left_upper_limit = self.make_production_limit(self.state.get_intake().get_left_actuator().get_upper().clone(), config.get_left_upper_limit(), self.state.get_limits().get_intake_left_upper());
+        self.right_upper_limit = self.make_production_limit(self.state.get_intake().get_right_actuator().get_upper().clone(), config.get_right_upper_limit(), self.state.get_limits().get_intake_right_upper());
+        self.left_lower_limit = self.make_production_limit(self.state.get_intake().get_left_actuator().get_lower().clone(), config.get_left_lower_limit(), self.state.get_limits().get_intake_left_lower());
+        self.right_lower_limit = self.make_production_limit(self.state.get_intake().get_right_actuator().get_lower().clone(), config.get_right_lower_limit(), self.state.get_limits().get_intake_right_lower());
         self.with_pinouts()
     }
 
     pub fn with_test_ladder(&mut self) -> &mut Self {
-        self.intake = Box::new(TestIntakeFactory::new(self.state.clone()));
+        self.intake = Box::new(TestIntakeFactory::new(self.state.clone(), self.config.get_intake().clone()));
         self
     }
 
@@ -152,6 +311,10 @@ impl RobotAssemblyBuilder {
         let intake = self.intake.produce();
         let mut monitor = CompositeRunnable::new();
 
+        if let Some(simulation) = self.simulation {
+            monitor.add_runnable(Box::new(simulation));
+        }
+
         if let Some(sensor) = self.dumper_lower_limit {
             monitor.add_runnable(sensor.produce());
         }
@@ -176,7 +339,36 @@ impl RobotAssemblyBuilder {
             monitor.add_runnable(sensor.produce());
         }
 
-        RobotAssembler::new(dumper, drive, intake, self.state, self.bench, monitor)
+        if let Some(sensor) = self.current_sensor {
+            monitor.add_runnable(sensor.produce());
+        }
+
+        if let Some(sensor) = self.left_encoder {
+            monitor.add_runnable(sensor.produce());
+        }
+
+        if let Some(sensor) = self.right_encoder {
+            monitor.add_runnable(sensor.produce());
+        }
+
+        let shedder = LoadShedder::new(self.state.get_current(), self.config.get_current());
+        let shaper = InputShaper::new(self.config.get_shaping().clone(), self.state.get_precision());
+
+        let services = RobotServices {
+            controller_period: self.controller_period,
+            monitor_period: self.monitor_period,
+            bench: self.bench,
+            telemetry: self.telemetry,
+            recorder: self.recorder,
+        };
+        let options = RobotOptions {
+            shedder,
+            shaper,
+            journal: self.journal,
+            services,
+        };
+
+        RobotAssembler::new(dumper, drive, intake, self.state, monitor, options)
     }
 
     pub fn get_drive_factory(&self) -> String {
@@ -192,9 +384,12 @@ impl RobotAssemblyBuilder {
     }
 
     fn with_pinouts(&mut self) -> &mut Self {
-        if !self.get_pin_status() {
+        if self.io.get_root() != Path::new(SYSFS_ROOT) {
+            info!("Using sysfs tree at {}, skipping enable", self.io.get_root().display());
+        } else if !self.get_pin_status() {
             if enable_pins().is_err() {
                 error!("Failed to enable pins!");
+                self.state.get_faults().raise("enable_pins", Subsystem::Pinouts, Severity::Error, "Failed to enable the pwms");
             } else {
                 info!("Enabled pins!");
                 self.pin_enabled_status = true;
@@ -205,13 +400,20 @@ impl RobotAssemblyBuilder {
         self
     }
 
-    fn make_production_limit(&self, state: Arc<AtomicBool>, pin: Pin) -> Option<Box<SubsystemFactory<Box<Runnable>>>> {
+    fn make_production_limit(&self, state: Arc<AtomicBool>, pin: Pin, debounce: Arc<GlobalInputState>) -> Option<Box<SubsystemFactory<Box<Runnable>>>> {
         Some(Box::new(DigitalMonitorFactory::new(state,
-                                                 self.io.generate_digital_input(pin))))
+                                                 self.io.generate_digital_input(pin),
+                                                 debounce)))
     }
 
-    fn make_test_limit(&self, state: Arc<AtomicBool>, input: Box<DigitalInput>) -> Option<Box<SubsystemFactory<Box<Runnable>>>> {
-        Some(Box::new(DigitalMonitorFactory::new(state, input)))
+    fn make_encoder(&self, state: Arc<GlobalEncoderState>, config: EncoderConfig) -> Option<Box<SubsystemFactory<Box<Runnable>>>> {
+        Some(Box::new(EncoderMonitorFactory::new(state,
+                                                 self.io.generate_digital_input(config.get_a()),
+                                                 self.io.generate_digital_input(config.get_b()))))
+    }
+
+    fn make_test_limit(&self, state: Arc<AtomicBool>, input: Box<DigitalInput>, debounce: Arc<GlobalInputState>) -> Option<Box<SubsystemFactory<Box<Runnable>>>> {
+        Some(Box::new(DigitalMonitorFactory::new(state, input, debounce)))
     }
 
     fn get_pin_status(&self) -> bool {
@@ -221,6 +423,10 @@ impl RobotAssemblyBuilder {
 
 #[cfg(test)]
 mod tests {
+    use crate::robot_config::ControllerConfig;
+    use crate::robot_config::DebounceConfig;
+    use crate::robot_config::LimitsConfig;
+
     use super::*;
 
     #[test]
@@ -251,4 +457,24 @@ mod tests {
         assert_eq!("print intake", builder.get_intake_factory());
         assert_eq!(false, builder.get_pin_status());
     }
+
+    #[test]
+    fn global_simulation() {
+        let mut builder = RobotAssemblyBuilder::new();
+        builder.with_simulation();
+        assert_eq!("test drive", builder.get_drive_factory());
+        assert_eq!(true, builder.get_state().get_current_state().get_simulation().is_some());
+    }
+
+    #[test]
+    fn from_config() {
+        let config = RobotConfig::default()
+            .with_controller(ControllerConfig::default().with_watchdog_timeout_millis(250).with_kill_on_critical_fault(true))
+            .with_limits(LimitsConfig::default().with_dumper_lower(DebounceConfig::default().with_samples(4)));
+        let builder = RobotAssemblyBuilder::from_config(config);
+        assert_eq!(Duration::from_millis(250), builder.get_state().get_watchdog().get_timeout());
+        assert_eq!(4, builder.get_state().get_limits().get_dumper_lower().get_samples());
+        assert_eq!(true, builder.get_state().get_faults().get_kill_on_critical());
+        assert_eq!("print drive", builder.get_drive_factory());
+    }
 }
\ No newline at end of file
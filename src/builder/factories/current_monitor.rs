@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use crate::builder::factories::SubsystemFactory;
+use crate::framework::Runnable;
+use crate::pinouts::analog::input::AnalogInput;
+use crate::robot_config::CurrentConfig;
+use crate::sensors::current::CurrentMonitor;
+use crate::status::current::GlobalCurrentState;
+
+pub struct CurrentMonitorFactory {
+    state: Arc<GlobalCurrentState>,
+    input: Box<AnalogInput>,
+    config: CurrentConfig,
+}
+
+impl CurrentMonitorFactory {
+    pub fn new(state: Arc<GlobalCurrentState>, input: Box<AnalogInput>, config: CurrentConfig) -> Self {
+        Self {
+            state,
+            input,
+            config,
+        }
+    }
+}
+
+impl ToString for CurrentMonitorFactory {
+    fn to_string(&self) -> String {
+        "current monitor".to_owned()
+    }
+}
+
+impl SubsystemFactory<Box<Runnable>> for CurrentMonitorFactory {
+    fn produce(self: Box<Self>) -> Box<Runnable> {
+        Box::new(CurrentMonitor::new(self.input, self.state, &self.config))
+    }
+}
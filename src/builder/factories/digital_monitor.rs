@@ -5,17 +5,20 @@ use crate::builder::factories::SubsystemFactory;
 use crate::framework::Runnable;
 use crate::pinouts::digital::input::DigitalInput;
 use crate::sensors::digital::DigitalInputMonitor;
+use crate::status::limits::GlobalInputState;
 
 pub struct DigitalMonitorFactory {
     update_field: Arc<AtomicBool>,
     input: Box<DigitalInput>,
+    debounce: Arc<GlobalInputState>,
 }
 
 impl DigitalMonitorFactory {
-    pub fn new(state: Arc<AtomicBool>, input: Box<DigitalInput>) -> Self {
+    pub fn new(state: Arc<AtomicBool>, input: Box<DigitalInput>, debounce: Arc<GlobalInputState>) -> Self {
         Self {
             update_field: state,
             input,
+            debounce,
         }
     }
 }
@@ -29,6 +32,6 @@ impl ToString for DigitalMonitorFactory {
 
 impl SubsystemFactory<Box<Runnable>> for DigitalMonitorFactory {
     fn produce(self: Box<Self>) -> Box<Runnable> {
-        Box::new(DigitalInputMonitor::new(self.input, self.update_field))
+        Box::new(DigitalInputMonitor::new(self.input, self.update_field, self.debounce))
     }
 }
\ No newline at end of file
@@ -3,48 +3,57 @@ use std::sync::Arc;
 
 use crate::builder::factories::SubsystemFactory;
 use crate::mechatronics::drive_train::DriveTrain;
+use crate::motor_controllers::decorators::pid::PidSpeedMotor;
+use crate::motor_controllers::decorators::ramp::RampedMotor;
 use crate::motor_controllers::hover_board::HoverBoardMotor;
+use crate::motor_controllers::MotorController;
 use crate::motor_controllers::motor_group::MotorGroup;
 use crate::motor_controllers::print_motor::PrintMotor;
 use crate::motor_controllers::test_motor::TestMotor;
 use crate::pinouts::factories::IoFactory;
-use crate::robot_map::*;
+use crate::robot_config::DriveConfig;
 use crate::status::robot_state::GlobalRobotState;
 
 pub struct ProductionDriveFactory {
     state: Arc<GlobalRobotState>,
     io: Rc<IoFactory>,
+    config: DriveConfig,
 }
 
 pub struct TestDriveFactory {
-    state: Arc<GlobalRobotState>
+    state: Arc<GlobalRobotState>,
+    config: DriveConfig,
 }
 
 pub struct PrintDriveFactory {
-    state: Arc<GlobalRobotState>
+    state: Arc<GlobalRobotState>,
+    config: DriveConfig,
 }
 
 impl ProductionDriveFactory {
-    pub fn new(state: Arc<GlobalRobotState>, io: Rc<IoFactory>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, io: Rc<IoFactory>, config: DriveConfig) -> Self {
         Self {
             state,
             io,
+            config,
         }
     }
 }
 
 impl TestDriveFactory {
-    pub fn new(state: Arc<GlobalRobotState>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, config: DriveConfig) -> Self {
         Self {
-            state
+            state,
+            config,
         }
     }
 }
 
 impl PrintDriveFactory {
-    pub fn new(state: Arc<GlobalRobotState>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, config: DriveConfig) -> Self {
         Self {
-            state
+            state,
+            config,
         }
     }
 }
@@ -70,26 +79,36 @@ impl ToString for PrintDriveFactory {
 impl SubsystemFactory<DriveTrain> for ProductionDriveFactory {
     fn produce(self: Box<Self>) -> DriveTrain {
         let io_factory = &self.io;
+        let config = &self.config;
 
-        let left_front_pwm = io_factory.generate_analog_output(FRONT_LEFT_PWM_CHIP, FRONT_LEFT_PWM_NUMBER);
-        let right_front_pwm = io_factory.generate_analog_output(FRONT_RIGHT_PWM_CHIP, FRONT_RIGHT_PWM_NUMBER);
-        let left_rear_pwm = io_factory.generate_analog_output(REAR_LEFT_PWM_CHIP, REAR_LEFT_PWM_NUMBER);
-        let right_rear_pwm = io_factory.generate_analog_output(REAR_RIGHT_PWM_CHIP, REAR_RIGHT_PWM_NUMBER);
+        let left_front_pwm = io_factory.generate_analog_output(config.get_front_left_pwm().get_chip(), config.get_front_left_pwm().get_num());
+        let right_front_pwm = io_factory.generate_analog_output(config.get_front_right_pwm().get_chip(), config.get_front_right_pwm().get_num());
+        let left_rear_pwm = io_factory.generate_analog_output(config.get_rear_left_pwm().get_chip(), config.get_rear_left_pwm().get_num());
+        let right_rear_pwm = io_factory.generate_analog_output(config.get_rear_right_pwm().get_chip(), config.get_rear_right_pwm().get_num());
 
-        let front_right_direction = io_factory.generate_digital_output(FRONT_RIGHT_DIRECTION);
-        let front_left_direction = io_factory.generate_digital_output(FRONT_LEFT_DIRECTION);
-        let rear_right_direction = io_factory.generate_digital_output(REAR_RIGHT_DIRECTION);
-        let rear_left_direction = io_factory.generate_digital_output(REAR_LEFT_DIRECTION);
+        let front_right_direction = io_factory.generate_digital_output(config.get_front_right_direction());
+        let front_left_direction = io_factory.generate_digital_output(config.get_front_left_direction());
+        let rear_right_direction = io_factory.generate_digital_output(config.get_rear_right_direction());
+        let rear_left_direction = io_factory.generate_digital_output(config.get_rear_left_direction());
 
         let front_right_motor = Box::new(HoverBoardMotor::new(right_front_pwm, front_right_direction));
         let front_left_motor = Box::new(HoverBoardMotor::new(left_front_pwm, front_left_direction));
         let rear_right_motor = Box::new(HoverBoardMotor::new(right_rear_pwm, rear_right_direction));
         let rear_left_motor = Box::new(HoverBoardMotor::new(left_rear_pwm, rear_left_direction));
 
-        let left_drive = Box::new(MotorGroup::new(vec![front_left_motor, rear_left_motor], self.state.get_drive().get_left()));
-        let right_drive = Box::new(MotorGroup::new(vec![front_right_motor, rear_right_motor], self.state.get_drive().get_right()));
+        let mut left_drive: Box<MotorController> = Box::new(MotorGroup::new(vec![front_left_motor, rear_left_motor], self.state.get_drive().get_left()));
+        let mut right_drive: Box<MotorController> = Box::new(MotorGroup::new(vec![front_right_motor, rear_right_motor], self.state.get_drive().get_right()));
+
+        // The encoders themselves are sampled by the monitor, which the builder sets up alongside.
+        if config.get_left_encoder().is_some() && config.get_right_encoder().is_some() {
+            left_drive = Box::new(PidSpeedMotor::new(left_drive, self.state.get_drive().get_left_encoder(), config.get_speed_control().clone()));
+            right_drive = Box::new(PidSpeedMotor::new(right_drive, self.state.get_drive().get_right_encoder(), config.get_speed_control().clone()));
+        }
+
+        let left_drive = Box::new(RampedMotor::new(left_drive, config.get_acceleration(), config.get_deceleration()));
+        let right_drive = Box::new(RampedMotor::new(right_drive, config.get_acceleration(), config.get_deceleration()));
 
-        DriveTrain::new(self.state.get_drive(), left_drive, right_drive, self.state.get_life())
+        DriveTrain::new(self.state.get_drive(), left_drive, right_drive, self.state.get_life(), self.config.clone())
     }
 }
 
@@ -99,7 +118,7 @@ impl SubsystemFactory<DriveTrain> for TestDriveFactory {
         let left_motor = Box::new(TestMotor::new(state.get_drive().get_left()));
         let right_motor = Box::new(TestMotor::new(state.get_drive().get_right()));
 
-        DriveTrain::new(self.state.get_drive(), left_motor, right_motor, self.state.get_life())
+        DriveTrain::new(self.state.get_drive(), left_motor, right_motor, self.state.get_life(), self.config.clone())
     }
 }
 
@@ -112,6 +131,6 @@ impl SubsystemFactory<DriveTrain> for PrintDriveFactory {
         let left_group = Box::new(MotorGroup::new(vec![left_motor], state.get_drive().get_left()));
         let right_group = Box::new(MotorGroup::new(vec![right_motor], state.get_drive().get_right()));
 
-        DriveTrain::new(self.state.get_drive(), left_group, right_group, self.state.get_life())
+        DriveTrain::new(self.state.get_drive(), left_group, right_group, self.state.get_life(), self.config.clone())
     }
 }
\ No newline at end of file
@@ -8,44 +8,49 @@ use crate::motor_controllers::print_motor::PrintMotor;
 use crate::motor_controllers::roboclaw::RoboClaw;
 use crate::motor_controllers::test_motor::TestMotor;
 use crate::pinouts::factories::IoFactory;
-use crate::robot_map::DUMPER_PWM_CHIP;
-use crate::robot_map::DUMPER_PWM_NUM;
+use crate::robot_config::DumperConfig;
 use crate::status::robot_state::GlobalRobotState;
 
 pub struct ProductionDumperFactory {
     state: Arc<GlobalRobotState>,
     io: Rc<IoFactory>,
+    config: DumperConfig,
 }
 
 pub struct TestDumperFactory {
-    state: Arc<GlobalRobotState>
+    state: Arc<GlobalRobotState>,
+    config: DumperConfig,
 }
 
 pub struct PrintDumperFactory {
-    state: Arc<GlobalRobotState>
+    state: Arc<GlobalRobotState>,
+    config: DumperConfig,
 }
 
 impl ProductionDumperFactory {
-    pub fn new(state: Arc<GlobalRobotState>, io: Rc<IoFactory>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, io: Rc<IoFactory>, config: DumperConfig) -> Self {
         Self {
             state,
             io,
+            config,
         }
     }
 }
 
 impl TestDumperFactory {
-    pub fn new(state: Arc<GlobalRobotState>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, config: DumperConfig) -> Self {
         Self {
-            state
+            state,
+            config,
         }
     }
 }
 
 impl PrintDumperFactory {
-    pub fn new(state: Arc<GlobalRobotState>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, config: DumperConfig) -> Self {
         Self {
-            state
+            state,
+            config,
         }
     }
 }
@@ -71,10 +76,10 @@ impl ToString for PrintDumperFactory {
 impl SubsystemFactory<Dumper> for ProductionDumperFactory {
     fn produce(self: Box<Self>) -> Dumper {
         let state = &self.state;
-        let pwm = self.io.generate_pwm(DUMPER_PWM_CHIP, DUMPER_PWM_NUM);
+        let pwm = self.io.generate_pwm(self.config.get_pwm().get_chip(), self.config.get_pwm().get_num());
         let dumper_motor = Box::new(RoboClaw::new(pwm, state.get_dumper().get_motor()));
 
-        Dumper::new(state.get_life(), dumper_motor, state.get_dumper())
+        Dumper::new(state.get_life(), dumper_motor, state.get_dumper(), self.config.clone())
     }
 }
 
@@ -83,7 +88,7 @@ impl SubsystemFactory<Dumper> for TestDumperFactory {
         let state = &self.state;
         let dumper_motor = Box::new(TestMotor::new(state.get_dumper().get_motor()));
 
-        Dumper::new(state.get_life(), dumper_motor, state.get_dumper())
+        Dumper::new(state.get_life(), dumper_motor, state.get_dumper(), self.config.clone())
     }
 }
 
@@ -94,6 +99,6 @@ impl SubsystemFactory<Dumper> for PrintDumperFactory {
 
         let dumper_group = Box::new(MotorGroup::new(vec![dumper_motor], state.get_dumper().get_motor()));
 
-        Dumper::new(state.get_life(), dumper_group, state.get_dumper())
+        Dumper::new(state.get_life(), dumper_group, state.get_dumper(), self.config.clone())
     }
 }
\ No newline at end of file
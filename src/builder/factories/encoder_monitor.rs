@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use crate::builder::factories::SubsystemFactory;
+use crate::framework::Runnable;
+use crate::pinouts::digital::input::DigitalInput;
+use crate::pinouts::encoder::GlobalEncoderState;
+use crate::pinouts::encoder::quadrature::QuadratureEncoder;
+use crate::sensors::encoder::EncoderMonitor;
+
+pub struct EncoderMonitorFactory {
+    state: Arc<GlobalEncoderState>,
+    a: Box<DigitalInput>,
+    b: Box<DigitalInput>,
+}
+
+impl EncoderMonitorFactory {
+    pub fn new(state: Arc<GlobalEncoderState>, a: Box<DigitalInput>, b: Box<DigitalInput>) -> Self {
+        Self {
+            state,
+            a,
+            b,
+        }
+    }
+}
+
+impl ToString for EncoderMonitorFactory {
+    fn to_string(&self) -> String {
+        "quadrature encoder monitor".to_owned()
+    }
+}
+
+impl SubsystemFactory<Box<Runnable>> for EncoderMonitorFactory {
+    fn produce(self: Box<Self>) -> Box<Runnable> {
+        Box::new(EncoderMonitor::new(Box::new(QuadratureEncoder::new(self.a, self.b)), self.state))
+    }
+}
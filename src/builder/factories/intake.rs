@@ -2,52 +2,57 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use crate::builder::factories::SubsystemFactory;
+use crate::mechatronics::bucket_ladder::ActuatorLayout;
 use crate::mechatronics::bucket_ladder::Intake;
+use crate::motor_controllers::decorators::stall::StallDetectingMotor;
 use crate::motor_controllers::motor_group::MotorGroup;
 use crate::motor_controllers::print_motor::PrintMotor;
 use crate::motor_controllers::roboclaw::RoboClaw;
 use crate::motor_controllers::test_motor::TestMotor;
 use crate::pinouts::factories::IoFactory;
-use crate::robot_map::ACTUATOR_PWM_CHIP;
-use crate::robot_map::ACTUATOR_PWM_NUM;
-use crate::robot_map::DIGGER_PWM_CHIP;
-use crate::robot_map::DIGGER_PWM_NUM;
+use crate::robot_config::IntakeConfig;
 use crate::status::robot_state::GlobalRobotState;
 
 pub struct ProductionIntakeFactory {
     state: Arc<GlobalRobotState>,
     io: Rc<IoFactory>,
+    config: IntakeConfig,
 }
 
 pub struct TestIntakeFactory {
-    state: Arc<GlobalRobotState>
+    state: Arc<GlobalRobotState>,
+    config: IntakeConfig,
 }
 
 pub struct PrintIntakeFactory {
-    state: Arc<GlobalRobotState>
+    state: Arc<GlobalRobotState>,
+    config: IntakeConfig,
 }
 
 impl ProductionIntakeFactory {
-    pub fn new(state: Arc<GlobalRobotState>, io: Rc<IoFactory>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, io: Rc<IoFactory>, config: IntakeConfig) -> Self {
         Self {
             state,
             io,
+            config,
         }
     }
 }
 
 impl TestIntakeFactory {
-    pub fn new(state: Arc<GlobalRobotState>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, config: IntakeConfig) -> Self {
         Self {
-            state
+            state,
+            config,
         }
     }
 }
 
 impl PrintIntakeFactory {
-    pub fn new(state: Arc<GlobalRobotState>) -> Self {
+    pub fn new(state: Arc<GlobalRobotState>, config: IntakeConfig) -> Self {
         Self {
-            state
+            state,
+            config,
         }
     }
 }
@@ -73,12 +78,41 @@ impl ToString for PrintIntakeFactory {
 impl SubsystemFactory<Intake> for ProductionIntakeFactory {
     fn produce(self: Box<Self>) -> Intake {
         let state = &self.state;
-        let digger_pwm = self.io.generate_pwm(DIGGER_PWM_CHIP, DIGGER_PWM_NUM);
-        let left_pwm = self.io.generate_pwm(ACTUATOR_PWM_CHIP, ACTUATOR_PWM_NUM);
+        let config = &self.config;
+        let digger_pwm = self.io.generate_pwm(config.get_digger_pwm().get_chip(), config.get_digger_pwm().get_num());
+        let left_pwm = self.io.generate_pwm(config.get_actuator_pwm().get_chip(), config.get_actuator_pwm().get_num());
         let digger_motor = Box::new(RoboClaw::new(digger_pwm, state.get_intake().get_digger()));
         let actuator = Box::new(RoboClaw::new(left_pwm, state.get_intake().get_actuator()));
 
-        Intake::new(digger_motor, actuator, state.get_intake(), state.get_life())
+        let digger_stall = config.get_digger_stall();
+        let digger_sensor = digger_stall.get_adc_channel()
+            .map(|channel| self.io.generate_analog_input(digger_stall.get_adc_device(), channel));
+        let digger_motor = Box::new(StallDetectingMotor::new(digger_motor, digger_sensor, digger_stall.clone()));
+
+        let actuator_stall = config.get_actuator_stall();
+        let actuator_sensor = actuator_stall.get_adc_channel()
+            .map(|channel| self.io.generate_analog_input(actuator_stall.get_adc_device(), channel));
+        let actuator = Box::new(StallDetectingMotor::new(actuator, actuator_sensor, actuator_stall.clone()));
+
+        let actuator_position = config.get_actuator_position();
+        let potentiometer = actuator_position.get_adc_channel()
+            .map(|channel| self.io.generate_analog_input(actuator_position.get_adc_device(), channel));
+
+        let actuators = match config.get_right_actuator_pwm() {
+            Some(right_pwm) => {
+                let right_pwm = self.io.generate_pwm(right_pwm.get_chip(), right_pwm.get_num());
+                let right = Box::new(RoboClaw::new(right_pwm, state.get_intake().get_right_actuator_motor()));
+                let right_sensor = actuator_stall.get_right_adc_channel()
+                    .map(|channel| self.io.generate_analog_input(actuator_stall.get_adc_device(), channel));
+                let right = Box::new(StallDetectingMotor::new(right, right_sensor, actuator_stall.clone()));
+                let right_potentiometer = actuator_position.get_right_adc_channel()
+                    .map(|channel| self.io.generate_analog_input(actuator_position.get_adc_device(), channel));
+                ActuatorLayout::Dual { left: actuator, right, left_potentiometer: potentiometer, right_potentiometer }
+            }
+            None => ActuatorLayout::Single { motor: actuator, potentiometer },
+        };
+
+        Intake::new(digger_motor, actuators, state.get_intake(), state.get_life(), self.config.clone())
     }
 }
 
@@ -90,7 +124,18 @@ impl SubsystemFactory<Intake> for TestIntakeFactory {
 
         let digger_group = Box::new(MotorGroup::new(vec![digger_motor], state.get_intake().get_digger()));
 
-        Intake::new(digger_group, left_actuator, state.get_intake(), state.get_life())
+        let digger = Box::new(StallDetectingMotor::new(digger_group, None, self.config.get_digger_stall().clone()));
+        let actuator = Box::new(StallDetectingMotor::new(left_actuator, None, self.config.get_actuator_stall().clone()));
+
+        let actuators = if self.config.get_right_actuator_pwm().is_some() {
+            let right_actuator = Box::new(TestMotor::new(state.get_intake().get_right_actuator_motor()));
+            let right = Box::new(StallDetectingMotor::new(right_actuator, None, self.config.get_actuator_stall().clone()));
+            ActuatorLayout::Dual { left: actuator, right, left_potentiometer: None, right_potentiometer: None }
+        } else {
+            ActuatorLayout::Single { motor: actuator, potentiometer: None }
+        };
+
+        Intake::new(digger, actuators, state.get_intake(), state.get_life(), self.config.clone())
     }
 }
 
@@ -98,10 +143,17 @@ impl SubsystemFactory<Intake> for PrintIntakeFactory {
     fn produce(self: Box<Self>) -> Intake {
         let state = &self.state;
         let digger_motor = Box::new(PrintMotor::new("Digger", state.get_intake().get_digger()));
-        let left_actuator = Box::new(PrintMotor::new("Actuators", state.get_intake().get_actuator()));
-
         let digger_group = Box::new(MotorGroup::new(vec![digger_motor], state.get_intake().get_digger()));
 
-        Intake::new(digger_group, left_actuator, state.get_intake(), state.get_life())
+        let actuators = if self.config.get_right_actuator_pwm().is_some() {
+            let left = Box::new(PrintMotor::new("Left Actuator", state.get_intake().get_actuator()));
+            let right = Box::new(PrintMotor::new("Right Actuator", state.get_intake().get_right_actuator_motor()));
+            ActuatorLayout::Dual { left, right, left_potentiometer: None, right_potentiometer: None }
+        } else {
+            let motor = Box::new(PrintMotor::new("Actuators", state.get_intake().get_actuator()));
+            ActuatorLayout::Single { motor, potentiometer: None }
+        };
+
+        Intake::new(digger_group, actuators, state.get_intake(), state.get_life(), self.config.clone())
     }
 }
\ No newline at end of file
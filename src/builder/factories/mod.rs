@@ -2,6 +2,8 @@ pub mod drive;
 pub mod dumper;
 pub mod intake;
 pub mod digital_monitor;
+pub mod current_monitor;
+pub mod encoder_monitor;
 
 pub trait SubsystemFactory<T>: ToString {
     fn produce(self: Box<Self>) -> T;
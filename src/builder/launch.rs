@@ -1,26 +1,50 @@
+use std::sync::Arc;
+use std::sync::mpsc::SyncSender;
 use std::thread;
+use std::time::Duration;
 
 use rocket::Rocket;
 
 use crate::benchmarking::ControllerBench;
 use crate::framework::{Runnable, CompositeRunnable};
 use crate::mechatronics::controller::RobotController;
+use crate::mechatronics::RobotMessage;
 use crate::builder::robot::Robot;
+use crate::comms::telemetry::TelemetryServer;
+use crate::recording::StateRecorder;
+use crate::status::robot_state::GlobalRobotState;
+
+/// How often the loops run, and the optional services which run in threads of their own alongside
+/// them.
+pub struct RobotServices {
+    /// How often the controller runs, or `None` to run it as fast as possible.
+    pub controller_period: Option<Duration>,
+    /// How often the sensor monitor runs, or `None` to run it as fast as possible.
+    pub monitor_period: Option<Duration>,
+    pub bench: Option<ControllerBench>,
+    pub telemetry: Option<TelemetryServer>,
+    pub recorder: Option<StateRecorder>,
+}
 
 pub struct RobotLauncher {
     controller: RobotController,
+    sender: SyncSender<RobotMessage>,
     bfr: Rocket,
-    bench: Option<ControllerBench>,
-    monitor: CompositeRunnable
+    monitor: CompositeRunnable,
+    state: Arc<GlobalRobotState>,
+    services: RobotServices,
 }
 
 impl RobotLauncher {
-    pub fn new(controller: RobotController, bfr: Rocket, bench: Option<ControllerBench>, monitor: CompositeRunnable) -> Self {
+    pub fn new(controller: RobotController, sender: SyncSender<RobotMessage>, bfr: Rocket, monitor: CompositeRunnable,
+               state: Arc<GlobalRobotState>, services: RobotServices) -> Self {
         Self {
             controller,
+            sender,
             bfr,
-            bench,
             monitor,
+            state,
+            services,
         }
     }
 
@@ -29,13 +53,36 @@ impl RobotLauncher {
     pub fn launch(self) -> Robot {
         let mut controller = self.controller;
         let mut monitor = self.monitor;
-        let controller_thread = thread::Builder::new().name("Controller Thread".to_string()).spawn(move || controller.start()).unwrap();
-        let bench_thread = self.bench.map(|bench| thread::Builder::new().name("Bench Thread".to_string()).spawn(move || {
+        let services = self.services;
+        let controller_period = services.controller_period;
+        let controller_timing = self.state.get_controller_timing();
+        let controller_thread = thread::Builder::new().name("Controller Thread".to_string()).spawn(move || {
+            match controller_period {
+                Some(period) => controller.start_fixed_rate(period, controller_timing),
+                None => controller.start(),
+            }
+        }).unwrap();
+        let bench_thread = services.bench.map(|bench| thread::Builder::new().name("Bench Thread".to_string()).spawn(move || {
             bench.launch();
         }).unwrap());
 
-        let monitor_thread = thread::Builder::new().name("Monitor Thread".to_string()).spawn(move || monitor.start()).unwrap();
+        let monitor_period = services.monitor_period;
+        let monitor_timing = self.state.get_monitor_timing();
+        let monitor_thread = thread::Builder::new().name("Monitor Thread".to_string()).spawn(move || {
+            match monitor_period {
+                Some(period) => monitor.start_fixed_rate(period, monitor_timing),
+                None => monitor.start(),
+            }
+        }).unwrap();
+
+        let telemetry_thread = services.telemetry.map(|telemetry| thread::Builder::new().name("Telemetry Thread".to_string()).spawn(move || {
+            telemetry.launch();
+        }).unwrap());
+
+        let recorder_thread = services.recorder.map(|recorder| thread::Builder::new().name("Recorder Thread".to_string()).spawn(move || {
+            recorder.launch();
+        }).unwrap());
 
-        Robot::new(controller_thread, self.bfr, bench_thread, monitor_thread)
+        Robot::new(controller_thread, self.sender, self.bfr, bench_thread, monitor_thread, telemetry_thread, recorder_thread)
     }
 }
\ No newline at end of file
@@ -1,27 +1,45 @@
+use std::sync::mpsc::SyncSender;
 use std::thread;
 use std::thread::JoinHandle;
 
 use rocket::local::Client;
 use rocket::Rocket;
 
+use crate::mechatronics::CommandSource;
+use crate::mechatronics::RobotMessage;
+use crate::mechatronics::RobotMessenger;
+
 
 pub struct Robot {
     controller: JoinHandle<()>,
+    sender: SyncSender<RobotMessage>,
     bfr: Rocket,
     _bench: Option<JoinHandle<()>>,
     _monitor: JoinHandle<()>,
+    _telemetry: Option<JoinHandle<()>>,
+    _recorder: Option<JoinHandle<()>>,
 }
 
 impl Robot {
-    pub fn new(controller: JoinHandle<()>, bfr: Rocket, bench: Option<JoinHandle<()>>, monitor: JoinHandle<()>) -> Self {
+    pub fn new(controller: JoinHandle<()>, sender: SyncSender<RobotMessage>, bfr: Rocket, bench: Option<JoinHandle<()>>, monitor: JoinHandle<()>,
+               telemetry: Option<JoinHandle<()>>, recorder: Option<JoinHandle<()>>) -> Self {
         Self {
             controller,
+            sender,
             bfr,
             _bench: bench,
             _monitor: monitor,
+            _telemetry: telemetry,
+            _recorder: recorder,
         }
     }
 
+    /// Returns a messenger which sends commands straight to the controller, tagged as coming from
+    /// `source`.
+    pub fn get_messenger(&self, source: CommandSource) -> RobotMessenger {
+        RobotMessenger::with_source(self.sender.clone(), source)
+    }
+
     pub fn engage_production_server(self) {
         let bfr = self.bfr;
         let _rocket_thread = thread::Builder::new().name("Rocket Thread".to_string()).spawn(move || bfr.launch()).unwrap();
@@ -9,10 +9,16 @@ use rocket::State;
 use rocket_contrib::json::Json;
 
 use crate::mechatronics::commands::RobotCommandFactory;
+use crate::mechatronics::controller::MechState;
+use crate::mechatronics::routines::RoutineId;
 use crate::mechatronics::RobotMessenger;
+use crate::status::faults::Fault;
 use crate::status::robot_state::GlobalRobotState;
 use crate::status::robot_state::RobotStateInstance;
 
+/// Contains the WebSocket server which streams the robot state to the driver station.
+pub mod telemetry;
+
 #[cfg(test)]
 mod tests;
 
@@ -24,6 +30,8 @@ pub fn stage(messenger: RobotMessenger, state: Arc<GlobalRobotState>, command_fa
         .manage(command_factory)
         .mount("/",
                routes![get_state,
+                              get_faults,
+                              delete_fault,
                               index,
                               files,
                               favicon,
@@ -31,16 +39,11 @@ pub fn stage(messenger: RobotMessenger, state: Arc<GlobalRobotState>, command_fa
                               put_drive,
                               put_intake,
                               put_dumper,
+                              put_heartbeat,
+                              put_routine,
                               ])
 }
 
-#[derive(Deserialize, Serialize)]
-pub enum RobotMode {
-    Digging,
-    Driving,
-    Dumping,
-}
-
 #[derive(Serialize, Deserialize)]
 pub enum RobotLifeRestId {
     Alive,
@@ -49,14 +52,19 @@ pub enum RobotLifeRestId {
 
 #[derive(Deserialize, Serialize)]
 struct RobotPutRequest {
-    mode: Option<RobotMode>,
+    mode: Option<MechState>,
     life: Option<RobotLifeRestId>,
+    precision: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub enum DriveTrainAction {
     Drive { left: f32, right: f32 },
+    Arcade { throttle: f32, turn: f32 },
+    Curvature { throttle: f32, curvature: f32 },
     Brake,
+    /// Makes the robot's current position the origin of its pose.
+    ResetPose,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -70,6 +78,10 @@ pub enum ActuatorAction {
     Raise,
     Lower,
     Stop,
+    /// Moves the actuators to `fraction` of their stroke from the upper limit, and stops them there.
+    MoveTo { fraction: f32 },
+    /// Allows the actuators to move again after a fault stopped them.
+    ClearFault,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -83,11 +95,23 @@ pub enum DumperAction {
     Reset,
     Dump,
     Stop,
+    /// Dumps, waits for the material to fall out and resets, all without further input.
+    Cycle,
+    /// Allows the dumper to move again after a fault stopped it.
+    ClearFault,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum RoutineAction {
+    Start(RoutineId),
+    Pause,
+    Resume,
+    Cancel,
 }
 
 #[put("/robot", format = "application/json", data = "<robot>")]
 fn put_robot(robot: Json<RobotPutRequest>, messenger: State<RobotMessenger>, factory: State<RobotCommandFactory>) {
-    let RobotPutRequest { mode: mode_opt, life: life_opt } = robot.into_inner();
+    let RobotPutRequest { mode: mode_opt, life: life_opt, precision: precision_opt } = robot.into_inner();
 
     if let Some(life) = life_opt {
         match life {
@@ -97,12 +121,12 @@ fn put_robot(robot: Json<RobotPutRequest>, messenger: State<RobotMessenger>, fac
     };
 
     if let Some(mode) = mode_opt {
-        match mode {
-            RobotMode::Digging => messenger.send_command(Box::new(factory.generate_intake_switch_command())),
-            RobotMode::Driving => messenger.send_command(Box::new(factory.generate_drive_switch_command())),
-            RobotMode::Dumping => messenger.send_command(Box::new(factory.generate_dumper_switch_command())),
-        }
+        messenger.send_command(Box::new(factory.generate_switch_mode_command(mode)));
     };
+
+    if let Some(precision) = precision_opt {
+        messenger.send_command(Box::new(factory.generate_set_precision_command(precision)));
+    }
 }
 
 #[put("/robot/drive", format = "application/json", data = "<action>")]
@@ -116,15 +140,35 @@ fn put_drive(action: Json<DriveTrainAction>, messenger: State<RobotMessenger>, f
                 Status::BadRequest
             }
         }
+        DriveTrainAction::Arcade { throttle, turn } => {
+            if let Some(command) = factory.generate_arcade_drive_command(throttle, turn) {
+                messenger.send_command(Box::new(command));
+                Status::Ok
+            } else {
+                Status::BadRequest
+            }
+        }
+        DriveTrainAction::Curvature { throttle, curvature } => {
+            if let Some(command) = factory.generate_curvature_drive_command(throttle, curvature) {
+                messenger.send_command(Box::new(command));
+                Status::Ok
+            } else {
+                Status::BadRequest
+            }
+        }
         DriveTrainAction::Brake => {
             messenger.send_command(Box::new(factory.generate_brake_command()));
             Status::Ok
         }
+        DriveTrainAction::ResetPose => {
+            messenger.send_command(Box::new(factory.generate_reset_pose_command()));
+            Status::Ok
+        }
     }
 }
 
 #[put("/robot/intake", format = "application/json", data = "<request>")]
-fn put_intake(request: Json<IntakePutRequest>, messenger: State<RobotMessenger>, factory: State<RobotCommandFactory>) {
+fn put_intake(request: Json<IntakePutRequest>, messenger: State<RobotMessenger>, factory: State<RobotCommandFactory>) -> Status {
     let IntakePutRequest { digger: digger_opt, actuator: actuator_opt } = request.into_inner();
 
     if let Some(actuator) = actuator_opt {
@@ -133,6 +177,11 @@ fn put_intake(request: Json<IntakePutRequest>, messenger: State<RobotMessenger>,
                 ActuatorAction::Raise => Box::new(factory.generate_raise_actuators_command()),
                 ActuatorAction::Lower => Box::new(factory.generate_lower_actuators_command()),
                 ActuatorAction::Stop => Box::new(factory.generate_stop_actuators_command()),
+                ActuatorAction::MoveTo { fraction } => match factory.generate_move_actuators_command(fraction) {
+                    Some(command) => Box::new(command),
+                    None => return Status::BadRequest,
+                },
+                ActuatorAction::ClearFault => Box::new(factory.generate_clear_actuator_fault_command()),
             });
     };
 
@@ -143,6 +192,8 @@ fn put_intake(request: Json<IntakePutRequest>, messenger: State<RobotMessenger>,
                 DiggerAction::Stop => Box::new(factory.generate_stop_digger_command()),
             });
     };
+
+    Status::Ok
 }
 
 #[put("/robot/dumper", format = "application/json", data = "<action>")]
@@ -152,16 +203,53 @@ fn put_dumper(action: Json<DumperAction>, messenger: State<RobotMessenger>, fact
             DumperAction::Reset => Box::new(factory.generate_reset_dumper_command()),
             DumperAction::Dump => Box::new(factory.generate_dump_command()),
             DumperAction::Stop => Box::new(factory.generate_stop_dumper_command()),
+            DumperAction::Cycle => Box::new(factory.generate_cycle_dumper_command()),
+            DumperAction::ClearFault => Box::new(factory.generate_clear_dumper_fault_command()),
         }
     );
 }
 
+/// Starts, pauses, resumes or cancels an autonomous routine.
+#[put("/robot/routine", format = "application/json", data = "<action>")]
+fn put_routine(action: Json<RoutineAction>, messenger: State<RobotMessenger>, factory: State<RobotCommandFactory>) {
+    messenger.send_command(
+        match action.into_inner() {
+            RoutineAction::Start(routine) => Box::new(factory.generate_start_routine_command(routine)),
+            RoutineAction::Pause => Box::new(factory.generate_pause_routine_command()),
+            RoutineAction::Resume => Box::new(factory.generate_resume_routine_command()),
+            RoutineAction::Cancel => Box::new(factory.generate_cancel_routine_command()),
+        }
+    );
+}
+
+/// Keeps the communication watchdog from tripping while the client has no motion commands to send.
+#[put("/robot/heartbeat")]
+fn put_heartbeat(messenger: State<RobotMessenger>, factory: State<RobotCommandFactory>) {
+    messenger.send_command(Box::new(factory.generate_heartbeat_command()));
+}
+
 /// Responds with the current state of the robot, as a JSON object.
 #[get("/robot")]
 fn get_state(state: State<Arc<GlobalRobotState>>) -> Json<RobotStateInstance> {
     Json(state.get_current_state())
 }
 
+/// Responds with every fault raised on the robot and not yet cleared, as a JSON array.
+#[get("/robot/faults")]
+fn get_faults(state: State<Arc<GlobalRobotState>>) -> Json<Vec<Fault>> {
+    Json(state.get_faults().get_current_state())
+}
+
+/// Clears the fault `code`, so it is no longer reported and, if critical, no longer kills the robot.
+#[delete("/robot/faults/<code>")]
+fn delete_fault(code: String, state: State<Arc<GlobalRobotState>>) -> Status {
+    if state.get_faults().clear(&code) {
+        Status::Ok
+    } else {
+        Status::NotFound
+    }
+}
+
 #[get("/favicon.ico")]
 fn favicon() -> Option<NamedFile> {
     NamedFile::open(Path::new("static/").join("favicon.png")).ok()
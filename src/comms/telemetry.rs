@@ -0,0 +1,251 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use ws::Handshake;
+use ws::Handler;
+use ws::Sender;
+use ws::WebSocket;
+
+use crate::mechatronics::dumper::state::DumperPhase;
+use crate::mechatronics::routines::state::RoutineStatus;
+use crate::robot_map::TELEMETRY_POLL_MILLIS;
+use crate::status::robot_state::GlobalRobotState;
+
+/// Streams snapshots of the robot state to every connected WebSocket client.
+///
+/// A snapshot is pushed once every period, and immediately whenever something the driver needs to
+/// react to changes, such as the robot being killed or a limit switch tripping.
+pub struct TelemetryServer {
+    state: Arc<GlobalRobotState>,
+    port: u16,
+    period: Duration,
+}
+
+impl TelemetryServer {
+    pub fn new(state: Arc<GlobalRobotState>, port: u16, period: Duration) -> Self {
+        Self {
+            state,
+            port,
+            period,
+        }
+    }
+
+    /// Serves telemetry on the configured port, taking over the current thread. The port is
+    /// reported in the robot state once it is bound.
+    pub fn launch(self) {
+        let state = self.state.clone();
+        let socket = match WebSocket::new(move |out| TelemetryClient::new(out, state.clone())) {
+            Ok(socket) => socket,
+            Err(e) => {
+                error!("Failed to create telemetry server: {}", e);
+                return;
+            }
+        };
+        let socket = match socket.bind(("0.0.0.0", self.port)) {
+            Ok(socket) => socket,
+            Err(e) => {
+                error!("Failed to bind telemetry server to port {}: {}", self.port, e);
+                return;
+            }
+        };
+        // The system picks the port when asked for port 0, so report the one which was bound.
+        let port = match socket.local_addr() {
+            Ok(address) => address.port(),
+            Err(e) => {
+                error!("Failed to read the telemetry server's address: {}", e);
+                return;
+            }
+        };
+
+        let broadcaster = socket.broadcaster();
+        let mut publisher = TelemetryPublisher::new(self.state.clone(), self.period);
+        let publisher_thread = thread::Builder::new().name("Telemetry Publisher Thread".to_string()).spawn(move || {
+            loop {
+                if let Some(message) = publisher.poll(Instant::now()) {
+                    if let Err(e) = broadcaster.send(message) {
+                        warn!("Failed to broadcast telemetry: {}", e);
+                    }
+                }
+                thread::sleep(Duration::from_millis(TELEMETRY_POLL_MILLIS));
+            }
+        });
+        if let Err(e) = publisher_thread {
+            error!("Failed to start telemetry publisher: {}", e);
+            return;
+        }
+
+        info!("Serving telemetry on port {}", port);
+        self.state.get_telemetry_port().store(Some(port), Ordering::SeqCst);
+        if let Err(e) = socket.run() {
+            error!("Telemetry server stopped: {}", e);
+        }
+    }
+}
+
+/// Decides when a new snapshot of the robot state should be pushed to clients.
+pub struct TelemetryPublisher {
+    state: Arc<GlobalRobotState>,
+    period: Duration,
+    last_sent: Option<Instant>,
+    last_significant: Option<SignificantState>,
+}
+
+impl TelemetryPublisher {
+    pub fn new(state: Arc<GlobalRobotState>, period: Duration) -> Self {
+        Self {
+            state,
+            period,
+            last_sent: None,
+            last_significant: None,
+        }
+    }
+
+    /// Returns a serialized snapshot if one is due at `now`, either because a period has passed
+    /// since the last one or because a significant part of the state has changed.
+    pub fn poll(&mut self, now: Instant) -> Option<String> {
+        let significant = SignificantState::capture(&self.state);
+        let due = match self.last_sent {
+            Some(last_sent) => now.duration_since(last_sent) >= self.period,
+            None => true,
+        };
+
+        if !due && self.last_significant == Some(significant) {
+            return None;
+        }
+
+        self.last_sent = Some(now);
+        self.last_significant = Some(significant);
+        snapshot(&self.state)
+    }
+}
+
+/// The parts of the robot state which are pushed to clients as soon as they change.
+#[derive(Clone, Copy, PartialEq)]
+struct SignificantState {
+    alive: bool,
+    watchdog_tripped: bool,
+    drive_enabled: bool,
+    intake_enabled: bool,
+    dumper_enabled: bool,
+    intake_limits: [bool; 4],
+    dumper_limits: [bool; 2],
+    intake_stalls: [bool; 2],
+    dumper_phase: DumperPhase,
+    routine_status: RoutineStatus,
+}
+
+impl SignificantState {
+    fn capture(state: &GlobalRobotState) -> Self {
+        let intake = state.get_intake().get_current_state();
+        let dumper = state.get_dumper().get_current_state();
+        Self {
+            alive: state.get_life().is_alive(),
+            watchdog_tripped: state.get_watchdog().is_tripped(),
+            drive_enabled: state.get_drive().get_enabled(),
+            intake_enabled: intake.get_enabled(),
+            dumper_enabled: dumper.get_enabled(),
+            intake_limits: [
+                intake.get_left_actuator().get_upper(),
+                intake.get_left_actuator().get_lower(),
+                intake.get_right_actuator().get_upper(),
+                intake.get_right_actuator().get_lower(),
+            ],
+            dumper_limits: [dumper.get_upper_limit(), dumper.get_lower_limit()],
+            intake_stalls: [
+                intake.get_digger().get_stalled(),
+                intake.get_actuator().get_stalled() || intake.get_right_actuator_motor().get_stalled(),
+            ],
+            dumper_phase: dumper.get_phase(),
+            routine_status: state.get_routine().get_status(),
+        }
+    }
+}
+
+/// A single WebSocket connection, which is sent a snapshot as soon as it opens.
+struct TelemetryClient {
+    out: Sender,
+    state: Arc<GlobalRobotState>,
+}
+
+impl TelemetryClient {
+    fn new(out: Sender, state: Arc<GlobalRobotState>) -> Self {
+        Self {
+            out,
+            state,
+        }
+    }
+}
+
+impl Handler for TelemetryClient {
+    fn on_open(&mut self, _shake: Handshake) -> ws::Result<()> {
+        match snapshot(&self.state) {
+            Some(message) => self.out.send(message),
+            None => Ok(()),
+        }
+    }
+}
+
+fn snapshot(state: &GlobalRobotState) -> Option<String> {
+    match serde_json::to_string(&state.get_current_state()) {
+        Ok(json) => Some(json),
+        Err(e) => {
+            error!("Failed to serialize telemetry: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    fn setup() -> (Arc<GlobalRobotState>, TelemetryPublisher, Instant) {
+        let state = Arc::new(GlobalRobotState::new());
+        let publisher = TelemetryPublisher::new(state.clone(), Duration::from_millis(100));
+        (state, publisher, Instant::now())
+    }
+
+    #[test]
+    fn first_poll_sends() {
+        let (_state, mut publisher, start) = setup();
+        let message = publisher.poll(start).unwrap();
+        assert!(message.contains("\"life\""));
+    }
+
+    #[test]
+    fn waits_for_period() {
+        let (state, mut publisher, start) = setup();
+        publisher.poll(start);
+
+        state.get_drive().get_left().set_speed(0.5);
+        assert_eq!(None, publisher.poll(start + Duration::from_millis(50)));
+        assert!(publisher.poll(start + Duration::from_millis(100)).is_some());
+    }
+
+    #[test]
+    fn kill_sends_immediately() {
+        let (state, mut publisher, start) = setup();
+        publisher.poll(start);
+
+        state.get_life().kill();
+        assert!(publisher.poll(start + Duration::from_millis(1)).is_some());
+        assert_eq!(None, publisher.poll(start + Duration::from_millis(2)));
+    }
+
+    #[test]
+    fn limit_sends_immediately() {
+        let (state, mut publisher, start) = setup();
+        publisher.poll(start);
+
+        state.get_dumper().get_upper_limit().store(true, Ordering::SeqCst);
+        assert!(publisher.poll(start + Duration::from_millis(1)).is_some());
+
+        state.get_intake().get_left_actuator().set_lower(true);
+        assert!(publisher.poll(start + Duration::from_millis(2)).is_some());
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::atomic::Ordering;
 use std::sync::mpsc::Receiver;
 use std::sync::mpsc::sync_channel;
 
@@ -6,12 +7,18 @@ use rocket::http::Status;
 use rocket::local::{Client, LocalResponse};
 
 use crate::comms;
-use crate::mechatronics::commands::RobotCommand;
+use crate::mechatronics::commands::CommandRecord;
+use crate::mechatronics::controller::MechState;
+use crate::mechatronics::CommandSource;
+use crate::mechatronics::routines::RoutineId;
+use crate::mechatronics::RobotMessage;
+use crate::status::faults::Severity;
+use crate::status::faults::Subsystem;
 
 use super::*;
 
 struct TestEnvironment {
-    receiver: Receiver<Box<RobotCommand>>,
+    receiver: Receiver<RobotMessage>,
     client: Client,
     status: Arc<GlobalRobotState>,
 }
@@ -57,6 +64,19 @@ fn test_bad_switch() {
     assert_eq!(Status::UnprocessableEntity, response.status());
 }
 
+#[test]
+fn test_switch_mode() {
+    let env = setup();
+    let response = env.client
+        .put("/robot")
+        .header(ContentType::JSON)
+        .body(r#"{ "mode" : "Idle" }"#)
+        .dispatch();
+    assert_eq!(Status::Ok, response.status());
+    let record = env.receiver.try_recv().unwrap().into_command().record();
+    assert_eq!(CommandRecord::SwitchMode { mode: MechState::Idle }, record);
+}
+
 #[test]
 fn test_bad_drive() {
     let env = setup();
@@ -75,6 +95,108 @@ fn test_bad_drive() {
     assert_eq!(Status::BadRequest, response.status());
 }
 
+#[test]
+fn test_arcade() {
+    let env = setup();
+    let response = env.client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#"{ "Arcade" : { "throttle": 0.5, "turn": -0.25 } }"#)
+        .dispatch();
+    assert_eq!(Status::Ok, response.status());
+    let record = env.receiver.try_recv().unwrap().into_command().record();
+    assert_eq!(CommandRecord::ArcadeDrive { throttle: 0.5, turn: -0.25 }, record);
+
+    let response = env.client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#"{ "Curvature" : { "throttle": 1.0, "curvature": 1.5 } }"#)
+        .dispatch();
+    assert_eq!(Status::BadRequest, response.status());
+}
+
+#[test]
+fn test_reset_pose() {
+    let env = setup();
+    let response = env.client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#" "ResetPose" "#)
+        .dispatch();
+    assert_eq!(Status::Ok, response.status());
+    let record = env.receiver.try_recv().unwrap().into_command().record();
+    assert_eq!(CommandRecord::ResetPose, record);
+}
+
+#[test]
+fn test_move_actuators() {
+    let env = setup();
+    let response = env.client.put("/robot/intake")
+        .header(ContentType::JSON)
+        .body(r#"{ "actuator" : { "MoveTo" : { "fraction": 0.5 } } }"#)
+        .dispatch();
+    assert_eq!(Status::Ok, response.status());
+    let record = env.receiver.try_recv().unwrap().into_command().record();
+    assert_eq!(CommandRecord::MoveActuators { fraction: 0.5 }, record);
+
+    let response = env.client.put("/robot/intake")
+        .header(ContentType::JSON)
+        .body(r#"{ "actuator" : { "MoveTo" : { "fraction": 1.5 } }, "digger" : "Dig" }"#)
+        .dispatch();
+    assert_eq!(Status::BadRequest, response.status());
+    assert!(env.receiver.try_recv().is_err());
+}
+
+#[test]
+fn test_clear_faults() {
+    let env = setup();
+    env.client.put("/robot/intake").header(ContentType::JSON).body(r#"{ "actuator" : "ClearFault" }"#).dispatch();
+    assert_eq!(CommandRecord::ClearActuatorFault, env.receiver.try_recv().unwrap().into_command().record());
+
+    env.client.put("/robot/dumper").header(ContentType::JSON).body(r#" "ClearFault" "#).dispatch();
+    assert_eq!(CommandRecord::ClearDumperFault, env.receiver.try_recv().unwrap().into_command().record());
+}
+
+#[test]
+fn test_heartbeat() {
+    let env = setup();
+    let response = env.client.put("/robot/heartbeat").dispatch();
+    assert_eq!(Status::Ok, response.status());
+    assert!(env.receiver.try_recv().is_ok());
+}
+
+#[test]
+fn test_command_source() {
+    let env = setup();
+    env.client.put("/robot/heartbeat").dispatch();
+    let message = env.receiver.try_recv().unwrap();
+    assert_eq!(CommandSource::Rest, message.get_source());
+    assert_eq!(CommandRecord::Heartbeat, message.into_command().record());
+}
+
+#[test]
+fn test_routine() {
+    let env = setup();
+    let response = env.client.put("/robot/routine")
+        .header(ContentType::JSON)
+        .body(r#"{ "Start" : "Dump" }"#)
+        .dispatch();
+    assert_eq!(Status::Ok, response.status());
+    let record = env.receiver.try_recv().unwrap().into_command().record();
+    assert_eq!(CommandRecord::StartRoutine { routine: RoutineId::Dump }, record);
+
+    env.client.put("/robot/routine").header(ContentType::JSON).body(r#" "Cancel" "#).dispatch();
+    assert_eq!(CommandRecord::CancelRoutine, env.receiver.try_recv().unwrap().into_command().record());
+}
+
+#[test]
+fn test_bad_routine() {
+    let env = setup();
+    let response = env.client.put("/robot/routine")
+        .header(ContentType::JSON)
+        .body(r#"{ "Start" : "Dance" }"#)
+        .dispatch();
+    assert_eq!(Status::UnprocessableEntity, response.status());
+    assert!(env.receiver.try_recv().is_err());
+}
+
 #[test]
 fn test_state() {
     let env = setup();
@@ -83,6 +205,29 @@ fn test_state() {
     assert!(response.body().is_some());
 }
 
+#[test]
+fn test_telemetry_port() {
+    let env = setup();
+    assert!(env.client.get("/robot").dispatch().body_string().unwrap().contains(r#""telemetry_port":null"#));
+
+    env.status.get_telemetry_port().store(Some(8001), Ordering::SeqCst);
+    assert!(env.client.get("/robot").dispatch().body_string().unwrap().contains(r#""telemetry_port":8001"#));
+}
+
+#[test]
+fn test_faults() {
+    let env = setup();
+    env.status.get_faults().raise("gpio45", Subsystem::Pinouts, Severity::Error, "gpio45: No such file or directory");
+
+    let mut response = env.client.get("/robot/faults").dispatch();
+    assert_eq!(Status::Ok, response.status());
+    assert!(response.body_string().unwrap().contains(r#""code":"gpio45""#));
+
+    assert_eq!(Status::Ok, env.client.delete("/robot/faults/gpio45").dispatch().status());
+    assert_eq!(Status::NotFound, env.client.delete("/robot/faults/gpio45").dispatch().status());
+    assert_eq!(None, env.status.get_faults().get_fault("gpio45"));
+}
+
 #[test]
 fn test_index() {
     let env = setup();
@@ -1,4 +1,13 @@
+use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::status::timing::GlobalLoopState;
+
+/// How long before a cycle's deadline a fixed-rate loop stops sleeping and starts yielding, to
+/// make up for the imprecision of `thread::sleep`.
+const SPIN_MARGIN_MICROS: u64 = 200;
 
 /// The runnable trait represents a process which should initialize itself and run repeatedly.
 pub trait Runnable: Send {
@@ -21,6 +30,57 @@ pub trait Runnable: Send {
             thread::yield_now();
         }
     }
+
+    /// Starts the Runnable in the current thread, running it once every `period` and sleeping in
+    /// between. The timing of every cycle is recorded in `stats`.
+    ///
+    /// If a cycle overruns its period, the following cycle starts immediately and the schedule is
+    /// restarted from there rather than trying to catch up.
+    fn start_fixed_rate(&mut self, period: Duration, stats: Arc<GlobalLoopState>) {
+        stats.set_period(period);
+        self.init();
+        let mut deadline = Instant::now();
+        loop {
+            let start = Instant::now();
+            self.run();
+            let end = Instant::now();
+            stats.record_cycle(end - start, jitter(deadline, start));
+
+            deadline = next_deadline(deadline, period, end);
+            if end < deadline {
+                sleep_until(deadline);
+            }
+        }
+    }
+}
+
+/// How far a cycle which started at `start` was from the time it was scheduled for, early or late.
+fn jitter(deadline: Instant, start: Instant) -> Duration {
+    if start > deadline { start - deadline } else { deadline - start }
+}
+
+/// When the cycle after one scheduled for `deadline` which finished at `end` should start. A cycle
+/// which overran restarts the schedule from when it finished.
+fn next_deadline(deadline: Instant, period: Duration, end: Instant) -> Instant {
+    let next = deadline + period;
+    if end > next { end } else { next }
+}
+
+/// Returns `duration` as a number of seconds, for use in rates and physics.
+pub fn seconds(duration: Duration) -> f32 {
+    duration.as_secs() as f32 + duration.subsec_nanos() as f32 / 1_000_000_000.0
+}
+
+/// Sleeps until shortly before `deadline`, then yields until it has passed.
+fn sleep_until(deadline: Instant) {
+    let margin = Duration::from_micros(SPIN_MARGIN_MICROS);
+    let now = Instant::now();
+    if deadline > now + margin {
+        thread::sleep(deadline - now - margin);
+    }
+    while Instant::now() < deadline {
+        thread::yield_now();
+    }
 }
 
 pub struct CompositeRunnable {
@@ -51,4 +111,47 @@ impl CompositeRunnable {
     pub fn add_runnable(&mut self, runnable: Box<Runnable>) {
         self.children.push(runnable)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_rate_schedule() {
+        let period = Duration::from_millis(5);
+        let stats = GlobalLoopState::new();
+        stats.set_period(period);
+        let first = Instant::now();
+
+        // Started late but finished in time, so the next cycle keeps to the schedule.
+        let start = first + Duration::from_micros(100);
+        let end = first + Duration::from_millis(2);
+        stats.record_cycle(end - start, jitter(first, start));
+        let second = next_deadline(first, period, end);
+        assert_eq!(first + period, second);
+
+        // Overran its period, so the schedule restarts from when it finished and the next cycle
+        // starts straight away.
+        let end = second + Duration::from_millis(7);
+        stats.record_cycle(end - second, jitter(second, second));
+        let third = next_deadline(second, period, end);
+        assert_eq!(end, third);
+
+        let end = third + Duration::from_millis(1);
+        stats.record_cycle(end - third, jitter(third, third));
+        let fourth = next_deadline(third, period, end);
+        assert_eq!(third + period, fourth);
+
+        // Woke up early.
+        let start = fourth - Duration::from_micros(200);
+        let end = fourth + Duration::from_millis(1);
+        stats.record_cycle(end - start, jitter(fourth, start));
+
+        let instance = stats.get_current_state();
+        assert_eq!(4, instance.get_cycles());
+        assert_eq!(1, instance.get_overruns());
+        assert_eq!(7_000, instance.get_worst_cycle_micros());
+        assert_eq!(75, instance.get_mean_jitter_micros());
+        assert_eq!(200, instance.get_worst_jitter_micros());
+    }
+}
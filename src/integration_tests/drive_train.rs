@@ -56,4 +56,34 @@ fn brake() {
 
     assert_eq!(0.0, state.get_drive().get_current_state().get_left().get_speed());
     assert_eq!(0.0, state.get_drive().get_current_state().get_right().get_speed());
-}
\ No newline at end of file
+}
+
+#[test]
+fn arcade() {
+    let (state, client) = setup();
+    enable_drive(&client);
+    let response = client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#"{"Arcade" : { "throttle": 0.5, "turn": 0.25 } }"#)
+        .dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!(0.75, state.get_drive().get_left().get_speed());
+    assert_eq!(0.25, state.get_drive().get_right().get_speed());
+}
+
+#[test]
+fn precision() {
+    let (state, client) = setup();
+    enable_drive(&client);
+    client.put("/robot")
+        .header(ContentType::JSON)
+        .body(r#"{"precision":true}"#)
+        .dispatch();
+    send_drive(&client, 1.0, -1.0);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(PRECISION_SCALE, state.get_drive().get_left().get_speed());
+    assert_eq!(-PRECISION_SCALE, state.get_drive().get_right().get_speed());
+    assert_eq!(true, state.get_current_state().get_precision());
+}
@@ -3,6 +3,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use rocket::http::ContentType;
 use rocket::local::LocalResponse;
 
+use crate::mechatronics::dumper::state::DumperPhase;
 use crate::pinouts::digital::TestPin;
 
 use super::*;
@@ -49,6 +50,16 @@ fn send_stop(client: &Client) -> LocalResponse {
     response
 }
 
+fn send_cycle(client: &Client) -> LocalResponse {
+    let response = client.put("/robot/dumper")
+        .header(ContentType::JSON)
+        .body(r#" "Cycle" "#)
+        .dispatch();
+
+    assert_eq!(Status::Ok, response.status());
+    response
+}
+
 #[test]
 fn dump() {
     let (state, client) = setup();
@@ -91,6 +102,27 @@ fn stop() {
     assert_eq!(0.0, state.get_dumper().get_current_state().get_motor().get_speed());
 }
 
+#[test]
+fn cycle() {
+    let (state, client) = setup();
+    enable_dumper(&client);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    send_cycle(&client);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(DUMPING_RATE, state.get_dumper().get_motor().get_speed());
+    assert_eq!(DumperPhase::CycleRaising, state.get_current_state().get_dumper().get_phase());
+
+    state.get_dumper().get_upper_limit().store(true, Ordering::SeqCst);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(0.0, state.get_dumper().get_motor().get_speed());
+    assert_eq!(DumperPhase::CycleDwelling, state.get_current_state().get_dumper().get_phase());
+
+    send_stop(&client);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(DumperPhase::Stopped, state.get_current_state().get_dumper().get_phase());
+}
+
 #[test]
 fn upper_dumper_tripped() {
     let upper_input = Arc::new(AtomicBool::new(false));
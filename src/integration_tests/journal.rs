@@ -0,0 +1,123 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+use rocket::http::ContentType;
+
+use crate::mechatronics::CommandSource;
+use crate::mechatronics::commands::CommandRecord;
+use crate::mechatronics::controller::MechState;
+use crate::recording::journal::JournalEntry;
+use crate::recording::journal::read_journal;
+use crate::recording::replay::CommandReplayer;
+use crate::recording::replay::ReplayTiming;
+
+use super::*;
+
+const TIMEOUT_MILLIS: u64 = 30;
+
+fn journal_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("rmc-journal-{}-{}", name, process::id()))
+}
+
+fn record_session(path: &PathBuf) -> (Arc<GlobalRobotState>, Vec<JournalEntry>) {
+    let mut builder = RobotAssemblyBuilder::new();
+    let state = builder.get_state();
+    builder.with_test();
+    builder.with_journal(path.clone());
+    let client = builder.generate().assemble().launch().engage_testing_server();
+
+    client.put("/robot").header(ContentType::JSON).body(r#"{"mode":"Driving"}"#).dispatch();
+    client.put("/robot/heartbeat").dispatch();
+    client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#"{"Drive" : { "left": 0.5, "right": -0.5 } }"#)
+        .dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS * 3));
+    client.put("/robot").header(ContentType::JSON).body(r#"{"mode":"Dumping"}"#).dispatch();
+    client.put("/robot/dumper").header(ContentType::JSON).body(r#" "Dump" "#).dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    let files: Vec<_> = fs::read_dir(path).unwrap().map(|entry| entry.unwrap().path()).collect();
+    assert_eq!(1, files.len());
+    (state, read_journal(&files[0]).unwrap())
+}
+
+#[test]
+fn journal_records_commands() {
+    let path = journal_path("record");
+    let (_state, entries) = record_session(&path);
+    fs::remove_dir_all(&path).unwrap();
+
+    let commands: Vec<CommandRecord> = entries.iter().map(|entry| entry.get_command()).collect();
+    assert_eq!(vec![
+        CommandRecord::SwitchMode { mode: MechState::Driving },
+        CommandRecord::Drive { left: 0.5, right: -0.5 },
+        CommandRecord::SwitchMode { mode: MechState::Dumping },
+        CommandRecord::Dump,
+    ], commands);
+    assert!(entries.iter().all(|entry| entry.get_source() == CommandSource::Rest));
+    assert!(entries[2].get_run_millis() >= entries[1].get_run_millis() + TIMEOUT_MILLIS * 3);
+}
+
+#[test]
+fn replay_reproduces_state() {
+    let path = journal_path("replay");
+    let (recorded, entries) = record_session(&path);
+    fs::remove_dir_all(&path).unwrap();
+
+    let mut builder = RobotAssemblyBuilder::new();
+    let replayed = builder.get_state();
+    builder.with_test();
+    let robot = builder.generate().assemble().launch();
+
+    let replayer = CommandReplayer::new(entries, ReplayTiming::AsFastAsPossible);
+    assert_eq!(4, replayer.replay(&robot.get_messenger(CommandSource::Replay)));
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(recorded.get_drive().get_enabled(), replayed.get_drive().get_enabled());
+    assert_eq!(recorded.get_dumper().get_enabled(), replayed.get_dumper().get_enabled());
+    assert_eq!(DUMPING_RATE, replayed.get_dumper().get_motor().get_speed());
+}
+
+#[test]
+fn replay_original_timing() {
+    let path = journal_path("timing");
+    let (_state, entries) = record_session(&path);
+    fs::remove_dir_all(&path).unwrap();
+    let span = entries.last().unwrap().get_run_millis() - entries.first().unwrap().get_run_millis();
+
+    let mut builder = RobotAssemblyBuilder::new();
+    builder.with_test();
+    let robot = builder.generate().assemble().launch();
+
+    let start = Instant::now();
+    CommandReplayer::new(entries, ReplayTiming::Original).replay(&robot.get_messenger(CommandSource::Replay));
+    assert!(start.elapsed() >= Duration::from_millis(span));
+}
+
+#[test]
+fn replay_legacy_switches() {
+    let path = journal_path("legacy");
+    fs::write(&path, concat!(
+        r#"{"unix_millis":1000,"run_millis":0,"source":"Rest","command":"DriveSwitch"}"#, "\n",
+        r#"{"unix_millis":1010,"run_millis":10,"source":"Rest","command":"DumperSwitch"}"#, "\n",
+    )).unwrap();
+    let entries = read_journal(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(CommandRecord::DumperSwitch, entries[1].get_command());
+
+    let mut builder = RobotAssemblyBuilder::new();
+    let state = builder.get_state();
+    builder.with_test();
+    let robot = builder.generate().assemble().launch();
+
+    assert_eq!(2, CommandReplayer::new(entries, ReplayTiming::AsFastAsPossible)
+        .replay(&robot.get_messenger(CommandSource::Replay)));
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(MechState::Dumping, state.get_current_state().get_mode());
+    assert_eq!(true, state.get_dumper().get_current_state().get_enabled());
+}
@@ -3,6 +3,7 @@ use rocket::local::LocalResponse;
 use rocket::Response;
 
 use crate::comms::RobotLifeRestId;
+use crate::robot_config::CurrentConfig;
 
 use super::*;
 
@@ -37,4 +38,26 @@ fn revive() {
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(true, state.get_life().is_alive());
-}
\ No newline at end of file
+}
+
+#[test]
+fn critical_current() {
+    let config = RobotConfig::default().with_current(CurrentConfig::default().with_kill_on_critical(true));
+    let mut builder = RobotAssemblyBuilder::from_config(config);
+    let state = builder.get_state();
+    builder.with_test();
+    let client = builder.generate().assemble().launch().engage_testing_server();
+
+    state.get_current().update_current(state.get_current().get_critical());
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(false, state.get_life().is_alive());
+
+    let mut response = client.get("/robot").dispatch();
+    assert!(response.body_string().unwrap().contains(r#""reason":"CriticalCurrent""#));
+
+    state.get_current().update_current(0.0);
+    let response = send_life(&client, RobotLifeRestId::Alive);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(true, state.get_life().is_alive());
+    assert_eq!(None, state.get_life().get_reason());
+}
@@ -32,6 +32,30 @@ mod intake;
 #[cfg(test)]
 mod killing;
 
+#[cfg(test)]
+mod watchdog;
+
+#[cfg(test)]
+mod sysfs;
+
+#[cfg(test)]
+mod simulation;
+
+#[cfg(test)]
+mod timing;
+
+#[cfg(test)]
+mod telemetry;
+
+#[cfg(test)]
+mod recording;
+
+#[cfg(test)]
+mod journal;
+
+#[cfg(test)]
+mod routines;
+
 fn setup() -> (Arc<GlobalRobotState>, Client) {
     let mut builder = RobotAssemblyBuilder::new();
     let state = builder.get_state();
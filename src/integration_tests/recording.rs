@@ -0,0 +1,26 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use super::*;
+
+#[test]
+fn records_run() {
+    let path = env::temp_dir().join(format!("rmc-recording-run-{}", process::id()));
+    let mut builder = RobotAssemblyBuilder::new();
+    builder.with_test();
+    builder.with_recorder(path.clone(), Duration::from_millis(20), true);
+    let _client = builder.generate().assemble().launch().engage_testing_server();
+
+    sleep(Duration::from_millis(300));
+
+    let runs: Vec<_> = fs::read_dir(&path).unwrap().map(|entry| entry.unwrap().path()).collect();
+    assert_eq!(1, runs.len());
+
+    let jsonl = fs::read_to_string(runs[0].join("state.jsonl")).unwrap();
+    let csv = fs::read_to_string(runs[0].join("state.csv")).unwrap();
+    assert!(jsonl.lines().count() >= 5);
+    assert!(csv.lines().next().unwrap().contains("state.life.life"));
+
+    fs::remove_dir_all(&path).unwrap();
+}
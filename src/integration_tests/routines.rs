@@ -0,0 +1,84 @@
+use rocket::http::ContentType;
+use rocket::local::LocalResponse;
+
+use crate::mechatronics::routines::RoutineId;
+use crate::mechatronics::routines::state::RoutineStatus;
+
+use super::*;
+
+const TIMEOUT_MILLIS: u64 = 30;
+
+fn send_routine(client: &Client, body: &'static str) -> LocalResponse {
+    let response = client.put("/robot/routine")
+        .header(ContentType::JSON)
+        .body(body)
+        .dispatch();
+
+    assert_eq!(Status::Ok, response.status());
+    response
+}
+
+#[test]
+fn lower_intake() {
+    let (state, client) = setup();
+
+    send_routine(&client, r#"{ "Start" : "LowerIntake" }"#);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(true, state.get_intake().get_enabled());
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_intake().get_actuator().get_speed());
+    assert_eq!(RoutineStatus::Running, state.get_routine().get_status());
+
+    state.get_intake().get_left_actuator().set_lower(true);
+    state.get_intake().get_right_actuator().set_lower(true);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    let routine = state.get_current_state();
+    assert_eq!(RoutineStatus::Completed, routine.get_routine().get_status());
+    assert_eq!(Some(RoutineId::LowerIntake), routine.get_routine().get_routine());
+    assert_eq!(routine.get_routine().get_steps(), routine.get_routine().get_step());
+    assert_eq!(0.0, state.get_intake().get_actuator().get_speed());
+}
+
+#[test]
+fn cancel() {
+    let (state, client) = setup();
+
+    send_routine(&client, r#"{ "Start" : "Dump" }"#);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(DUMPING_RATE, state.get_dumper().get_motor().get_speed());
+
+    send_routine(&client, r#" "Cancel" "#);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(RoutineStatus::Cancelled, state.get_routine().get_status());
+    assert_eq!(0.0, state.get_dumper().get_motor().get_speed());
+}
+
+#[test]
+fn kill_aborts() {
+    let (state, client) = setup();
+
+    send_routine(&client, r#"{ "Start" : "Dig" }"#);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    client.put("/robot").header(ContentType::JSON).body(r#"{ "life" : "Dead" }"#).dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(RoutineStatus::Aborted, state.get_routine().get_status());
+    assert_eq!(0.0, state.get_intake().get_actuator().get_speed());
+}
+
+#[test]
+fn pause_and_resume() {
+    let (state, client) = setup();
+
+    send_routine(&client, r#"{ "Start" : "RaiseIntake" }"#);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    send_routine(&client, r#" "Pause" "#);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(RoutineStatus::Paused, state.get_routine().get_status());
+    assert_eq!(0.0, state.get_intake().get_actuator().get_speed());
+
+    send_routine(&client, r#" "Resume" "#);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(RoutineStatus::Running, state.get_routine().get_status());
+    assert_eq!(MH_ACTUATOR_RATE, state.get_intake().get_actuator().get_speed());
+}
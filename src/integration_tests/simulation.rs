@@ -0,0 +1,63 @@
+use std::sync::atomic::Ordering;
+
+use rocket::http::ContentType;
+
+use super::*;
+
+const TIMEOUT_MILLIS: u64 = 30;
+
+fn setup_simulation() -> (Arc<GlobalRobotState>, Client) {
+    let mut builder = RobotAssemblyBuilder::new();
+    let state = builder.get_state();
+    builder.with_simulation();
+    // The dumper takes longer to travel than the default watchdog timeout.
+    builder.with_watchdog_timeout(Duration::from_secs(10));
+    let client = builder.generate().assemble().launch().engage_testing_server();
+
+    (state, client)
+}
+
+#[test]
+fn pose_reported() {
+    let (_state, client) = setup_simulation();
+    let mut response = client.get("/robot").dispatch();
+    let body = response.body_string().unwrap();
+    assert!(body.contains("\"simulation\""));
+    assert!(body.contains("\"heading\""));
+}
+
+#[test]
+fn pose_omitted_without_simulation() {
+    let (_state, client) = setup();
+    let mut response = client.get("/robot").dispatch();
+    assert_eq!(false, response.body_string().unwrap().contains("\"simulation\""));
+}
+
+#[test]
+fn drive_moves_robot() {
+    let (state, client) = setup_simulation();
+    client.put("/robot").header(ContentType::JSON).body(r#"{"mode":"Driving"}"#).dispatch();
+    client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#"{"Drive" : { "left": 1.0, "right": 1.0 } }"#)
+        .dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS * 5));
+
+    assert!(state.get_simulation().get_pose().get_x() > 0.0);
+    assert!(state.get_drive().get_pose().get_x() > 0.0);
+}
+
+#[test]
+fn dumper_stops_at_limit() {
+    let (state, client) = setup_simulation();
+    client.put("/robot").header(ContentType::JSON).body(r#"{"mode":"Dumping"}"#).dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    client.put("/robot/dumper").header(ContentType::JSON).body(r#" "Dump" "#).dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(DUMPING_RATE, state.get_dumper().get_motor().get_speed());
+
+    sleep(Duration::from_millis((SIM_DUMPER_TRAVEL_SECONDS * 1000.0) as u64 + TIMEOUT_MILLIS * 3));
+
+    assert_eq!(true, state.get_dumper().get_upper_limit().load(Ordering::SeqCst));
+    assert_eq!(0.0, state.get_dumper().get_motor().get_speed());
+}
@@ -1,19 +1,20 @@
 use rocket::http::ContentType;
 use rocket::local::LocalResponse;
 
-use crate::comms::RobotMode;
+use crate::mechatronics::controller::MechState;
 
 use super::*;
 
 const TIMEOUT_MILLIS: u64 = 50;
 
-fn switch(client: &Client, mode: RobotMode) -> LocalResponse {
+fn switch(client: &Client, mode: MechState) -> LocalResponse {
     client.put("/robot")
         .header(ContentType::JSON)
         .body(match mode {
-            RobotMode::Digging => r#"{ "mode":"Digging" }"#,
-            RobotMode::Driving => r#"{ "mode":"Driving" }"#,
-            RobotMode::Dumping => r#"{ "mode":"Dumping" }"#,
+            MechState::Idle => r#"{ "mode":"Idle" }"#,
+            MechState::Digging => r#"{ "mode":"Digging" }"#,
+            MechState::Driving => r#"{ "mode":"Driving" }"#,
+            MechState::Dumping => r#"{ "mode":"Dumping" }"#,
         })
         .dispatch()
 }
@@ -26,7 +27,7 @@ fn drive() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let response = switch(&client, RobotMode::Driving);
+    let response = switch(&client, MechState::Driving);
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(Status::Ok, response.status());
@@ -43,7 +44,7 @@ fn dump() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let response = switch(&client, RobotMode::Dumping);
+    let response = switch(&client, MechState::Dumping);
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(Status::Ok, response.status());
@@ -60,7 +61,7 @@ fn dig() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let response = switch(&client, RobotMode::Digging);
+    let response = switch(&client, MechState::Digging);
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(Status::Ok, response.status());
@@ -77,11 +78,11 @@ fn switch_dig_to_drive() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let _response = switch(&client, RobotMode::Digging);
+    let _response = switch(&client, MechState::Digging);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
-    let response = switch(&client, RobotMode::Driving);
+    let response = switch(&client, MechState::Driving);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
@@ -99,11 +100,11 @@ fn switch_dig_to_dump() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let _response = switch(&client, RobotMode::Digging);
+    let _response = switch(&client, MechState::Digging);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
-    let response = switch(&client, RobotMode::Dumping);
+    let response = switch(&client, MechState::Dumping);
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(Status::Ok, response.status());
@@ -120,11 +121,11 @@ fn switch_drive_to_dump() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let _response = switch(&client, RobotMode::Driving);
+    let _response = switch(&client, MechState::Driving);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
-    let response = switch(&client, RobotMode::Dumping);
+    let response = switch(&client, MechState::Dumping);
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(Status::Ok, response.status());
@@ -141,11 +142,11 @@ fn switch_drive_to_dig() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let _response = switch(&client, RobotMode::Driving);
+    let _response = switch(&client, MechState::Driving);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
-    let response = switch(&client, RobotMode::Digging);
+    let response = switch(&client, MechState::Digging);
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(Status::Ok, response.status());
@@ -162,11 +163,11 @@ fn switch_dump_to_drive() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let _response = switch(&client, RobotMode::Dumping);
+    let _response = switch(&client, MechState::Dumping);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
-    let response = switch(&client, RobotMode::Driving);
+    let response = switch(&client, MechState::Driving);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
@@ -184,15 +185,39 @@ fn switch_dump_to_dig() {
     let robot = builder.generate().assemble();
     let client = robot.launch().engage_testing_server();
 
-    let _response = switch(&client, RobotMode::Dumping);
+    let _response = switch(&client, MechState::Dumping);
 
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
-    let response = switch(&client, RobotMode::Digging);
+    let response = switch(&client, MechState::Digging);
     sleep(Duration::from_millis(TIMEOUT_MILLIS));
 
     assert_eq!(Status::Ok, response.status());
     assert_eq!(false, state.get_drive().get_current_state().get_enabled());
     assert_eq!(false, state.get_dumper().get_current_state().get_enabled());
     assert_eq!(true, state.get_intake().get_current_state().get_enabled());
-}
\ No newline at end of file
+}
+
+#[test]
+fn switch_drive_to_idle() {
+    let mut builder = RobotAssemblyBuilder::new();
+    let state = builder.get_state();
+    builder.with_test();
+    let robot = builder.generate().assemble();
+    let client = robot.launch().engage_testing_server();
+
+    let _response = switch(&client, MechState::Driving);
+
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    let response = switch(&client, MechState::Idle);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!(false, state.get_drive().get_current_state().get_enabled());
+    assert_eq!(false, state.get_dumper().get_current_state().get_enabled());
+    assert_eq!(false, state.get_intake().get_current_state().get_enabled());
+
+    let mut response = client.get("/robot").dispatch();
+    assert!(response.body_string().unwrap().contains(r#""mode":"Idle""#));
+}
@@ -0,0 +1,115 @@
+use std::sync::atomic::Ordering;
+
+use rocket::http::ContentType;
+
+use crate::pinouts::analog::input::libbeaglebone::volts_to_raw;
+use crate::pinouts::sysfs::FakeSysfs;
+use crate::robot_config::RobotConfig;
+use crate::status::current::CurrentUsageLevel;
+
+use super::*;
+
+const TIMEOUT_MILLIS: u64 = 30;
+
+fn setup_sysfs() -> (Arc<GlobalRobotState>, Client, FakeSysfs, RobotConfig) {
+    let config = RobotConfig::default();
+    let sysfs = FakeSysfs::new(&config).unwrap();
+
+    let mut builder = RobotAssemblyBuilder::from_config(config.clone());
+    let state = builder.get_state();
+    builder.with_sysfs_root(sysfs.get_root());
+    builder.with_iio_root(sysfs.get_iio_root());
+    builder.with_production();
+    let client = builder.generate().assemble().launch().engage_testing_server();
+
+    (state, client, sysfs, config)
+}
+
+fn set_mode(client: &Client, mode: &str) {
+    let response = client.put("/robot")
+        .header(ContentType::JSON)
+        .body(format!("{{\"mode\":\"{}\"}}", mode))
+        .dispatch();
+    assert_eq!(Status::Ok, response.status());
+}
+
+#[test]
+fn pins_exported() {
+    let (_state, _client, sysfs, config) = setup_sysfs();
+    let drive = config.get_drive();
+
+    assert_eq!("out", sysfs.get_direction(drive.get_front_left_direction()).unwrap());
+    assert_eq!("in", sysfs.get_direction(config.get_dumper().get_upper_limit()).unwrap());
+    assert_eq!(20_000, sysfs.get_period(drive.get_front_left_pwm()).unwrap());
+    assert_eq!(true, sysfs.is_enabled(drive.get_front_left_pwm()).unwrap());
+}
+
+#[test]
+fn drive_writes_pins() {
+    let (_state, client, sysfs, config) = setup_sysfs();
+    let drive = config.get_drive();
+
+    set_mode(&client, "Driving");
+    client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#"{"Drive" : { "left": 0.5, "right": -0.25 } }"#)
+        .dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(10_000, sysfs.get_duty_cycle(drive.get_front_left_pwm()).unwrap());
+    assert_eq!(10_000, sysfs.get_duty_cycle(drive.get_rear_left_pwm()).unwrap());
+    assert_eq!(5_000, sysfs.get_duty_cycle(drive.get_front_right_pwm()).unwrap());
+    assert_eq!(5_000, sysfs.get_duty_cycle(drive.get_rear_right_pwm()).unwrap());
+
+    assert_eq!(false, sysfs.get_gpio(drive.get_front_left_direction()).unwrap());
+    assert_eq!(false, sysfs.get_gpio(drive.get_rear_left_direction()).unwrap());
+    assert_eq!(true, sysfs.get_gpio(drive.get_front_right_direction()).unwrap());
+    assert_eq!(true, sysfs.get_gpio(drive.get_rear_right_direction()).unwrap());
+}
+
+#[test]
+fn dumper_writes_pwm() {
+    let (_state, client, sysfs, config) = setup_sysfs();
+    let pwm = config.get_dumper().get_pwm();
+
+    // The RoboClaw maps a stopped motor to the middle of its input range.
+    assert_eq!(6_060, sysfs.get_duty_cycle(pwm).unwrap());
+
+    set_mode(&client, "Dumping");
+    client.put("/robot/dumper")
+        .header(ContentType::JSON)
+        .body(r#" "Dump" "#)
+        .dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(12_121, sysfs.get_duty_cycle(pwm).unwrap());
+}
+
+#[test]
+fn limit_read_from_sysfs() {
+    let (state, _client, sysfs, config) = setup_sysfs();
+
+    sysfs.set_gpio(config.get_dumper().get_upper_limit(), true).unwrap();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(true, state.get_dumper().get_upper_limit().load(Ordering::SeqCst));
+    assert_eq!(false, state.get_dumper().get_lower_limit().load(Ordering::SeqCst));
+}
+
+#[test]
+fn current_read_from_adc() {
+    let (state, client, sysfs, config) = setup_sysfs();
+    let current = config.get_current();
+
+    sysfs.set_adc(current.get_adc_device(), current.get_adc_channel(), volts_to_raw(0.5)).unwrap();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert!((state.get_current().get_current() - 1.0).abs() < 0.01);
+    assert_eq!(CurrentUsageLevel::Normal, state.get_current().get_level());
+
+    sysfs.set_adc(current.get_adc_device(), current.get_adc_channel(), volts_to_raw(1.8)).unwrap();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(CurrentUsageLevel::Critical, state.get_current().get_level());
+
+    let mut response = client.get("/robot").dispatch();
+    assert!(response.body_string().unwrap().contains(r#""level":"Critical""#));
+}
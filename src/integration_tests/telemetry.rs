@@ -0,0 +1,44 @@
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Instant;
+
+use ws::CloseCode;
+
+use super::*;
+
+const STARTUP_MILLIS: u64 = 5000;
+
+#[test]
+fn snapshot_on_connect() {
+    let mut builder = RobotAssemblyBuilder::new();
+    let state = builder.get_state();
+    builder.with_test();
+    builder.with_telemetry(0, Duration::from_millis(50));
+    let _client = builder.generate().assemble().launch().engage_testing_server();
+
+    // The server reports the port the system gave it once it is listening.
+    let deadline = Instant::now() + Duration::from_millis(STARTUP_MILLIS);
+    let port = loop {
+        if let Some(port) = state.get_telemetry_port().load(Ordering::SeqCst) {
+            break port;
+        }
+        assert!(Instant::now() < deadline, "telemetry server did not start");
+        sleep(Duration::from_millis(1));
+    };
+
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+        ws::connect(format!("ws://127.0.0.1:{}", port), |out| {
+            let sender = sender.clone();
+            move |message: ws::Message| {
+                sender.send(message.into_text()?).unwrap();
+                out.close(CloseCode::Normal)
+            }
+        }).unwrap();
+    });
+
+    let message = receiver.recv_timeout(Duration::from_secs(2)).unwrap();
+    assert!(message.contains("\"life\""));
+    assert!(message.contains("\"watchdog\""));
+}
@@ -0,0 +1,37 @@
+use std::time::Instant;
+
+use super::*;
+
+const STARTUP_MILLIS: u64 = 5000;
+
+#[test]
+fn fixed_rate_loops() {
+    let mut builder = RobotAssemblyBuilder::new();
+    let state = builder.get_state();
+    builder.with_test();
+    builder.with_controller_period(Duration::from_millis(5));
+    builder.with_monitor_period(Duration::from_millis(1));
+    let _client = builder.generate().assemble().launch().engage_testing_server();
+
+    // How many cycles run in a given time depends on the machine, so only wait for both loops to
+    // have run at least once.
+    let deadline = Instant::now() + Duration::from_millis(STARTUP_MILLIS);
+    while (state.get_controller_timing().get_cycles() == 0 || state.get_monitor_timing().get_cycles() == 0)
+        && Instant::now() < deadline {
+        sleep(Duration::from_millis(1));
+    }
+
+    assert!(state.get_controller_timing().get_cycles() > 0);
+    assert!(state.get_monitor_timing().get_cycles() > 0);
+    assert_eq!(Duration::from_millis(5), state.get_controller_timing().get_period());
+    assert_eq!(Duration::from_millis(1), state.get_monitor_timing().get_period());
+}
+
+#[test]
+fn timing_reported() {
+    let (_state, client) = setup();
+    let mut response = client.get("/robot").dispatch();
+    let body = response.body_string().unwrap();
+    assert!(body.contains("\"controller_timing\""));
+    assert!(body.contains("\"worst_jitter_micros\""));
+}
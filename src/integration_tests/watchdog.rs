@@ -0,0 +1,66 @@
+use rocket::http::ContentType;
+use rocket::local::LocalResponse;
+
+use super::*;
+
+const TIMEOUT_MILLIS: u64 = 30;
+const WATCHDOG_MILLIS: u64 = 150;
+
+fn setup_watchdog() -> (Arc<GlobalRobotState>, Client) {
+    let mut builder = RobotAssemblyBuilder::new();
+    let state = builder.get_state();
+    builder.with_test();
+    builder.with_watchdog_timeout(Duration::from_millis(WATCHDOG_MILLIS));
+    let client = builder.generate().assemble().launch().engage_testing_server();
+
+    (state, client)
+}
+
+fn send_heartbeat(client: &Client) -> LocalResponse {
+    client.put("/robot/heartbeat").dispatch()
+}
+
+#[test]
+fn silence_brakes_drive() {
+    let (state, client) = setup_watchdog();
+
+    client.put("/robot").header(ContentType::JSON).body(r#"{"mode":"Driving"}"#).dispatch();
+    client.put("/robot/drive")
+        .header(ContentType::JSON)
+        .body(r#"{"Drive" : { "left": 1.0, "right": 1.0 } }"#)
+        .dispatch();
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+    assert_eq!(1.0, state.get_drive().get_left().get_speed());
+
+    sleep(Duration::from_millis(WATCHDOG_MILLIS * 2));
+
+    assert_eq!(true, state.get_current_state().get_watchdog().get_tripped());
+    assert_eq!(0.0, state.get_drive().get_left().get_speed());
+    assert_eq!(0.0, state.get_drive().get_right().get_speed());
+}
+
+#[test]
+fn heartbeat_keeps_link_alive() {
+    let (state, client) = setup_watchdog();
+
+    for _ in 0..5 {
+        let response = send_heartbeat(&client);
+        assert_eq!(Status::Ok, response.status());
+        sleep(Duration::from_millis(WATCHDOG_MILLIS / 3));
+    }
+
+    assert_eq!(false, state.get_watchdog().is_tripped());
+}
+
+#[test]
+fn heartbeat_clears_trip() {
+    let (state, client) = setup_watchdog();
+
+    sleep(Duration::from_millis(WATCHDOG_MILLIS * 2));
+    assert_eq!(true, state.get_watchdog().is_tripped());
+
+    send_heartbeat(&client);
+    sleep(Duration::from_millis(TIMEOUT_MILLIS));
+
+    assert_eq!(false, state.get_watchdog().is_tripped());
+}
@@ -4,10 +4,10 @@ use slog::Drain;
 use slog::Duplicate;
 use slog_scope::GlobalLoggerGuard;
 
-use crate::robot_map::*;
+use crate::robot_config::LoggingConfig;
 
 /// Launches the logger, returning back a global guard object that <b>MUST NOT BE DROPPED!</b>
-pub fn launch_logger() -> GlobalLoggerGuard {
+pub fn launch_logger(config: &LoggingConfig) -> GlobalLoggerGuard {
     let term_decorator = slog_term::TermDecorator::new().force_color().build();
     let term_drain = slog_term::FullFormat::new(term_decorator).build().fuse();
     let term_drain = slog_async::Async::new(term_drain).build().fuse();
@@ -16,7 +16,7 @@ pub fn launch_logger() -> GlobalLoggerGuard {
         .create(true)
         .write(true)
         .append(true)
-        .open(LOG_PATH)
+        .open(config.get_path())
         .unwrap();
 
     let file_decorator = slog_term::PlainDecorator::new(file);
@@ -24,7 +24,7 @@ pub fn launch_logger() -> GlobalLoggerGuard {
     let file_drain = slog_async::Async::new(file_drain).build().fuse();
 
     let broadcaster = Duplicate::new(term_drain, file_drain)
-        .filter_level(LOG_FILTER_LEVEL);
+        .filter_level(config.get_level());
 
     let logger = slog::Logger::root(broadcaster.fuse(), o!());
 
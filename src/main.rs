@@ -11,7 +11,11 @@ extern crate serde_derive;
 #[macro_use(o)]
 extern crate slog;
 
+use std::process;
+
 use crate::builder::config::RobotAssemblyBuilder;
+use crate::robot_config::RobotConfig;
+use crate::robot_map::CONFIG_PATH;
 
 /// Contains code for benchmarking the performance of the system.
 pub mod benchmarking;
@@ -39,6 +43,16 @@ pub mod mechatronics;
 /// It is used to make reconfiguring pinouts a simpler process.
 pub mod robot_map;
 
+/// Contains the runtime configuration of the robot, which is loaded from a file at startup and
+/// falls back to the values in the robot map.
+pub mod robot_config;
+
+/// Contains a physics simulation of the robot, used to practise driving without the hardware.
+pub mod simulation;
+
+/// Contains the recorder which writes the robot state to disk during a run.
+pub mod recording;
+
 /// Contains code used to start the global logger.
 pub mod logging;
 
@@ -55,8 +69,16 @@ pub mod sensors;
 mod integration_tests;
 
 fn main() {
-    let _logging_guard = logging::launch_logger();
-    let mut builder = RobotAssemblyBuilder::new();
+    let config = match RobotConfig::load_or_default(CONFIG_PATH) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", CONFIG_PATH, e);
+            process::exit(1);
+        }
+    };
+
+    let _logging_guard = logging::launch_logger(config.get_logging());
+    let mut builder = RobotAssemblyBuilder::from_config(config);
 
 //    builder.with_production();
 
@@ -1,13 +1,20 @@
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::time::Instant;
 
 use atomic::Ordering;
 
+use crate::mechatronics::bucket_ladder::position::PositionEstimator;
+use crate::mechatronics::bucket_ladder::state::actuator::GlobalActuatorLimitState;
 use crate::mechatronics::bucket_ladder::state::GlobalIntakeState;
+use crate::mechatronics::plausibility::LimitPlausibility;
+use crate::mechatronics::plausibility::MechanismFault;
+use crate::mechatronics::plausibility::Travel;
 use crate::motor_controllers::MotorController;
-use crate::robot_map::*;
+use crate::pinouts::analog::input::AnalogInput;
+use crate::robot_config::IntakeConfig;
 use crate::status::life::GlobalLifeState;
 
+pub mod position;
 pub mod state;
 
 #[cfg(test)]
@@ -16,28 +23,122 @@ mod tests;
 enum IntakeActuatorAction {
     Rising,
     Falling,
+    /// Moving towards a position, stopping once it is reached.
+    MovingTo { target: f32, lowering: bool },
     Stopped,
 }
 
+/// How the actuators which raise and lower the intake are driven.
+pub enum ActuatorLayout {
+    /// One motor drives both actuators, and stops as soon as either reaches a limit.
+    Single {
+        motor: Box<MotorController>,
+        potentiometer: Option<Box<AnalogInput>>,
+    },
+    /// Each actuator is driven by its own motor, which stops at that actuator's own limits.
+    Dual {
+        left: Box<MotorController>,
+        right: Box<MotorController>,
+        left_potentiometer: Option<Box<AnalogInput>>,
+        right_potentiometer: Option<Box<AnalogInput>>,
+    },
+}
+
+/// A motor driving one or both actuators, along with the limits of the actuators it drives.
+struct ActuatorDrive {
+    motor: Box<MotorController>,
+    limits: Vec<Arc<GlobalActuatorLimitState>>,
+    position: PositionEstimator,
+    /// The fraction of full speed the motor was last run at, which is zero while it is stopped.
+    pace: f32,
+}
+
+impl ActuatorDrive {
+    fn new(motor: Box<MotorController>, limits: Vec<Arc<GlobalActuatorLimitState>>,
+           potentiometer: Option<Box<AnalogInput>>, config: &IntakeConfig) -> Self {
+        Self {
+            motor,
+            limits,
+            position: PositionEstimator::new(potentiometer, config.get_actuator_position().clone()),
+            pace: 0.0,
+        }
+    }
+
+    /// Runs the motor at `speed`, which is `pace` of the actuators' full speed.
+    fn run(&mut self, speed: f32, pace: f32) {
+        self.motor.set_speed(speed);
+        self.pace = pace;
+    }
+
+    fn stop(&mut self) {
+        self.motor.stop();
+        self.pace = 0.0;
+    }
+
+    fn raised(&self) -> bool {
+        self.limits.iter().any(|limits| limits.get_upper().load(Ordering::SeqCst))
+    }
+
+    fn lowered(&self) -> bool {
+        self.limits.iter().any(|limits| limits.get_lower().load(Ordering::SeqCst))
+    }
+
+    /// Updates the estimated position of the actuators this motor drives.
+    fn update_position(&mut self) -> Option<f32> {
+        let (raised, lowered) = (self.raised(), self.lowered());
+        let position = self.position.update(self.motor.get_motor_state().get_speed(), raised, lowered);
+        for limits in &self.limits {
+            limits.set_position(position);
+        }
+        position
+    }
+}
+
 pub struct Intake {
-    actuator: Box<MotorController>,
+    actuators: Vec<ActuatorDrive>,
+    /// Checks the limits of the left and right actuators.
+    limit_checks: [LimitPlausibility; 2],
     ladder: Box<MotorController>,
     state: Arc<GlobalIntakeState>,
     life: Arc<GlobalLifeState>,
     enabled_cache: bool,
     action: IntakeActuatorAction,
+    digging: bool,
+    digger_scale: f32,
+    actuator_scale: f32,
+    config: IntakeConfig,
 }
 
 impl Intake {
-    pub fn new(ladder: Box<MotorController>, actuator: Box<MotorController>, state: Arc<GlobalIntakeState>, life: Arc<GlobalLifeState>) -> Self {
+    pub fn new(ladder: Box<MotorController>, actuators: ActuatorLayout, state: Arc<GlobalIntakeState>,
+               life: Arc<GlobalLifeState>, config: IntakeConfig) -> Self {
         let enabled_cache = state.get_enabled();
+        let (left_limits, right_limits) = (state.get_left_actuator(), state.get_right_actuator());
+        let actuators = match actuators {
+            ActuatorLayout::Single { motor, potentiometer } => vec![
+                ActuatorDrive::new(motor, vec![left_limits, right_limits], potentiometer, &config),
+            ],
+            ActuatorLayout::Dual { left, right, left_potentiometer, right_potentiometer } => vec![
+                ActuatorDrive::new(left, vec![left_limits], left_potentiometer, &config),
+                ActuatorDrive::new(right, vec![right_limits], right_potentiometer, &config),
+            ],
+        };
+        let limit_checks = [
+            LimitPlausibility::new(config.get_limit_check(), config.get_actuator_timeout()),
+            LimitPlausibility::new(config.get_limit_check(), config.get_actuator_timeout()),
+        ];
         Self {
-            actuator,
+            actuators,
+            limit_checks,
             ladder,
             state,
             life,
             enabled_cache,
             action: IntakeActuatorAction::Stopped,
+            digging: false,
+            digger_scale: 1.0,
+            actuator_scale: 1.0,
+            config,
         }
     }
 
@@ -54,44 +155,154 @@ impl Intake {
     }
 
     pub fn raise(&mut self) {
-        if !reached_limit(self.state.get_left_actuator().get_upper(), self.state.get_right_actuator().get_upper()) && self.enabled_cache && self.life.is_alive() {
-            self.actuator.set_speed(MH_ACTUATOR_RATE);
+        if self.run_actuators(false) {
             self.action = IntakeActuatorAction::Rising;
         }
     }
 
     pub fn lower(&mut self) {
-        if !reached_limit(self.state.get_left_actuator().get_lower(), self.state.get_right_actuator().get_lower()) && self.enabled_cache && self.life.is_alive() {
-            self.actuator.set_speed(-MH_ACTUATOR_RATE);
+        if self.run_actuators(true) {
             self.action = IntakeActuatorAction::Falling;
         }
     }
 
+    /// Moves the actuators to `target`, as a fraction of their stroke from the upper limit, and
+    /// stops them there. Does nothing while their position is unknown.
+    pub fn move_to(&mut self, target: f32) {
+        let position = match self.state.get_position() {
+            Some(position) => position,
+            None => {
+                warn!("Cannot move the actuators to {} while their position is unknown", target);
+                return;
+            }
+        };
+
+        if (target - position).abs() <= self.config.get_actuator_position().get_tolerance() {
+            self.stop_actuators();
+            return;
+        }
+
+        let lowering = target > position;
+        if self.run_actuators(lowering) {
+            self.action = IntakeActuatorAction::MovingTo { target, lowering };
+        }
+    }
+
     pub fn stop_actuators(&mut self) {
-        self.actuator.stop();
+        for actuator in &mut self.actuators {
+            actuator.stop();
+        }
         self.action = IntakeActuatorAction::Stopped;
     }
 
+    /// The fault which stopped the actuators, if they refuse to move until it is cleared.
+    pub fn get_fault(&self) -> Option<MechanismFault> {
+        self.state.get_fault()
+    }
+
+    /// Allows the actuators to move again after they were stopped by a fault.
+    pub fn clear_fault(&mut self) {
+        if let Some(fault) = self.state.get_fault() {
+            info!("Clearing intake fault {:?}", fault);
+            self.state.set_fault(None);
+        }
+    }
+
+    /// Whether the digger stalled and refuses to dig until it is stopped.
+    pub fn is_digger_stalled(&self) -> bool {
+        self.ladder.get_motor_state().get_stalled()
+    }
+
+    /// Whether either actuator stalled and refuses to move until the actuators are stopped.
+    pub fn is_actuator_stalled(&self) -> bool {
+        self.actuators.iter().any(|actuator| actuator.motor.get_motor_state().get_stalled())
+    }
+
     pub fn dig(&mut self) {
         if self.is_enabled() && self.life.is_alive() {
-            self.ladder.set_speed(DIGGING_RATE);
+            self.ladder.set_speed(self.config.get_digging_rate() * self.digger_scale);
+            self.digging = true;
         }
     }
 
     pub fn stop_digging(&mut self) {
         self.ladder.stop();
+        self.digging = false;
+    }
+
+    /// Limits the digger to `scale` of its digging rate, including while it is already digging.
+    pub fn set_digger_scale(&mut self, scale: f32) {
+        self.digger_scale = scale;
+        if self.digging {
+            self.dig();
+        }
+    }
+
+    /// Limits the actuators to `scale` of their rate, including while they are already moving.
+    pub fn set_actuator_scale(&mut self, scale: f32) {
+        self.actuator_scale = scale;
+        match self.action {
+            IntakeActuatorAction::Rising => self.raise(),
+            IntakeActuatorAction::Falling => self.lower(),
+            IntakeActuatorAction::MovingTo { target, .. } => self.move_to(target),
+            IntakeActuatorAction::Stopped => {}
+        }
     }
 
     pub fn run_cycle(&mut self) {
+        self.run_cycle_at(Instant::now());
+    }
+
+    fn run_cycle_at(&mut self, now: Instant) {
+        self.ladder.run_cycle();
+        for actuator in &mut self.actuators {
+            actuator.motor.run_cycle();
+        }
+
+        let positions: Vec<Option<f32>> = self.actuators.iter_mut().map(ActuatorDrive::update_position).collect();
+        let position = if positions.iter().all(Option::is_some) {
+            Some(positions.iter().flatten().sum::<f32>() / positions.len() as f32)
+        } else {
+            None
+        };
+        self.state.set_position(position);
+
+        if self.ladder.get_motor_state().get_stalled() {
+            self.digging = false;
+        }
+        self.check_limits(now);
+        if self.is_actuator_stalled() {
+            // Stop the other actuator too, without clearing the stall on the one which stalled.
+            for actuator in &mut self.actuators {
+                if actuator.motor.get_motor_state().get_stalled() {
+                    actuator.pace = 0.0;
+                } else {
+                    actuator.stop();
+                }
+            }
+            self.action = IntakeActuatorAction::Stopped;
+        }
+
         if self.enabled_cache {
             match self.action {
                 IntakeActuatorAction::Rising => {
-                    if reached_limit(self.state.get_left_actuator().get_upper(), self.state.get_right_actuator().get_upper()) {
+                    if !self.run_actuators(false) {
                         self.stop_actuators();
                     }
                 }
                 IntakeActuatorAction::Falling => {
-                    if reached_limit(self.state.get_left_actuator().get_lower(), self.state.get_right_actuator().get_lower()) {
+                    if !self.run_actuators(true) {
+                        self.stop_actuators();
+                    }
+                }
+                IntakeActuatorAction::MovingTo { target, lowering } => {
+                    let tolerance = self.config.get_actuator_position().get_tolerance();
+                    let arrived = match position {
+                        Some(position) if lowering => position >= target - tolerance,
+                        Some(position) => position <= target + tolerance,
+                        None => true,
+                    };
+                    if arrived || !self.run_actuators(lowering) {
                         self.stop_actuators();
                     }
                 }
@@ -102,13 +313,89 @@ impl Intake {
         }
     }
 
+    /// Stops the actuators and holds them faulted if the limits of either contradict the way it is
+    /// moving, or it takes too long to reach the limit it is heading for. Each side's travel is
+    /// timed at the pace its own motor was run at, so a side slowed to correct tilt is allowed
+    /// longer.
+    fn check_limits(&mut self, now: Instant) {
+        let travel = match self.action {
+            IntakeActuatorAction::Rising | IntakeActuatorAction::MovingTo { lowering: false, .. } => Travel::Up,
+            IntakeActuatorAction::Falling | IntakeActuatorAction::MovingTo { lowering: true, .. } => Travel::Down,
+            IntakeActuatorAction::Stopped => Travel::Still,
+        };
+
+        let sides = [self.state.get_left_actuator(), self.state.get_right_actuator()];
+        let mut fault = None;
+        for (side, (check, limits)) in self.limit_checks.iter_mut().zip(sides.iter()).enumerate() {
+            // A single motor drives both sides.
+            let pace = self.actuators[side.min(self.actuators.len() - 1)].pace;
+            let upper = limits.get_upper().load(Ordering::SeqCst);
+            let lower = limits.get_lower().load(Ordering::SeqCst);
+            fault = fault.or(check.check_at(now, travel, pace, upper, lower));
+        }
+
+        if let Some(fault) = fault {
+            if self.state.get_fault().is_none() {
+                error!("Intake actuators faulted ({:?}), stopping them!", fault);
+                self.state.set_fault(Some(fault));
+            }
+            self.stop_actuators();
+        }
+    }
+
+    /// Runs each actuator downwards or upwards, unless it is already at that limit or the intake
+    /// may not move. An actuator which is ahead of the other is slowed until they agree again.
+    /// Returns whether any actuator was started.
+    fn run_actuators(&mut self, lowering: bool) -> bool {
+        if !self.enabled_cache || !self.life.is_alive() || self.state.get_fault().is_some() {
+            return false;
+        }
+
+        let direction = if lowering { -1.0 } else { 1.0 };
+        let speed = direction * self.config.get_actuator_rate();
+        let scale = self.actuator_scale;
+        let tilt_scale = self.config.get_tilt_scale();
+        let leading = self.leading_actuator(lowering);
+
+        let mut running = false;
+        for (index, actuator) in self.actuators.iter_mut().enumerate() {
+            let limit = if lowering { actuator.lowered() } else { actuator.raised() };
+            if limit {
+                actuator.stop();
+            } else if leading == Some(index) {
+                actuator.run(speed * scale * tilt_scale, scale * tilt_scale);
+                running = true;
+            } else {
+                actuator.run(speed * scale, scale);
+                running = true;
+            }
+        }
+        running
+    }
+
+    /// Finds the actuator which has travelled further than the other in the direction of travel,
+    /// if they disagree by more than the tilt tolerance.
+    ///
+    /// Dead reckoned positions only reflect the speeds the actuators were given, so tilt can only
+    /// be corrected when both have a potentiometer.
+    fn leading_actuator(&self, lowering: bool) -> Option<usize> {
+        if self.actuators.len() != 2 || !self.actuators.iter().all(|actuator| actuator.position.has_potentiometer()) {
+            return None;
+        }
+
+        let left = self.state.get_left_actuator().get_position()?;
+        let right = self.state.get_right_actuator().get_position()?;
+        if (left - right).abs() <= self.config.get_tilt_tolerance() {
+            None
+        } else if (left > right) == lowering {
+            Some(0)
+        } else {
+            Some(1)
+        }
+    }
+
     #[inline]
     fn is_enabled(&self) -> bool {
         self.enabled_cache
     }
-}
-
-#[inline]
-fn reached_limit(left: Arc<AtomicBool>, right: Arc<AtomicBool>) -> bool {
-    left.load(Ordering::SeqCst) || right.load(Ordering::SeqCst)
 }
\ No newline at end of file
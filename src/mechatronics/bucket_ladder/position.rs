@@ -0,0 +1,124 @@
+use std::time::Instant;
+
+use crate::framework::seconds;
+use crate::pinouts::analog::input::AnalogInput;
+use crate::robot_config::ActuatorPositionConfig;
+
+/// Estimates how far the intake actuators have travelled from their upper limit, as a fraction of
+/// their full stroke, so that 0 is fully raised and 1 is fully lowered.
+///
+/// With a potentiometer the position is read directly. Without one it is dead reckoned from the
+/// speed the actuators are run at, and is unknown until they first reach one of their limits.
+pub struct PositionEstimator {
+    potentiometer: Option<Box<AnalogInput>>,
+    config: ActuatorPositionConfig,
+    position: Option<f32>,
+    last_update: Instant,
+}
+
+impl PositionEstimator {
+    pub fn new(potentiometer: Option<Box<AnalogInput>>, config: ActuatorPositionConfig) -> Self {
+        Self {
+            potentiometer,
+            config,
+            position: None,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// Whether the position is read from a potentiometer rather than dead reckoned.
+    pub fn has_potentiometer(&self) -> bool {
+        self.potentiometer.is_some()
+    }
+
+    /// Updates the estimate, given the speed the actuators have been running at since the last
+    /// update, where positive speeds raise them, and whether they are at either limit.
+    pub fn update(&mut self, speed: f32, raised: bool, lowered: bool) -> Option<f32> {
+        self.update_at(Instant::now(), speed, raised, lowered)
+    }
+
+    fn update_at(&mut self, now: Instant, speed: f32, raised: bool, lowered: bool) -> Option<f32> {
+        let elapsed = now.duration_since(self.last_update);
+        let elapsed = seconds(elapsed);
+        self.last_update = now;
+
+        let upper_volts = self.config.get_upper_volts();
+        let lower_volts = self.config.get_lower_volts();
+        self.position = if let Some(potentiometer) = &mut self.potentiometer {
+            potentiometer.get_value()
+                .map(|volts| clamp((volts - upper_volts) / (lower_volts - upper_volts)))
+        } else if raised {
+            Some(0.0)
+        } else if lowered {
+            Some(1.0)
+        } else {
+            let stroke = self.config.get_stroke_time();
+            let stroke = seconds(stroke);
+            self.position.map(|position| clamp(position - speed * elapsed / stroke))
+        };
+
+        self.position
+    }
+}
+
+fn clamp(position: f32) -> f32 {
+    position.max(0.0).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use atomic::Atomic;
+
+    use crate::pinouts::analog::input::TestAnalogInput;
+
+    use super::*;
+
+    fn setup(potentiometer: Option<Box<AnalogInput>>) -> (PositionEstimator, Instant) {
+        let config = ActuatorPositionConfig::default().with_stroke_millis(1000).with_upper_volts(1.5).with_lower_volts(0.5);
+        let estimator = PositionEstimator::new(potentiometer, config);
+        let start = estimator.last_update;
+
+        (estimator, start)
+    }
+
+    #[test]
+    fn unknown_until_limit() {
+        let (mut estimator, start) = setup(None);
+
+        assert_eq!(None, estimator.update_at(start + Duration::from_millis(100), -1.0, false, false));
+        assert_eq!(Some(0.0), estimator.update_at(start + Duration::from_millis(200), -1.0, true, false));
+    }
+
+    #[test]
+    fn travel_time() {
+        let (mut estimator, start) = setup(None);
+        estimator.update_at(start, 0.0, true, false);
+
+        assert_eq!(Some(0.25), estimator.update_at(start + Duration::from_millis(250), -1.0, false, false));
+        assert_eq!(Some(0.375), estimator.update_at(start + Duration::from_millis(500), -0.5, false, false));
+        assert_eq!(Some(0.125), estimator.update_at(start + Duration::from_millis(750), 1.0, false, false));
+        assert_eq!(Some(0.0), estimator.update_at(start + Duration::from_millis(1000), 1.0, false, false));
+        assert_eq!(Some(1.0), estimator.update_at(start + Duration::from_millis(1250), 0.0, false, true));
+    }
+
+    #[test]
+    fn potentiometer() {
+        let volts = Arc::new(Atomic::new(Some(1.0)));
+        let (mut estimator, _) = setup(Some(Box::new(TestAnalogInput::new(volts.clone()))));
+
+        assert_eq!(Some(0.5), estimator.update(0.0, false, false));
+
+        // The potentiometer is trusted over the limit switches.
+        volts.store(Some(1.5), atomic::Ordering::SeqCst);
+        assert_eq!(Some(0.0), estimator.update(0.0, false, true));
+
+        volts.store(Some(0.0), atomic::Ordering::SeqCst);
+        assert_eq!(Some(1.0), estimator.update(0.0, false, false));
+
+        volts.store(None, atomic::Ordering::SeqCst);
+        assert_eq!(None, estimator.update(0.0, false, false));
+    }
+}
@@ -2,9 +2,13 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use atomic::Atomic;
+
+/// The limit switches of one of the intake actuators, along with its estimated position.
 pub struct GlobalActuatorLimitState {
     upper: Arc<AtomicBool>,
     lower: Arc<AtomicBool>,
+    position: Atomic<Option<f32>>,
 }
 
 impl GlobalActuatorLimitState {
@@ -12,6 +16,7 @@ impl GlobalActuatorLimitState {
         GlobalActuatorLimitState {
             upper: Arc::new(AtomicBool::new(false)),
             lower: Arc::new(AtomicBool::new(false)),
+            position: Atomic::new(None),
         }
     }
 
@@ -19,6 +24,7 @@ impl GlobalActuatorLimitState {
         ActuatorLimitStateInstance::new(
             self.upper.load(Ordering::Relaxed),
             self.lower.load(Ordering::Relaxed),
+            self.get_position(),
         )
     }
 
@@ -37,19 +43,30 @@ impl GlobalActuatorLimitState {
     pub fn get_lower(&self) -> Arc<AtomicBool> {
         self.lower.clone()
     }
+
+    /// How far this actuator is from its upper limit, as a fraction of its stroke, if it is known.
+    pub fn get_position(&self) -> Option<f32> {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    pub fn set_position(&self, position: Option<f32>) {
+        self.position.store(position, Ordering::Relaxed);
+    }
 }
 
 #[derive(Serialize)]
 pub struct ActuatorLimitStateInstance {
     upper: bool,
     lower: bool,
+    position: Option<f32>,
 }
 
 impl ActuatorLimitStateInstance {
-    fn new(upper: bool, lower: bool) -> Self {
+    fn new(upper: bool, lower: bool, position: Option<f32>) -> Self {
         ActuatorLimitStateInstance {
             upper,
             lower,
+            position,
         }
     }
 
@@ -60,4 +77,8 @@ impl ActuatorLimitStateInstance {
     pub fn get_lower(&self) -> bool {
         self.lower
     }
+
+    pub fn get_position(&self) -> Option<f32> {
+        self.position
+    }
 }
\ No newline at end of file
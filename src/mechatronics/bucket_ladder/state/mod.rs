@@ -2,8 +2,11 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use atomic::Atomic;
+
 use crate::mechatronics::bucket_ladder::state::actuator::ActuatorLimitStateInstance;
 use crate::mechatronics::bucket_ladder::state::actuator::GlobalActuatorLimitState;
+use crate::mechatronics::plausibility::MechanismFault;
 use crate::motor_controllers::{GlobalMotorState, MotorStateInstance};
 
 pub mod actuator;
@@ -12,8 +15,11 @@ pub struct GlobalIntakeState {
     left_limits: Arc<GlobalActuatorLimitState>,
     right_limits: Arc<GlobalActuatorLimitState>,
     actuator: Arc<GlobalMotorState>,
+    right_actuator_motor: Arc<GlobalMotorState>,
     digger: Arc<GlobalMotorState>,
     enabled: AtomicBool,
+    position: Atomic<Option<f32>>,
+    fault: Atomic<Option<MechanismFault>>,
 }
 
 impl GlobalIntakeState {
@@ -22,19 +28,25 @@ impl GlobalIntakeState {
             left_limits: Arc::new(GlobalActuatorLimitState::new()),
             right_limits: Arc::new(GlobalActuatorLimitState::new()),
             actuator: Arc::new(GlobalMotorState::new()),
+            right_actuator_motor: Arc::new(GlobalMotorState::new()),
             digger: Arc::new(GlobalMotorState::new()),
             enabled: AtomicBool::new(false),
+            position: Atomic::new(None),
+            fault: Atomic::new(None),
         }
     }
 
     pub fn get_current_state(&self) -> IntakeStateInstance {
-        IntakeStateInstance::new(
-            self.left_limits.get_current_state(),
-            self.right_limits.get_current_state(),
-            self.actuator.get_current_state(),
-            self.digger.get_current_state(),
-            self.enabled.load(Ordering::SeqCst),
-        )
+        IntakeStateInstance {
+            left_limits: self.left_limits.get_current_state(),
+            right_limits: self.right_limits.get_current_state(),
+            actuator: self.actuator.get_current_state(),
+            right_actuator_motor: self.right_actuator_motor.get_current_state(),
+            digger: self.digger.get_current_state(),
+            enabled: self.enabled.load(Ordering::SeqCst),
+            position: self.get_position(),
+            fault: self.get_fault(),
+        }
     }
 
     pub fn set_enabled(&self, enabled: bool) {
@@ -53,13 +65,38 @@ impl GlobalIntakeState {
         self.digger.clone()
     }
 
+    /// The motor driving both actuators, or only the left one when each has its own motor.
     pub fn get_actuator(&self) -> Arc<GlobalMotorState> {
         self.actuator.clone()
     }
 
+    /// The motor driving the right actuator, when each actuator has its own motor.
+    pub fn get_right_actuator_motor(&self) -> Arc<GlobalMotorState> {
+        self.right_actuator_motor.clone()
+    }
+
     pub fn get_enabled(&self) -> bool {
         self.enabled.load(Ordering::Relaxed)
     }
+
+    /// How far the actuators are from their upper limit, as a fraction of their full stroke, if it
+    /// is known.
+    pub fn get_position(&self) -> Option<f32> {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    pub fn set_position(&self, position: Option<f32>) {
+        self.position.store(position, Ordering::Relaxed);
+    }
+
+    /// The fault which stopped the actuators, if they refuse to move until the fault is cleared.
+    pub fn get_fault(&self) -> Option<MechanismFault> {
+        self.fault.load(Ordering::Relaxed)
+    }
+
+    pub fn set_fault(&self, fault: Option<MechanismFault>) {
+        self.fault.store(fault, Ordering::Relaxed);
+    }
 }
 
 #[derive(Serialize)]
@@ -67,21 +104,14 @@ pub struct IntakeStateInstance {
     left_limits: ActuatorLimitStateInstance,
     right_limits: ActuatorLimitStateInstance,
     actuator: MotorStateInstance,
+    right_actuator_motor: MotorStateInstance,
     digger: MotorStateInstance,
     enabled: bool,
+    position: Option<f32>,
+    fault: Option<MechanismFault>,
 }
 
 impl IntakeStateInstance {
-    fn new(left_limits: ActuatorLimitStateInstance, right_limits: ActuatorLimitStateInstance, actuator: MotorStateInstance, digger: MotorStateInstance, enabled: bool) -> Self {
-        Self {
-            left_limits,
-            right_limits,
-            actuator,
-            digger,
-            enabled,
-        }
-    }
-
     pub fn get_left_actuator(&self) -> &ActuatorLimitStateInstance {
         &self.left_limits
     }
@@ -94,6 +124,10 @@ impl IntakeStateInstance {
         &self.actuator
     }
 
+    pub fn get_right_actuator_motor(&self) -> &MotorStateInstance {
+        &self.right_actuator_motor
+    }
+
     pub fn get_digger(&self) -> &MotorStateInstance {
         &self.digger
     }
@@ -101,4 +135,12 @@ impl IntakeStateInstance {
     pub fn get_enabled(&self) -> bool {
         self.enabled
     }
+
+    pub fn get_position(&self) -> Option<f32> {
+        self.position
+    }
+
+    pub fn get_fault(&self) -> Option<MechanismFault> {
+        self.fault
+    }
 }
\ No newline at end of file
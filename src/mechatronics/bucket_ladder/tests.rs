@@ -1,6 +1,16 @@
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
+use atomic::Atomic;
+
+use crate::mechatronics::plausibility::MechanismFault;
+use crate::motor_controllers::decorators::stall::StallDetectingMotor;
 use crate::motor_controllers::test_motor::TestMotor;
+use crate::pinouts::analog::input::TestAnalogInput;
+use crate::robot_config::ActuatorPositionConfig;
+use crate::robot_config::StallConfig;
+use crate::robot_map::*;
 
 use super::*;
 
@@ -11,7 +21,8 @@ fn setup() -> (Arc<GlobalLifeState>, Arc<GlobalIntakeState>, Intake) {
     let actuator = Box::new(TestMotor::new(state.get_actuator()));
 
     let life = Arc::new(GlobalLifeState::new());
-    let intake = Intake::new(ladder, actuator, state.clone(), life.clone());
+    let actuators = ActuatorLayout::Single { motor: actuator, potentiometer: None };
+    let intake = Intake::new(ladder, actuators, state.clone(), life.clone(), IntakeConfig::default());
 
     (life, state, intake)
 }
@@ -307,4 +318,309 @@ fn lower_both_limit_stasis() {
     intake.lower();
 
     assert_eq!(0.0, state.get_actuator().get_speed());
-}
\ No newline at end of file
+}
+
+#[test]
+fn implausible_limits_fault() {
+    let (_, state, mut intake) = setup();
+    intake.enable();
+    intake.raise();
+
+    state.get_right_actuator().set_upper(true);
+    state.get_right_actuator().set_lower(true);
+    intake.run_cycle();
+    assert_eq!(0.0, state.get_actuator().get_speed());
+    assert_eq!(Some(MechanismFault::BothLimits), state.get_current_state().get_fault());
+
+    // With neither limit asserted, only the fault holds the actuators still.
+    state.get_right_actuator().set_upper(false);
+    state.get_right_actuator().set_lower(false);
+    intake.lower();
+    assert_eq!(0.0, state.get_actuator().get_speed());
+
+    // Digging does not depend on the actuator limits.
+    intake.dig();
+    assert_eq!(DIGGING_RATE, state.get_digger().get_speed());
+
+    intake.clear_fault();
+    intake.raise();
+    assert_eq!(None, state.get_fault());
+    assert_eq!(MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+}
+
+#[test]
+fn actuator_timeout() {
+    let state = Arc::new(GlobalIntakeState::new());
+    let config = IntakeConfig::default().with_actuator_timeout_millis(20);
+    let ladder = Box::new(TestMotor::new(state.get_digger()));
+    let actuators = ActuatorLayout::Single { motor: Box::new(TestMotor::new(state.get_actuator())), potentiometer: None };
+    let mut intake = Intake::new(ladder, actuators, state.clone(), Arc::new(GlobalLifeState::new()), config);
+    intake.enable();
+
+    let start = Instant::now();
+    intake.raise();
+    intake.run_cycle_at(start);
+    intake.run_cycle_at(start + Duration::from_millis(30));
+
+    assert_eq!(0.0, state.get_actuator().get_speed());
+    assert_eq!(Some(MechanismFault::Timeout), state.get_current_state().get_fault());
+}
+
+#[test]
+fn digger_scale() {
+    let (_, state, mut intake) = setup();
+
+    intake.enable();
+    intake.dig();
+    intake.set_digger_scale(0.5);
+    assert_eq!(DIGGING_RATE * 0.5, state.get_digger().get_speed());
+
+    intake.stop_digging();
+    intake.set_digger_scale(1.0);
+    assert_eq!(0.0, state.get_digger().get_speed());
+}
+
+#[test]
+fn actuator_scale() {
+    let (_, state, mut intake) = setup();
+
+    intake.enable();
+    intake.lower();
+    intake.set_actuator_scale(0.5);
+    assert_eq!(-MH_ACTUATOR_RATE * 0.5, state.get_actuator().get_speed());
+
+    intake.raise();
+    assert_eq!(MH_ACTUATOR_RATE * 0.5, state.get_actuator().get_speed());
+}
+
+#[test]
+fn digger_stall() {
+    let state = Arc::new(GlobalIntakeState::new());
+    let config = IntakeConfig::default().with_digger_stall(StallConfig::default().with_current(MOTOR_STALL_CURRENT).with_time_millis(0));
+    let ladder = Box::new(TestMotor::new(state.get_digger()));
+    let ladder = Box::new(StallDetectingMotor::new(ladder, None, config.get_digger_stall().clone()));
+    let actuator = Box::new(TestMotor::new(state.get_actuator()));
+    let actuators = ActuatorLayout::Single { motor: actuator, potentiometer: None };
+    let mut intake = Intake::new(ladder, actuators, state.clone(), Arc::new(GlobalLifeState::new()), config);
+
+    intake.enable();
+    intake.dig();
+    state.get_digger().set_current(Some(MOTOR_STALL_CURRENT));
+    intake.run_cycle();
+
+    assert_eq!(0.0, state.get_digger().get_speed());
+    assert_eq!(true, state.get_digger().get_stalled());
+
+    intake.set_digger_scale(0.5);
+    assert_eq!(0.0, state.get_digger().get_speed());
+}
+
+fn setup_potentiometer() -> (Arc<Atomic<Option<f32>>>, Arc<GlobalIntakeState>, Intake) {
+    let state = Arc::new(GlobalIntakeState::new());
+    let volts = Arc::new(Atomic::new(Some(0.0)));
+    let position = ActuatorPositionConfig::default().with_tolerance(0.125).with_upper_volts(0.0).with_lower_volts(1.0);
+    let config = IntakeConfig::default().with_actuator_position(position);
+
+    let ladder = Box::new(TestMotor::new(state.get_digger()));
+    let actuator = Box::new(TestMotor::new(state.get_actuator()));
+    let potentiometer = Box::new(TestAnalogInput::new(volts.clone()));
+    let actuators = ActuatorLayout::Single { motor: actuator, potentiometer: Some(potentiometer) };
+    let intake = Intake::new(ladder, actuators, state.clone(), Arc::new(GlobalLifeState::new()), config);
+
+    (volts, state, intake)
+}
+
+#[test]
+fn position() {
+    let (volts, state, mut intake) = setup_potentiometer();
+    assert_eq!(None, state.get_current_state().get_position());
+
+    volts.store(Some(0.25), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+    assert_eq!(Some(0.25), state.get_current_state().get_position());
+}
+
+#[test]
+fn move_to() {
+    let (volts, state, mut intake) = setup_potentiometer();
+    intake.enable();
+
+    // The position must be known before the actuators can be moved to one.
+    intake.move_to(0.5);
+    assert_eq!(0.0, state.get_actuator().get_speed());
+
+    intake.run_cycle();
+    intake.move_to(0.5);
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+
+    volts.store(Some(0.25), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+
+    volts.store(Some(0.375), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+    assert_eq!(0.0, state.get_actuator().get_speed());
+
+    intake.move_to(0.0);
+    assert_eq!(MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+
+    intake.move_to(0.5);
+    assert_eq!(0.0, state.get_actuator().get_speed());
+}
+
+#[test]
+fn move_to_stops_at_limit() {
+    let (volts, state, mut intake) = setup_potentiometer();
+    intake.enable();
+    volts.store(Some(0.5), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+
+    intake.move_to(1.0);
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+
+    state.get_left_actuator().set_lower(true);
+    intake.run_cycle();
+    assert_eq!(0.0, state.get_actuator().get_speed());
+}
+
+fn dual_intake(state: &Arc<GlobalIntakeState>, right: Box<MotorController>, left_potentiometer: Option<Box<AnalogInput>>,
+               right_potentiometer: Option<Box<AnalogInput>>) -> Intake {
+    let position = ActuatorPositionConfig::default().with_upper_volts(0.0).with_lower_volts(1.0);
+    let config = IntakeConfig::default().with_tilt_tolerance(0.125).with_tilt_scale(0.5).with_actuator_position(position);
+
+    let ladder = Box::new(TestMotor::new(state.get_digger()));
+    let left = Box::new(TestMotor::new(state.get_actuator()));
+    let actuators = ActuatorLayout::Dual { left, right, left_potentiometer, right_potentiometer };
+    let mut intake = Intake::new(ladder, actuators, state.clone(), Arc::new(GlobalLifeState::new()), config);
+    intake.enable();
+    intake
+}
+
+fn setup_dual() -> (Arc<Atomic<Option<f32>>>, Arc<Atomic<Option<f32>>>, Arc<GlobalIntakeState>, Intake) {
+    let state = Arc::new(GlobalIntakeState::new());
+    let left_volts = Arc::new(Atomic::new(Some(0.0)));
+    let right_volts = Arc::new(Atomic::new(Some(0.0)));
+
+    let right = Box::new(TestMotor::new(state.get_right_actuator_motor()));
+    let left_potentiometer = Box::new(TestAnalogInput::new(left_volts.clone()));
+    let right_potentiometer = Box::new(TestAnalogInput::new(right_volts.clone()));
+    let intake = dual_intake(&state, right, Some(left_potentiometer), Some(right_potentiometer));
+
+    (left_volts, right_volts, state, intake)
+}
+
+#[test]
+fn dual_actuators_stop_at_own_limits() {
+    let (_, _, state, mut intake) = setup_dual();
+
+    intake.lower();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_right_actuator_motor().get_speed());
+
+    state.get_right_actuator().set_lower(true);
+    intake.run_cycle();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+    assert_eq!(0.0, state.get_right_actuator_motor().get_speed());
+
+    state.get_left_actuator().set_lower(true);
+    intake.run_cycle();
+    assert_eq!(0.0, state.get_actuator().get_speed());
+    assert_eq!(0.0, state.get_right_actuator_motor().get_speed());
+
+    intake.raise();
+    assert_eq!(MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+    assert_eq!(MH_ACTUATOR_RATE, state.get_right_actuator_motor().get_speed());
+}
+
+#[test]
+fn tilt_correction() {
+    let (left_volts, right_volts, state, mut intake) = setup_dual();
+
+    intake.lower();
+    left_volts.store(Some(0.5), atomic::Ordering::SeqCst);
+    right_volts.store(Some(0.25), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+
+    assert_eq!(Some(0.5), state.get_current_state().get_left_actuator().get_position());
+    assert_eq!(Some(0.375), state.get_current_state().get_position());
+    assert_eq!(-MH_ACTUATOR_RATE * 0.5, state.get_actuator().get_speed());
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_right_actuator_motor().get_speed());
+
+    // Once the sides are within tolerance, both run at full speed again.
+    right_volts.store(Some(0.375), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+
+    // Going up, the side nearer the top is the one ahead.
+    intake.raise();
+    right_volts.store(Some(0.25), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+    assert_eq!(MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+    assert_eq!(MH_ACTUATOR_RATE * 0.5, state.get_right_actuator_motor().get_speed());
+}
+
+#[test]
+fn slowed_actuator_full_stroke() {
+    let (left_volts, right_volts, state, mut intake) = setup_dual();
+    let stroke = ACTUATOR_STROKE_MILLIS as f32 / 1000.0;
+    let step = Duration::from_millis(100);
+    let start = Instant::now();
+
+    // The right actuator is stiffer and lags behind, so the left one spends much of the stroke
+    // slowed, taking longer to arrive than it would at full speed.
+    let (mut left, mut right) = (0.0_f32, 0.0_f32);
+    let mut slowed = false;
+    intake.lower();
+    for cycle in 1..=150 {
+        slowed |= state.get_actuator().get_speed() == -MH_ACTUATOR_RATE * 0.5;
+        left = (left - state.get_actuator().get_speed() * 0.1 / stroke).min(1.0);
+        right = (right - state.get_right_actuator_motor().get_speed() * 0.075 / stroke).min(1.0);
+        left_volts.store(Some(left), atomic::Ordering::SeqCst);
+        right_volts.store(Some(right), atomic::Ordering::SeqCst);
+        state.get_left_actuator().set_lower(left >= 1.0);
+        state.get_right_actuator().set_lower(right >= 1.0);
+        intake.run_cycle_at(start + step * cycle);
+    }
+
+    assert_eq!(true, slowed);
+    assert_eq!(None, state.get_current_state().get_fault());
+    assert_eq!(true, state.get_left_actuator().get_lower().load(Ordering::SeqCst));
+    assert_eq!(true, state.get_right_actuator().get_lower().load(Ordering::SeqCst));
+}
+
+#[test]
+fn tilt_needs_both_potentiometers() {
+    let state = Arc::new(GlobalIntakeState::new());
+    let left_volts = Arc::new(Atomic::new(Some(0.5)));
+    let right = Box::new(TestMotor::new(state.get_right_actuator_motor()));
+    let left_potentiometer = Box::new(TestAnalogInput::new(left_volts));
+    let mut intake = dual_intake(&state, right, Some(left_potentiometer), None);
+
+    // The right actuator's position is known from its upper limit, but is only a guess after that.
+    state.get_right_actuator().set_upper(true);
+    intake.run_cycle();
+    intake.lower();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_actuator().get_speed());
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_right_actuator_motor().get_speed());
+}
+
+#[test]
+fn right_stall_stops_both_actuators() {
+    let state = Arc::new(GlobalIntakeState::new());
+    let right_volts = Arc::new(Atomic::new(Some(0.0)));
+    let stall = StallConfig::default().with_current(MOTOR_STALL_CURRENT).with_time_millis(0);
+    let right = Box::new(TestMotor::new(state.get_right_actuator_motor()));
+    let right_sensor = Box::new(TestAnalogInput::new(right_volts.clone()));
+    let right = Box::new(StallDetectingMotor::new(right, Some(right_sensor), stall));
+    let mut intake = dual_intake(&state, right, None, None);
+
+    intake.lower();
+    intake.run_cycle();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_right_actuator_motor().get_speed());
+
+    right_volts.store(Some(CURRENT_ZERO_VOLTS + MOTOR_STALL_CURRENT / CURRENT_AMPS_PER_VOLT), atomic::Ordering::SeqCst);
+    intake.run_cycle();
+    assert_eq!(true, state.get_right_actuator_motor().get_stalled());
+    assert_eq!(0.0, state.get_right_actuator_motor().get_speed());
+    assert_eq!(0.0, state.get_actuator().get_speed());
+}
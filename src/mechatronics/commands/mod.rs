@@ -1,18 +1,65 @@
+use crate::mechatronics::controller::MechState;
 use crate::mechatronics::controller::RobotController;
+use crate::mechatronics::routines::Routine;
+use crate::mechatronics::routines::RoutineId;
+use crate::status::life::KillReason;
 
 pub trait RobotCommand: Send {
     fn execute(&self, controller: &mut RobotController);
+
+    /// Describes this command in a form which can be journaled and turned back into a command.
+    fn record(&self) -> CommandRecord;
+}
+
+/// A serializable description of a `RobotCommand`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CommandRecord {
+    Kill,
+    Revive,
+    Drive { left: f32, right: f32 },
+    ArcadeDrive { throttle: f32, turn: f32 },
+    CurvatureDrive { throttle: f32, curvature: f32 },
+    SwitchMode { mode: MechState },
+    /// Read from journals recorded before `SwitchMode`, and replayed as a switch to `Driving`.
+    #[serde(skip_serializing)]
+    DriveSwitch,
+    /// Read from journals recorded before `SwitchMode`, and replayed as a switch to `Digging`.
+    #[serde(skip_serializing)]
+    IntakeSwitch,
+    /// Read from journals recorded before `SwitchMode`, and replayed as a switch to `Dumping`.
+    #[serde(skip_serializing)]
+    DumperSwitch,
+    Brake,
+    ResetPose,
+    Dig,
+    StopDigger,
+    Dump,
+    StopDumper,
+    ResetDumper,
+    CycleDumper,
+    ClearDumperFault,
+    RaiseActuators,
+    LowerActuators,
+    StopActuators,
+    MoveActuators { fraction: f32 },
+    ClearActuatorFault,
+    Heartbeat,
+    StartRoutine { routine: RoutineId },
+    PauseRoutine,
+    ResumeRoutine,
+    CancelRoutine,
+    SetPrecision { precision: bool },
 }
 
 pub struct KillCommand {}
 
 impl RobotCommand for KillCommand {
     fn execute(&self, controller: &mut RobotController) {
-        controller.get_life().kill();
-        controller.get_dumper().stop();
-        controller.get_drive_train().brake();
-        controller.get_intake().stop_actuators();
-        controller.get_intake().stop_digging();
+        controller.kill(KillReason::Operator);
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Kill
     }
 }
 
@@ -23,45 +70,90 @@ pub struct DriveCommand {
 
 impl RobotCommand for DriveCommand {
     fn execute(&self, controller: &mut RobotController) {
-        controller.get_drive_train().drive(self.left, self.right)
+        let shaper = controller.get_shaper();
+        let left = shaper.shape(self.left);
+        let right = shaper.shape(self.right);
+        controller.get_drive_train().drive(left, right)
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Drive { left: self.left, right: self.right }
     }
 }
 
-pub struct ReviveCommand {}
+pub struct ArcadeDriveCommand {
+    throttle: f32,
+    turn: f32,
+}
 
-impl RobotCommand for ReviveCommand {
+impl RobotCommand for ArcadeDriveCommand {
     fn execute(&self, controller: &mut RobotController) {
-        controller.get_life().revive();
+        let shaper = controller.get_shaper();
+        let throttle = shaper.shape(self.throttle);
+        let turn = shaper.shape(self.turn);
+        controller.get_drive_train().arcade(throttle, turn)
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::ArcadeDrive { throttle: self.throttle, turn: self.turn }
     }
 }
 
-pub struct DriveSwitchCommand {}
+pub struct CurvatureDriveCommand {
+    throttle: f32,
+    curvature: f32,
+}
 
-impl RobotCommand for DriveSwitchCommand {
+impl RobotCommand for CurvatureDriveCommand {
     fn execute(&self, controller: &mut RobotController) {
-        controller.get_intake().disable();
-        controller.get_dumper().disable();
-        controller.get_drive_train().enable();
+        // The curvature sets the radius of the turn rather than how hard the robot drives, so
+        // only the throttle is shaped.
+        let throttle = controller.get_shaper().shape(self.throttle);
+        controller.get_drive_train().curvature(throttle, self.curvature)
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::CurvatureDrive { throttle: self.throttle, curvature: self.curvature }
     }
 }
 
-pub struct IntakeSwitchCommand {}
+pub struct SetPrecisionCommand {
+    precision: bool,
+}
 
-impl RobotCommand for IntakeSwitchCommand {
+impl RobotCommand for SetPrecisionCommand {
     fn execute(&self, controller: &mut RobotController) {
-        controller.get_dumper().disable();
-        controller.get_drive_train().disable();
-        controller.get_intake().enable();
+        controller.set_precision(self.precision);
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::SetPrecision { precision: self.precision }
     }
 }
 
-pub struct DumperSwitchCommand {}
+pub struct ReviveCommand {}
+
+impl RobotCommand for ReviveCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        controller.get_life().revive();
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Revive
+    }
+}
+
+pub struct SwitchModeCommand {
+    mode: MechState,
+}
 
-impl RobotCommand for DumperSwitchCommand {
+impl RobotCommand for SwitchModeCommand {
     fn execute(&self, controller: &mut RobotController) {
-        controller.get_drive_train().disable();
-        controller.get_intake().disable();
-        controller.get_dumper().enable();
+        controller.switch_mode(self.mode);
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::SwitchMode { mode: self.mode }
     }
 }
 
@@ -71,6 +163,22 @@ impl RobotCommand for BrakeCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_drive_train().brake();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Brake
+    }
+}
+
+pub struct ResetPoseCommand {}
+
+impl RobotCommand for ResetPoseCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        controller.get_drive_train().reset_pose();
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::ResetPose
+    }
 }
 
 pub struct DigCommand {}
@@ -79,6 +187,10 @@ impl RobotCommand for DigCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_intake().dig();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Dig
+    }
 }
 
 pub struct StopDiggerCommand {}
@@ -87,6 +199,10 @@ impl RobotCommand for StopDiggerCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_intake().stop_digging();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::StopDigger
+    }
 }
 
 pub struct DumpCommand {}
@@ -95,6 +211,10 @@ impl RobotCommand for DumpCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_dumper().dump();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Dump
+    }
 }
 
 pub struct StopDumperCommand {}
@@ -103,6 +223,10 @@ impl RobotCommand for StopDumperCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_dumper().stop();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::StopDumper
+    }
 }
 
 pub struct ResetDumperCommand {}
@@ -111,6 +235,34 @@ impl RobotCommand for ResetDumperCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_dumper().reset();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::ResetDumper
+    }
+}
+
+pub struct CycleDumperCommand {}
+
+impl RobotCommand for CycleDumperCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        controller.get_dumper().cycle();
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::CycleDumper
+    }
+}
+
+pub struct ClearDumperFaultCommand {}
+
+impl RobotCommand for ClearDumperFaultCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        controller.clear_dumper_fault();
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::ClearDumperFault
+    }
 }
 
 pub struct StopActuatorsCommand {}
@@ -119,6 +271,10 @@ impl RobotCommand for StopActuatorsCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_intake().stop_actuators();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::StopActuators
+    }
 }
 
 pub struct RaiseActuatorsCommand {}
@@ -127,6 +283,10 @@ impl RobotCommand for RaiseActuatorsCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_intake().raise();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::RaiseActuators
+    }
 }
 
 pub struct LowerActuatorsCommand {}
@@ -135,6 +295,105 @@ impl RobotCommand for LowerActuatorsCommand {
     fn execute(&self, controller: &mut RobotController) {
         controller.get_intake().lower();
     }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::LowerActuators
+    }
+}
+
+pub struct MoveActuatorsCommand {
+    fraction: f32,
+}
+
+impl RobotCommand for MoveActuatorsCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        controller.get_intake().move_to(self.fraction);
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::MoveActuators { fraction: self.fraction }
+    }
+}
+
+pub struct ClearActuatorFaultCommand {}
+
+impl RobotCommand for ClearActuatorFaultCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        controller.clear_intake_fault();
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::ClearActuatorFault
+    }
+}
+
+/// Does nothing on its own; receiving it keeps the communication watchdog from tripping.
+pub struct HeartbeatCommand {}
+
+impl RobotCommand for HeartbeatCommand {
+    fn execute(&self, _controller: &mut RobotController) {}
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Heartbeat
+    }
+}
+
+pub struct StartRoutineCommand {
+    routine: RoutineId,
+}
+
+impl RobotCommand for StartRoutineCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        if controller.get_life().is_alive() {
+            controller.get_routines().start(Routine::builtin(self.routine));
+        } else {
+            warn!("Refusing to start routine {:?} while the robot is dead", self.routine);
+        }
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::StartRoutine { routine: self.routine }
+    }
+}
+
+pub struct PauseRoutineCommand {}
+
+impl RobotCommand for PauseRoutineCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        if controller.get_routines().pause() {
+            controller.stop();
+        }
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::PauseRoutine
+    }
+}
+
+pub struct ResumeRoutineCommand {}
+
+impl RobotCommand for ResumeRoutineCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        controller.get_routines().resume();
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::ResumeRoutine
+    }
+}
+
+pub struct CancelRoutineCommand {}
+
+impl RobotCommand for CancelRoutineCommand {
+    fn execute(&self, controller: &mut RobotController) {
+        if controller.get_routines().cancel() {
+            controller.stop();
+        }
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::CancelRoutine
+    }
 }
 
 pub struct RobotCommandFactory {}
@@ -153,30 +412,48 @@ impl RobotCommandFactory {
         }
     }
 
-    pub fn generate_kill_command(&self) -> KillCommand {
-        KillCommand {}
+    pub fn generate_arcade_drive_command(&self, throttle: f32, turn: f32) -> Option<ArcadeDriveCommand> {
+        let check = |x| x <= 1.0 && x >= -1.0;
+        if check(throttle) && check(turn) {
+            Some(ArcadeDriveCommand { throttle, turn })
+        } else {
+            None
+        }
     }
 
-    pub fn generate_revive_command(&self) -> ReviveCommand {
-        ReviveCommand {}
+    pub fn generate_curvature_drive_command(&self, throttle: f32, curvature: f32) -> Option<CurvatureDriveCommand> {
+        let check = |x| x <= 1.0 && x >= -1.0;
+        if check(throttle) && check(curvature) {
+            Some(CurvatureDriveCommand { throttle, curvature })
+        } else {
+            None
+        }
+    }
+
+    pub fn generate_set_precision_command(&self, precision: bool) -> SetPrecisionCommand {
+        SetPrecisionCommand { precision }
     }
 
-    pub fn generate_drive_switch_command(&self) -> DriveSwitchCommand {
-        DriveSwitchCommand {}
+    pub fn generate_kill_command(&self) -> KillCommand {
+        KillCommand {}
     }
 
-    pub fn generate_intake_switch_command(&self) -> IntakeSwitchCommand {
-        IntakeSwitchCommand {}
+    pub fn generate_revive_command(&self) -> ReviveCommand {
+        ReviveCommand {}
     }
 
-    pub fn generate_dumper_switch_command(&self) -> DumperSwitchCommand {
-        DumperSwitchCommand {}
+    pub fn generate_switch_mode_command(&self, mode: MechState) -> SwitchModeCommand {
+        SwitchModeCommand { mode }
     }
 
     pub fn generate_brake_command(&self) -> BrakeCommand {
         BrakeCommand {}
     }
 
+    pub fn generate_reset_pose_command(&self) -> ResetPoseCommand {
+        ResetPoseCommand {}
+    }
+
     pub fn generate_dig_command(&self) -> DigCommand {
         DigCommand {}
     }
@@ -197,6 +474,14 @@ impl RobotCommandFactory {
         ResetDumperCommand {}
     }
 
+    pub fn generate_cycle_dumper_command(&self) -> CycleDumperCommand {
+        CycleDumperCommand {}
+    }
+
+    pub fn generate_clear_dumper_fault_command(&self) -> ClearDumperFaultCommand {
+        ClearDumperFaultCommand {}
+    }
+
     pub fn generate_raise_actuators_command(&self) -> RaiseActuatorsCommand {
         RaiseActuatorsCommand {}
     }
@@ -208,4 +493,112 @@ impl RobotCommandFactory {
     pub fn generate_stop_actuators_command(&self) -> StopActuatorsCommand {
         StopActuatorsCommand {}
     }
+
+    /// Creates a command to move the actuators to `fraction` of their stroke from the upper limit.
+    pub fn generate_move_actuators_command(&self, fraction: f32) -> Option<MoveActuatorsCommand> {
+        if fraction >= 0.0 && fraction <= 1.0 {
+            Some(MoveActuatorsCommand { fraction })
+        } else {
+            None
+        }
+    }
+
+    pub fn generate_clear_actuator_fault_command(&self) -> ClearActuatorFaultCommand {
+        ClearActuatorFaultCommand {}
+    }
+
+    pub fn generate_heartbeat_command(&self) -> HeartbeatCommand {
+        HeartbeatCommand {}
+    }
+
+    pub fn generate_start_routine_command(&self, routine: RoutineId) -> StartRoutineCommand {
+        StartRoutineCommand { routine }
+    }
+
+    pub fn generate_pause_routine_command(&self) -> PauseRoutineCommand {
+        PauseRoutineCommand {}
+    }
+
+    pub fn generate_resume_routine_command(&self) -> ResumeRoutineCommand {
+        ResumeRoutineCommand {}
+    }
+
+    pub fn generate_cancel_routine_command(&self) -> CancelRoutineCommand {
+        CancelRoutineCommand {}
+    }
+
+    /// Recreates the command described by `record`, returning `None` if it is not a valid command.
+    pub fn from_record(&self, record: CommandRecord) -> Option<Box<RobotCommand>> {
+        Some(match record {
+            CommandRecord::Kill => Box::new(self.generate_kill_command()),
+            CommandRecord::Revive => Box::new(self.generate_revive_command()),
+            CommandRecord::Drive { left, right } => Box::new(self.generate_drive_command(left, right)?),
+            CommandRecord::ArcadeDrive { throttle, turn } => Box::new(self.generate_arcade_drive_command(throttle, turn)?),
+            CommandRecord::CurvatureDrive { throttle, curvature } => Box::new(self.generate_curvature_drive_command(throttle, curvature)?),
+            CommandRecord::SwitchMode { mode } => Box::new(self.generate_switch_mode_command(mode)),
+            CommandRecord::DriveSwitch => Box::new(self.generate_switch_mode_command(MechState::Driving)),
+            CommandRecord::IntakeSwitch => Box::new(self.generate_switch_mode_command(MechState::Digging)),
+            CommandRecord::DumperSwitch => Box::new(self.generate_switch_mode_command(MechState::Dumping)),
+            CommandRecord::Brake => Box::new(self.generate_brake_command()),
+            CommandRecord::ResetPose => Box::new(self.generate_reset_pose_command()),
+            CommandRecord::Dig => Box::new(self.generate_dig_command()),
+            CommandRecord::StopDigger => Box::new(self.generate_stop_digger_command()),
+            CommandRecord::Dump => Box::new(self.generate_dump_command()),
+            CommandRecord::StopDumper => Box::new(self.generate_stop_dumper_command()),
+            CommandRecord::ResetDumper => Box::new(self.generate_reset_dumper_command()),
+            CommandRecord::CycleDumper => Box::new(self.generate_cycle_dumper_command()),
+            CommandRecord::ClearDumperFault => Box::new(self.generate_clear_dumper_fault_command()),
+            CommandRecord::RaiseActuators => Box::new(self.generate_raise_actuators_command()),
+            CommandRecord::LowerActuators => Box::new(self.generate_lower_actuators_command()),
+            CommandRecord::StopActuators => Box::new(self.generate_stop_actuators_command()),
+            CommandRecord::MoveActuators { fraction } => Box::new(self.generate_move_actuators_command(fraction)?),
+            CommandRecord::ClearActuatorFault => Box::new(self.generate_clear_actuator_fault_command()),
+            CommandRecord::Heartbeat => Box::new(self.generate_heartbeat_command()),
+            CommandRecord::StartRoutine { routine } => Box::new(self.generate_start_routine_command(routine)),
+            CommandRecord::PauseRoutine => Box::new(self.generate_pause_routine_command()),
+            CommandRecord::ResumeRoutine => Box::new(self.generate_resume_routine_command()),
+            CommandRecord::CancelRoutine => Box::new(self.generate_cancel_routine_command()),
+            CommandRecord::SetPrecision { precision } => Box::new(self.generate_set_precision_command(precision)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_round_trip() {
+        let factory = RobotCommandFactory::new();
+        let records = [
+            CommandRecord::Kill,
+            CommandRecord::Drive { left: 0.5, right: -1.0 },
+            CommandRecord::ArcadeDrive { throttle: 0.5, turn: 0.25 },
+            CommandRecord::CurvatureDrive { throttle: -1.0, curvature: 0.5 },
+            CommandRecord::SwitchMode { mode: MechState::Dumping },
+            CommandRecord::SwitchMode { mode: MechState::Idle },
+            CommandRecord::ResetPose,
+            CommandRecord::LowerActuators,
+            CommandRecord::MoveActuators { fraction: 0.25 },
+            CommandRecord::ClearActuatorFault,
+            CommandRecord::ClearDumperFault,
+            CommandRecord::Heartbeat,
+            CommandRecord::StartRoutine { routine: RoutineId::Dump },
+            CommandRecord::CancelRoutine,
+            CommandRecord::SetPrecision { precision: true },
+        ];
+
+        for record in records.iter() {
+            assert_eq!(*record, factory.from_record(*record).unwrap().record());
+        }
+    }
+
+    #[test]
+    fn invalid_record() {
+        let factory = RobotCommandFactory::new();
+        assert!(factory.from_record(CommandRecord::Drive { left: 2.0, right: 0.0 }).is_none());
+        assert!(factory.from_record(CommandRecord::ArcadeDrive { throttle: 0.0, turn: -1.5 }).is_none());
+        assert!(factory.from_record(CommandRecord::CurvatureDrive { throttle: 1.1, curvature: 0.0 }).is_none());
+        assert!(factory.from_record(CommandRecord::MoveActuators { fraction: -0.5 }).is_none());
+    }
 }
\ No newline at end of file
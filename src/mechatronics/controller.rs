@@ -2,27 +2,70 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::mpsc::Receiver;
+use std::time::Instant;
+
+use atomic::Atomic;
 
 use crate::framework::Runnable;
 use crate::mechatronics::bucket_ladder::Intake;
+use crate::mechatronics::commands::CommandRecord;
 use crate::mechatronics::commands::RobotCommand;
+use crate::mechatronics::commands::RobotCommandFactory;
 use crate::mechatronics::drive_train::DriveTrain;
 use crate::mechatronics::dumper::Dumper;
+use crate::mechatronics::input_shaping::InputShaper;
+use crate::mechatronics::load_shedding::Load;
+use crate::mechatronics::load_shedding::LoadShedder;
+use crate::mechatronics::CommandSource;
+use crate::mechatronics::RobotMessage;
+use crate::mechatronics::routines::RoutineEngine;
+use crate::recording::journal::CommandJournal;
+use crate::status::current::CurrentUsageLevel;
+use crate::status::faults::GlobalFaultState;
+use crate::status::faults::Severity;
+use crate::status::faults::Subsystem;
 use crate::status::life::GlobalLifeState;
+use crate::status::life::KillReason;
+use crate::status::robot_state::GlobalRobotState;
+use crate::status::watchdog::GlobalWatchdogState;
+
+/// The codes the mechanisms' faults are raised under in the fault registry.
+const INTAKE_FAULT: &str = "intake_limits";
+const DUMPER_FAULT: &str = "dumper_limits";
+const DIGGER_STALL: &str = "digger_stall";
+const ACTUATOR_STALL: &str = "actuator_stall";
+const MISSED_EDGES: &str = "drive_encoders";
 
+/// The mode the robot is operating in, which decides the one mechanism the driver may move.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
 pub enum MechState {
+    /// Every mechanism is disabled.
+    Idle,
     Digging,
     Driving,
     Dumping,
 }
 
 pub struct RobotController {
-    command_receiver: Receiver<Box<RobotCommand>>,
+    command_receiver: Receiver<RobotMessage>,
     drive_train: DriveTrain,
     dumper: Dumper,
     intake: Intake,
     life: Arc<GlobalLifeState>,
     cycles: Arc<AtomicUsize>,
+    watchdog: Arc<GlobalWatchdogState>,
+    faults: Arc<GlobalFaultState>,
+    /// The latched mechanism faults which have been raised in the registry and not yet released.
+    reported: Vec<&'static str>,
+    /// How many encoder edges had been missed when that was last raised in the registry.
+    missed_edges: usize,
+    mode: Arc<Atomic<MechState>>,
+    last_message: Instant,
+    journal: Option<CommandJournal>,
+    routines: RoutineEngine,
+    shedder: LoadShedder,
+    shaper: InputShaper,
+    factory: RobotCommandFactory,
 }
 
 impl Runnable for RobotController {
@@ -31,32 +74,62 @@ impl Runnable for RobotController {
         self.drive_train.disable();
         self.dumper.disable();
         self.intake.disable();
+        self.mode.store(MechState::Idle, Ordering::SeqCst);
+        self.last_message = Instant::now();
     }
 
     fn run(&mut self) {
         if let Ok(message) = self.command_receiver.try_recv() {
-            self.handle_message(message);
+            let source = message.get_source();
+            self.handle_command(message.into_command(), source);
         }
 
+        self.check_watchdog();
+        self.check_current();
+        self.check_faults();
+        self.run_routine();
+
         self.drive_train.run_cycle();
         self.dumper.run_cycle();
         self.intake.run_cycle();
+        self.report_faults();
 
         self.cycles.fetch_add(1, Ordering::SeqCst);
     }
 }
 
 impl RobotController {
-    pub fn new(command_receiver: Receiver<Box<RobotCommand>>, drive_train: DriveTrain,
-               dumper: Dumper, intake: Intake, life: Arc<GlobalLifeState>, cycles: Arc<AtomicUsize>) -> Self {
-        Self {
+    /// Creates a controller for the mechanisms, which reports to and takes its life, watchdog and
+    /// fault state from `state`.
+    pub fn new(command_receiver: Receiver<RobotMessage>, drive_train: DriveTrain, dumper: Dumper, intake: Intake,
+               state: Arc<GlobalRobotState>, shedder: LoadShedder, shaper: InputShaper) -> Self {
+        let mut controller = Self {
             command_receiver,
             drive_train,
             dumper,
             intake,
-            life,
-            cycles,
-        }
+            life: state.get_life(),
+            cycles: state.get_cycle_counter(),
+            watchdog: state.get_watchdog(),
+            faults: state.get_faults(),
+            reported: Vec::new(),
+            missed_edges: 0,
+            mode: state.get_mode(),
+            last_message: Instant::now(),
+            journal: None,
+            routines: RoutineEngine::new(state),
+            shedder,
+            shaper,
+            factory: RobotCommandFactory::new(),
+        };
+
+        controller.apply_limits();
+        controller
+    }
+
+    /// Records every command handled from now on in `journal`.
+    pub fn set_journal(&mut self, journal: CommandJournal) {
+        self.journal = Some(journal);
     }
 
     pub fn get_drive_train(&mut self) -> &mut DriveTrain {
@@ -75,7 +148,256 @@ impl RobotController {
         &self.life
     }
 
+    pub fn get_routines(&mut self) -> &mut RoutineEngine {
+        &mut self.routines
+    }
+
+    /// Kills the robot, aborting any routine and stopping every mechanism.
+    pub fn kill(&mut self, reason: KillReason) {
+        self.life.kill_because(reason);
+        self.routines.abort();
+        self.stop();
+    }
+
+    pub fn get_mode(&self) -> MechState {
+        self.mode.load(Ordering::SeqCst)
+    }
+
+    /// Leaves the current mode and enters `mode`, returning whether the switch was allowed.
+    ///
+    /// Leaving a mode disables its mechanism, stopping it, and entering one enables its mechanism.
+    /// The robot may always go idle, but may only enter another mode while it is alive, and may
+    /// not leave dumping while a dump cycle is running.
+    pub fn switch_mode(&mut self, mode: MechState) -> bool {
+        let current = self.get_mode();
+        if mode == current {
+            return true;
+        }
+
+        if mode != MechState::Idle {
+            if !self.life.is_alive() {
+                warn!("Cannot switch to {:?} while the robot is dead", mode);
+                return false;
+            }
+            if current == MechState::Dumping && self.dumper.is_cycling() {
+                warn!("Cannot switch to {:?} while a dump cycle is running", mode);
+                return false;
+            }
+        }
+
+        self.exit_mode(current);
+        self.enter_mode(mode);
+        self.mode.store(mode, Ordering::SeqCst);
+        info!("Switched from {:?} to {:?}", current, mode);
+        true
+    }
+
+    pub fn get_shaper(&self) -> &InputShaper {
+        &self.shaper
+    }
+
+    /// Turns precision mode on or off, slowing every mechanism to a fraction of its usual speed.
+    pub fn set_precision(&mut self, precision: bool) {
+        self.shaper.set_precision(precision);
+        self.apply_limits();
+    }
+
+    /// Brakes the drive train and stops the intake and dumper.
+    pub fn stop(&mut self) {
+        self.drive_train.brake();
+        self.dumper.stop();
+        self.intake.stop_actuators();
+        self.intake.stop_digging();
+    }
+
     pub fn handle_message(&mut self, command: Box<RobotCommand>) {
+        self.handle_command(command, CommandSource::Local);
+    }
+
+    fn handle_command(&mut self, command: Box<RobotCommand>, source: CommandSource) {
+        self.feed_watchdog();
+        self.execute_command(command, source);
+    }
+
+    fn execute_command(&mut self, command: Box<RobotCommand>, source: CommandSource) {
+        let record = command.record();
+        // Heartbeats arrive several times a second and change nothing, so they would only bury the
+        // commands worth replaying.
+        if record != CommandRecord::Heartbeat {
+            if let Some(journal) = &mut self.journal {
+                if let Err(e) = journal.record(record, source) {
+                    error!("Failed to journal command: {}", e);
+                }
+            }
+        }
         command.execute(self);
     }
+
+    /// Records that the driver station is still talking to us, clearing a tripped watchdog.
+    fn feed_watchdog(&mut self) {
+        self.last_message = Instant::now();
+        if self.watchdog.is_tripped() {
+            info!("Communication restored, clearing the watchdog");
+            self.watchdog.reset();
+        }
+    }
+
+    /// Brakes the drive train and stops the intake and dumper if the driver station has gone
+    /// silent for longer than the watchdog timeout.
+    fn check_watchdog(&mut self) {
+        if !self.watchdog.is_tripped() && self.last_message.elapsed() >= self.watchdog.get_timeout() {
+            warn!("No command received within the watchdog timeout, stopping the robot!");
+            self.faults.raise("watchdog", Subsystem::Comms, Severity::Warning, "No command received within the watchdog timeout");
+            self.watchdog.trip();
+            self.stop();
+        }
+    }
+
+    /// Kills the robot if the current is critical and it is configured to, and otherwise throttles
+    /// or restores the mechanisms as the load shedder sees fit.
+    fn check_current(&mut self) {
+        let critical = self.shedder.get_level() == CurrentUsageLevel::Critical;
+        if critical && self.shedder.get_kill_on_critical() {
+            if self.life.is_alive() {
+                error!("Current is critical, killing the robot!");
+                self.faults.raise("critical_current", Subsystem::Power, Severity::Critical, "Current is critical");
+                self.kill(KillReason::CriticalCurrent);
+            }
+            return;
+        }
+
+        if critical && self.faults.get_fault("critical_current").is_none() {
+            warn!("Current is critical, shedding load instead of killing the robot");
+            self.faults.raise("critical_current", Subsystem::Power, Severity::Warning, "Current is critical");
+        }
+        if self.shedder.update(Instant::now()) {
+            self.apply_limits();
+        }
+    }
+
+    /// Clears the intake's fault from the registry and lets its actuators move again.
+    pub fn clear_intake_fault(&mut self) {
+        self.clear_fault(INTAKE_FAULT);
+        self.intake.clear_fault();
+    }
+
+    /// Clears the dumper's fault from the registry and lets it move again.
+    pub fn clear_dumper_fault(&mut self) {
+        self.clear_fault(DUMPER_FAULT);
+        self.dumper.clear_fault();
+    }
+
+    fn clear_fault(&mut self, code: &str) {
+        self.faults.clear(code);
+        self.reported.retain(|reported| *reported != code);
+    }
+
+    /// Raises the faults latched by the mechanisms in the fault registry, and releases any latch
+    /// whose fault has since been cleared from the registry.
+    fn report_faults(&mut self) {
+        let intake = self.intake.get_fault().map(|fault| format!("Intake actuators stopped: {:?}", fault));
+        if self.mirror_fault(INTAKE_FAULT, Subsystem::Intake, Severity::Error, intake) {
+            self.intake.clear_fault();
+        }
+
+        let dumper = self.dumper.get_fault().map(|fault| format!("Dumper stopped: {:?}", fault));
+        if self.mirror_fault(DUMPER_FAULT, Subsystem::Dumper, Severity::Error, dumper) {
+            self.dumper.clear_fault();
+        }
+
+        // A stalled motor stays stopped until it is next told to stop, which releases the stall.
+        let digger = if self.intake.is_digger_stalled() { Some("The digger stalled".to_owned()) } else { None };
+        if self.mirror_fault(DIGGER_STALL, Subsystem::Intake, Severity::Warning, digger) {
+            self.intake.stop_digging();
+        }
+
+        let actuator = if self.intake.is_actuator_stalled() { Some("An intake actuator stalled".to_owned()) } else { None };
+        if self.mirror_fault(ACTUATOR_STALL, Subsystem::Intake, Severity::Warning, actuator) {
+            self.intake.stop_actuators();
+        }
+
+        let missed_edges = self.drive_train.get_missed_edges();
+        if missed_edges > self.missed_edges {
+            let message = format!("The drive encoders have missed {} edges", missed_edges);
+            self.faults.raise(MISSED_EDGES, Subsystem::Drive, Severity::Warning, &message);
+            self.missed_edges = missed_edges;
+        }
+    }
+
+    /// Raises `code` in the registry when a mechanism first latches it, with `latched` holding the
+    /// message while it stays latched. Returns whether the fault has been cleared from the
+    /// registry while still latched, in which case the latch should be released.
+    fn mirror_fault(&mut self, code: &'static str, subsystem: Subsystem, severity: Severity,
+                    latched: Option<String>) -> bool {
+        match &latched {
+            Some(message) if !self.reported.contains(&code) => {
+                self.faults.raise(code, subsystem, severity, message);
+                self.reported.push(code);
+                false
+            }
+            Some(_) if self.faults.get_fault(code).is_some() => false,
+            _ => {
+                self.reported.retain(|reported| *reported != code);
+                latched.is_some()
+            }
+        }
+    }
+
+    /// Kills the robot, if it is configured to, while a critical fault has not been cleared.
+    fn check_faults(&mut self) {
+        if self.life.is_alive() && self.faults.get_kill_on_critical() && self.faults.has_critical() {
+            error!("A critical fault has not been cleared, killing the robot!");
+            self.kill(KillReason::CriticalFault);
+        }
+    }
+
+    fn enter_mode(&mut self, mode: MechState) {
+        match mode {
+            MechState::Idle => {}
+            MechState::Digging => self.intake.enable(),
+            MechState::Driving => self.drive_train.enable(),
+            MechState::Dumping => self.dumper.enable(),
+        }
+    }
+
+    fn exit_mode(&mut self, mode: MechState) {
+        match mode {
+            MechState::Idle => {}
+            MechState::Digging => self.intake.disable(),
+            MechState::Driving => self.drive_train.disable(),
+            MechState::Dumping => self.dumper.disable(),
+        }
+    }
+
+    /// Limits each mechanism to the speed allowed by both the load shedder and the input shaping
+    /// profile of the mode it moves in.
+    fn apply_limits(&mut self) {
+        let driving = self.shaper.get_driving_limit();
+        let digging = self.shaper.get_digging_limit();
+        let dumping = self.shaper.get_dumping_limit();
+
+        self.drive_train.set_scale(self.shedder.get_scale(Load::Drive) * driving);
+        self.dumper.set_scale(self.shedder.get_scale(Load::Dumper) * dumping);
+        self.intake.set_digger_scale(self.shedder.get_scale(Load::Digger) * digging);
+        self.intake.set_actuator_scale(self.shedder.get_scale(Load::Actuators) * digging);
+    }
+
+    /// Executes the next command of the running routine, aborting it first if the robot has been
+    /// killed or the watchdog has tripped.
+    fn run_routine(&mut self) {
+        if self.routines.is_active() && (!self.life.is_alive() || self.watchdog.is_tripped()) {
+            warn!("Aborting the running routine!");
+            self.routines.abort();
+        }
+
+        if let Some(record) = self.routines.poll() {
+            match self.factory.from_record(record) {
+                Some(command) => self.execute_command(command, CommandSource::Routine),
+                None => {
+                    error!("Routine issued an invalid command {:?}, aborting it", record);
+                    self.routines.abort();
+                }
+            }
+        }
+    }
 }
@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
+use crate::mechatronics::drive_train::odometry::Odometry;
+use crate::mechatronics::drive_train::odometry::Pose;
 use crate::mechatronics::drive_train::state::GlobalDriveTrainState;
 use crate::motor_controllers::MotorController;
+use crate::robot_config::DriveConfig;
 use crate::status::life::GlobalLifeState;
 
+pub mod odometry;
 pub mod state;
 
 #[cfg(test)]
@@ -16,11 +20,15 @@ pub struct DriveTrain {
     right: Box<MotorController>,
     robot_status: Arc<GlobalLifeState>,
     enabled: bool,
+    left_speed: f32,
+    right_speed: f32,
+    scale: f32,
+    odometry: Odometry,
 }
 
 impl DriveTrain {
     pub fn new(state: Arc<GlobalDriveTrainState>, left: Box<MotorController>,
-               right: Box<MotorController>, robot_status: Arc<GlobalLifeState>) -> Self {
+               right: Box<MotorController>, robot_status: Arc<GlobalLifeState>, config: DriveConfig) -> Self {
         let enabled = state.get_enabled();
         Self {
             state,
@@ -28,26 +36,76 @@ impl DriveTrain {
             right,
             robot_status,
             enabled,
+            left_speed: 0.0,
+            right_speed: 0.0,
+            scale: 1.0,
+            odometry: Odometry::new(config.get_odometry()),
         }
     }
 
-    /// Runs a cycle of the drive train, instructing all motors to do what they did last time.
-    pub fn run_cycle(&mut self) {}
+    /// Runs a cycle of the drive train, instructing all motors to do what they did last time and
+    /// updating the robot's pose from the drive encoders, if there are any.
+    pub fn run_cycle(&mut self) {
+        self.left.run_cycle();
+        self.right.run_cycle();
+
+        let left = self.left.get_motor_state().get_position();
+        let right = self.right.get_motor_state().get_position();
+        if let (Some(left), Some(right)) = (left, right) {
+            let pose = self.odometry.update(self.state.get_pose(), left, right);
+            self.state.set_pose(pose);
+        }
+    }
+
+    /// Makes the robot's current position the origin of its pose.
+    pub fn reset_pose(&mut self) {
+        self.state.set_pose(Pose::default());
+    }
+
+    /// How many edges the drive encoders have missed between them since the robot started.
+    pub fn get_missed_edges(&self) -> usize {
+        self.state.get_left_encoder().get_missed() + self.state.get_right_encoder().get_missed()
+    }
 
     /// Drives the robot at the supplied speeds.
     pub fn drive(&mut self, left_speed: f32, right_speed: f32) {
         if self.enabled && self.robot_status.is_alive() {
-            self.left.set_speed(left_speed);
-            self.right.set_speed(right_speed);
+            self.left_speed = left_speed;
+            self.right_speed = right_speed;
+            self.left.set_speed(left_speed * self.scale);
+            self.right.set_speed(right_speed * self.scale);
         }
     }
 
+    /// Drives the robot forwards at `throttle` while turning clockwise at `turn`, as with a single
+    /// joystick.
+    pub fn arcade(&mut self, throttle: f32, turn: f32) {
+        let (left, right) = arcade_speeds(throttle, turn);
+        self.drive(left, right);
+    }
+
+    /// Drives the robot forwards at `throttle` along an arc, where `curvature` sets how sharply it
+    /// turns clockwise regardless of its speed.
+    pub fn curvature(&mut self, throttle: f32, curvature: f32) {
+        let (left, right) = curvature_speeds(throttle, curvature);
+        self.drive(left, right);
+    }
+
     /// Causes the robot to brake.
     pub fn brake(&mut self) {
+        self.left_speed = 0.0;
+        self.right_speed = 0.0;
         self.right.stop();
         self.left.stop();
     }
 
+    /// Limits the drive train to `scale` of the speeds it is commanded, including the current ones.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+        let (left_speed, right_speed) = (self.left_speed, self.right_speed);
+        self.drive(left_speed, right_speed);
+    }
+
     /// Enables the `DriveTrain`.
     pub fn enable(&mut self) {
         self.state.set_enabled(true);
@@ -60,4 +118,26 @@ impl DriveTrain {
         self.enabled = false;
         self.brake();
     }
-}
\ No newline at end of file
+}
+
+/// Mixes a throttle and turn rate into the left and right wheel speeds for arcade drive.
+pub fn arcade_speeds(throttle: f32, turn: f32) -> (f32, f32) {
+    normalize(throttle + turn, throttle - turn)
+}
+
+/// Mixes a throttle and curvature into the left and right wheel speeds for curvature drive. The
+/// turn rate scales with the throttle, so the robot follows the same arc at any speed.
+pub fn curvature_speeds(throttle: f32, curvature: f32) -> (f32, f32) {
+    let turn = throttle.abs() * curvature;
+    normalize(throttle + turn, throttle - turn)
+}
+
+/// Scales both wheel speeds down together so that neither exceeds full speed.
+fn normalize(left: f32, right: f32) -> (f32, f32) {
+    let max = left.abs().max(right.abs());
+    if max > 1.0 {
+        (left / max, right / max)
+    } else {
+        (left, right)
+    }
+}
@@ -0,0 +1,148 @@
+use std::f32::consts::PI;
+
+use crate::robot_config::OdometryConfig;
+
+/// Where the robot is, relative to where its pose was last reset.
+///
+/// `x` points forwards from the reset position and `y` to the left of it, both in meters. The
+/// heading is in radians, counterclockwise from `x`, between -π and π.
+#[derive(Serialize, Clone, Copy, PartialEq, Debug, Default)]
+pub struct Pose {
+    x: f32,
+    y: f32,
+    heading: f32,
+}
+
+impl Pose {
+    pub fn new(x: f32, y: f32, heading: f32) -> Self {
+        Self {
+            x,
+            y,
+            heading,
+        }
+    }
+
+    pub fn get_x(&self) -> f32 {
+        self.x
+    }
+
+    pub fn get_y(&self) -> f32 {
+        self.y
+    }
+
+    pub fn get_heading(&self) -> f32 {
+        self.heading
+    }
+
+    /// Moves a skid steer robot whose left and right wheels travelled the given distances, in
+    /// meters, assuming it moved along its heading halfway through the turn.
+    pub fn drive(&self, left_distance: f32, right_distance: f32, track_width: f32) -> Pose {
+        let distance = (left_distance + right_distance) / 2.0;
+        let turn = (right_distance - left_distance) / track_width;
+
+        let direction = self.heading + turn / 2.0;
+        Pose::new(self.x + distance * direction.cos(),
+                  self.y + distance * direction.sin(),
+                  wrap_angle(self.heading + turn))
+    }
+}
+
+/// Dead reckons the pose of a skid steer robot from the distance each side's wheels have turned.
+pub struct Odometry {
+    meters_per_count: f32,
+    track_width: f32,
+    last_counts: Option<(i64, i64)>,
+}
+
+impl Odometry {
+    pub fn new(config: &OdometryConfig) -> Self {
+        Self {
+            meters_per_count: config.get_meters_per_count(),
+            track_width: config.get_track_width(),
+            last_counts: None,
+        }
+    }
+
+    /// Moves `pose` by the distance the wheels have turned since the last update, given the
+    /// current encoder counts of each side. The first update only records the counts.
+    pub fn update(&mut self, pose: Pose, left: i64, right: i64) -> Pose {
+        let last_counts = self.last_counts.replace((left, right));
+        let (last_left, last_right) = match last_counts {
+            Some(counts) => counts,
+            None => return pose,
+        };
+
+        let left_distance = (left - last_left) as f32 * self.meters_per_count;
+        let right_distance = (right - last_right) as f32 * self.meters_per_count;
+        pose.drive(left_distance, right_distance, self.track_width)
+    }
+}
+
+/// Brings an angle in radians into the range -π to π.
+fn wrap_angle(angle: f32) -> f32 {
+    let wrapped = (angle + PI) % (2.0 * PI);
+    if wrapped <= 0.0 {
+        wrapped + PI
+    } else {
+        wrapped - PI
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Odometry {
+        Odometry::new(&OdometryConfig::default().with_wheel_radius(0.1).with_track_width(0.4).with_counts_per_revolution(1000.0))
+    }
+
+    fn assert_near(expected: f32, actual: f32) {
+        assert!((expected - actual).abs() < 1e-4, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn first_update() {
+        let mut odometry = setup();
+
+        assert_eq!(Pose::default(), odometry.update(Pose::default(), 500, 700));
+    }
+
+    #[test]
+    fn straight() {
+        let mut odometry = setup();
+        odometry.update(Pose::default(), 0, 0);
+
+        let pose = odometry.update(Pose::default(), 1000, 1000);
+        assert_near(0.2 * PI, pose.get_x());
+        assert_near(0.0, pose.get_y());
+        assert_near(0.0, pose.get_heading());
+    }
+
+    #[test]
+    fn turn_in_place() {
+        let mut odometry = setup();
+        odometry.update(Pose::default(), 0, 0);
+
+        let pose = odometry.update(Pose::default(), -500, 500);
+        assert_near(0.0, pose.get_x());
+        assert_near(0.0, pose.get_y());
+        assert_near(PI / 2.0, pose.get_heading());
+    }
+
+    #[test]
+    fn follows_heading() {
+        let mut odometry = setup();
+        odometry.update(Pose::default(), 0, 0);
+
+        let pose = odometry.update(Pose::new(1.0, 0.0, PI / 2.0), 1000, 1000);
+        assert_near(1.0, pose.get_x());
+        assert_near(0.2 * PI, pose.get_y());
+    }
+
+    #[test]
+    fn heading_wraps() {
+        assert_near(-PI / 2.0, wrap_angle(3.0 * PI / 2.0));
+        assert_near(PI / 2.0, wrap_angle(-3.0 * PI / 2.0));
+        assert_near(PI, wrap_angle(PI));
+    }
+}
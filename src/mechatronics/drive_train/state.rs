@@ -2,13 +2,21 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use atomic::Atomic;
+
+use crate::mechatronics::drive_train::odometry::Pose;
 use crate::motor_controllers::GlobalMotorState;
 use crate::motor_controllers::MotorStateInstance;
+use crate::pinouts::encoder::EncoderStateInstance;
+use crate::pinouts::encoder::GlobalEncoderState;
 
 pub struct GlobalDriveTrainState {
     enabled: AtomicBool,
     left: Arc<GlobalMotorState>,
     right: Arc<GlobalMotorState>,
+    left_encoder: Arc<GlobalEncoderState>,
+    right_encoder: Arc<GlobalEncoderState>,
+    pose: Atomic<Pose>,
 }
 
 impl GlobalDriveTrainState {
@@ -17,6 +25,9 @@ impl GlobalDriveTrainState {
             enabled: AtomicBool::new(false),
             left: Arc::new(GlobalMotorState::new()),
             right: Arc::new(GlobalMotorState::new()),
+            left_encoder: Arc::new(GlobalEncoderState::new()),
+            right_encoder: Arc::new(GlobalEncoderState::new()),
+            pose: Atomic::new(Pose::default()),
         }
     }
 
@@ -36,12 +47,34 @@ impl GlobalDriveTrainState {
         self.right.clone()
     }
 
+    /// The left side's encoder, which only counts while the drive has encoders.
+    pub fn get_left_encoder(&self) -> Arc<GlobalEncoderState> {
+        self.left_encoder.clone()
+    }
+
+    /// The right side's encoder, which only counts while the drive has encoders.
+    pub fn get_right_encoder(&self) -> Arc<GlobalEncoderState> {
+        self.right_encoder.clone()
+    }
+
+    /// The robot's estimated pose, which is only updated while the drive has encoders.
+    pub fn get_pose(&self) -> Pose {
+        self.pose.load(Ordering::Relaxed)
+    }
+
+    pub fn set_pose(&self, pose: Pose) {
+        self.pose.store(pose, Ordering::Relaxed)
+    }
+
     pub fn get_current_state(&self) -> DriveTrainStateInstance {
-        DriveTrainStateInstance::new(
-            self.enabled.load(Ordering::Relaxed),
-            self.left.get_current_state(),
-            self.right.get_current_state(),
-        )
+        DriveTrainStateInstance {
+            enabled: self.enabled.load(Ordering::Relaxed),
+            left: self.left.get_current_state(),
+            right: self.right.get_current_state(),
+            left_encoder: self.left_encoder.get_current_state(),
+            right_encoder: self.right_encoder.get_current_state(),
+            pose: self.get_pose(),
+        }
     }
 }
 
@@ -50,17 +83,12 @@ pub struct DriveTrainStateInstance {
     enabled: bool,
     left: MotorStateInstance,
     right: MotorStateInstance,
+    left_encoder: EncoderStateInstance,
+    right_encoder: EncoderStateInstance,
+    pose: Pose,
 }
 
 impl DriveTrainStateInstance {
-    fn new(enabled: bool, left: MotorStateInstance, right: MotorStateInstance) -> Self {
-        Self {
-            enabled,
-            left,
-            right,
-        }
-    }
-
     pub fn get_enabled(&self) -> bool {
         self.enabled
     }
@@ -72,4 +100,16 @@ impl DriveTrainStateInstance {
     pub fn get_right(&self) -> &MotorStateInstance {
         &self.right
     }
+
+    pub fn get_left_encoder(&self) -> &EncoderStateInstance {
+        &self.left_encoder
+    }
+
+    pub fn get_right_encoder(&self) -> &EncoderStateInstance {
+        &self.right_encoder
+    }
+
+    pub fn get_pose(&self) -> Pose {
+        self.pose
+    }
 }
\ No newline at end of file
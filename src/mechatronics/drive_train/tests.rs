@@ -1,6 +1,8 @@
+use std::f32::consts::PI;
 use std::sync::Arc;
 
 use crate::motor_controllers::test_motor::TestMotor;
+use crate::robot_config::DriveConfig;
 
 use super::*;
 
@@ -9,7 +11,7 @@ fn setup() -> (Arc<GlobalLifeState>, Arc<GlobalDriveTrainState>, DriveTrain) {
     let life = Arc::new(GlobalLifeState::new());
     let left = Box::new(TestMotor::new(state.get_left()));
     let right = Box::new(TestMotor::new(state.get_right()));
-    let drive_train = DriveTrain::new(state.clone(), left, right, life.clone());
+    let drive_train = DriveTrain::new(state.clone(), left, right, life.clone(), DriveConfig::default());
     (life, state, drive_train)
 }
 
@@ -115,4 +117,76 @@ fn enable() {
 
     assert_eq!(1.0, state.get_left().get_speed());
     assert_eq!(-1.0, state.get_right().get_speed());
-}
\ No newline at end of file
+}
+#[test]
+fn scale() {
+    let (_, state, mut drive_train) = setup();
+
+    drive_train.enable();
+    drive_train.drive(1.0, -0.5);
+    drive_train.set_scale(0.5);
+
+    assert_eq!(0.5, state.get_left().get_speed());
+    assert_eq!(-0.25, state.get_right().get_speed());
+
+    drive_train.drive(-1.0, 1.0);
+    assert_eq!(-0.5, state.get_left().get_speed());
+
+    drive_train.set_scale(1.0);
+    assert_eq!(-1.0, state.get_left().get_speed());
+    assert_eq!(1.0, state.get_right().get_speed());
+}
+
+#[test]
+fn arcade_mixing() {
+    assert_eq!((0.5, 0.5), arcade_speeds(0.5, 0.0));
+    assert_eq!((0.75, 0.25), arcade_speeds(0.5, 0.25));
+    assert_eq!((-0.5, 0.5), arcade_speeds(0.0, -0.5));
+    assert_eq!((1.0, 0.0), arcade_speeds(1.0, 1.0));
+    assert_eq!((-1.0, 0.0), arcade_speeds(-0.5, -0.5));
+}
+
+#[test]
+fn curvature_mixing() {
+    assert_eq!((0.0, 0.0), curvature_speeds(0.0, 1.0));
+    assert_eq!((0.75, 0.25), curvature_speeds(0.5, 0.5));
+    assert_eq!((-0.25, -0.75), curvature_speeds(-0.5, 0.5));
+    assert_eq!((1.0, 0.0), curvature_speeds(1.0, 1.0));
+}
+
+#[test]
+fn arcade() {
+    let (_, state, mut drive_train) = setup();
+
+    drive_train.enable();
+    drive_train.arcade(1.0, -1.0);
+
+    assert_eq!(0.0, state.get_left().get_speed());
+    assert_eq!(1.0, state.get_right().get_speed());
+}
+
+#[test]
+fn odometry() {
+    let (_, state, mut drive_train) = setup();
+
+    drive_train.run_cycle();
+    assert_eq!(Pose::default(), state.get_pose());
+
+    state.get_left().set_position(Some(0));
+    state.get_right().set_position(Some(0));
+    drive_train.run_cycle();
+    state.get_left().set_position(Some(1000));
+    state.get_right().set_position(Some(1000));
+    drive_train.run_cycle();
+
+    // One turn of a wheel with the default radius of 0.1 meters.
+    let pose = state.get_current_state().get_pose();
+    assert!((0.2 * PI - pose.get_x()).abs() < 1e-4);
+    assert_eq!(0.0, pose.get_heading());
+
+    drive_train.reset_pose();
+    assert_eq!(Pose::default(), state.get_pose());
+
+    drive_train.run_cycle();
+    assert_eq!(Pose::default(), state.get_pose());
+}
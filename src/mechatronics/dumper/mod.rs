@@ -1,10 +1,15 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use atomic::Ordering;
 
+use crate::mechatronics::dumper::state::DumperPhase;
 use crate::mechatronics::dumper::state::GlobalDumperState;
+use crate::mechatronics::plausibility::LimitPlausibility;
+use crate::mechatronics::plausibility::MechanismFault;
+use crate::mechatronics::plausibility::Travel;
 use crate::motor_controllers::MotorController;
-use crate::robot_map::*;
+use crate::robot_config::DumperConfig;
 use crate::status::life::GlobalLifeState;
 
 pub mod state;
@@ -17,18 +22,27 @@ pub struct Dumper {
     state: Arc<GlobalDumperState>,
     life: Arc<GlobalLifeState>,
     enabled: bool,
-    action: DumperAction,
+    phase: DumperPhase,
+    dwell_started: Instant,
+    scale: f32,
+    limit_check: LimitPlausibility,
+    config: DumperConfig,
 }
 
 impl Dumper {
-    pub fn new(life: Arc<GlobalLifeState>, motors: Box<MotorController>, state: Arc<GlobalDumperState>) -> Self {
+    pub fn new(life: Arc<GlobalLifeState>, motors: Box<MotorController>, state: Arc<GlobalDumperState>,
+               config: DumperConfig) -> Self {
         let enabled = state.get_enabled();
         Self {
             motors,
             state,
             life,
             enabled,
-            action: DumperAction::Stopped,
+            phase: DumperPhase::Stopped,
+            dwell_started: Instant::now(),
+            scale: 1.0,
+            limit_check: LimitPlausibility::new(config.get_limit_check(), config.get_timeout()),
+            config,
         }
     }
 
@@ -44,45 +58,144 @@ impl Dumper {
     }
 
     pub fn dump(&mut self) {
-        if self.enabled && !self.state.get_upper_limit().load(Ordering::Relaxed) && self.life.is_alive() {
-            self.motors.set_speed(DUMPING_RATE);
-            self.action = DumperAction::Dumping;
+        if self.can_move() && !self.state.get_upper_limit().load(Ordering::Relaxed) {
+            self.motors.set_speed(self.config.get_dumping_rate() * self.scale);
+            self.set_phase(DumperPhase::Dumping);
         }
     }
 
     pub fn reset(&mut self) {
-        if self.enabled && !self.state.get_lower_limit().load(Ordering::Relaxed) && self.life.is_alive() {
-            self.motors.set_speed(DUMPER_RESET_RATE);
-            self.action = DumperAction::Resetting;
+        if self.can_move() && !self.state.get_lower_limit().load(Ordering::Relaxed) {
+            self.motors.set_speed(self.config.get_reset_rate() * self.scale);
+            self.set_phase(DumperPhase::Resetting);
+        }
+    }
+
+    /// Raises the dumper to its upper limit, holds it there for the configured dwell time, then
+    /// resets it to its lower limit and stops. Calling `stop` cancels the cycle at any point.
+    pub fn cycle(&mut self) {
+        if self.can_move() {
+            if self.state.get_upper_limit().load(Ordering::Relaxed) {
+                self.start_dwell(Instant::now());
+            } else {
+                self.motors.set_speed(self.config.get_dumping_rate() * self.scale);
+                self.set_phase(DumperPhase::CycleRaising);
+            }
         }
     }
 
     pub fn stop(&mut self) {
         self.motors.stop();
-        self.action = DumperAction::Stopped
+        self.set_phase(DumperPhase::Stopped);
+    }
+
+    /// The fault which stopped the dumper, if it refuses to move until it is cleared.
+    pub fn get_fault(&self) -> Option<MechanismFault> {
+        self.state.get_fault()
+    }
+
+    /// Allows the dumper to move again after it was stopped by a fault.
+    pub fn clear_fault(&mut self) {
+        if let Some(fault) = self.state.get_fault() {
+            info!("Clearing dumper fault {:?}", fault);
+            self.state.set_fault(None);
+        }
+    }
+
+    /// Whether a dump cycle is raising, holding or lowering the dumper.
+    pub fn is_cycling(&self) -> bool {
+        match self.phase {
+            DumperPhase::CycleRaising | DumperPhase::CycleDwelling | DumperPhase::CycleLowering => true,
+            DumperPhase::Dumping | DumperPhase::Resetting | DumperPhase::Stopped => false,
+        }
+    }
+
+    /// Limits the dumper to `scale` of its rates, including while it is already moving.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+        match self.phase {
+            DumperPhase::Dumping | DumperPhase::CycleRaising => {
+                self.motors.set_speed(self.config.get_dumping_rate() * self.scale);
+            }
+            DumperPhase::Resetting | DumperPhase::CycleLowering => {
+                self.motors.set_speed(self.config.get_reset_rate() * self.scale);
+            }
+            DumperPhase::CycleDwelling | DumperPhase::Stopped => {}
+        }
     }
 
     pub fn run_cycle(&mut self) {
+        self.run_cycle_at(Instant::now());
+    }
+
+    fn run_cycle_at(&mut self, now: Instant) {
+        self.motors.run_cycle();
+        self.check_limits(now);
+
         if self.enabled {
-            match self.action {
-                DumperAction::Dumping => {
+            match self.phase {
+                DumperPhase::Dumping => {
                     if self.state.get_upper_limit().load(Ordering::Relaxed) {
                         self.stop();
                     }
                 },
-                DumperAction::Resetting => {
+                DumperPhase::Resetting | DumperPhase::CycleLowering => {
                     if self.state.get_lower_limit().load(Ordering::Relaxed) {
                         self.stop();
                     }
                 },
-                DumperAction::Stopped => {},
+                DumperPhase::CycleRaising => {
+                    if self.state.get_upper_limit().load(Ordering::Relaxed) {
+                        self.start_dwell(now);
+                    }
+                },
+                DumperPhase::CycleDwelling => {
+                    if now.duration_since(self.dwell_started) >= self.config.get_cycle_dwell() {
+                        if self.state.get_lower_limit().load(Ordering::Relaxed) {
+                            self.stop();
+                        } else {
+                            self.motors.set_speed(self.config.get_reset_rate() * self.scale);
+                            self.set_phase(DumperPhase::CycleLowering);
+                        }
+                    }
+                },
+                DumperPhase::Stopped => {},
             }
         }
     }
-}
 
-enum DumperAction {
-    Dumping,
-    Resetting,
-    Stopped,
+    /// Stops the dumper and holds it faulted if its limits contradict the way it is moving, or it
+    /// takes too long to reach the limit it is heading for.
+    fn check_limits(&mut self, now: Instant) {
+        let travel = match self.phase {
+            DumperPhase::Dumping | DumperPhase::CycleRaising => Travel::Up,
+            DumperPhase::Resetting | DumperPhase::CycleLowering => Travel::Down,
+            DumperPhase::CycleDwelling | DumperPhase::Stopped => Travel::Still,
+        };
+        let upper = self.state.get_upper_limit().load(Ordering::Relaxed);
+        let lower = self.state.get_lower_limit().load(Ordering::Relaxed);
+
+        if let Some(fault) = self.limit_check.check_at(now, travel, self.scale, upper, lower) {
+            if self.state.get_fault().is_none() {
+                error!("Dumper faulted ({:?}), stopping it!", fault);
+                self.state.set_fault(Some(fault));
+            }
+            self.stop();
+        }
+    }
+
+    fn can_move(&self) -> bool {
+        self.enabled && self.life.is_alive() && self.state.get_fault().is_none()
+    }
+
+    fn start_dwell(&mut self, now: Instant) {
+        self.motors.stop();
+        self.dwell_started = now;
+        self.set_phase(DumperPhase::CycleDwelling);
+    }
+
+    fn set_phase(&mut self, phase: DumperPhase) {
+        self.phase = phase;
+        self.state.set_phase(phase);
+    }
 }
\ No newline at end of file
@@ -2,32 +2,55 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use atomic::Atomic;
+
+use crate::mechatronics::plausibility::MechanismFault;
 use crate::motor_controllers::GlobalMotorState;
 use crate::motor_controllers::MotorStateInstance;
 
+/// What the dumper is currently doing.
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum DumperPhase {
+    Stopped,
+    Dumping,
+    Resetting,
+    /// A dump cycle is raising the dumper to its upper limit.
+    CycleRaising,
+    /// A dump cycle is holding the dumper at its upper limit.
+    CycleDwelling,
+    /// A dump cycle is lowering the dumper back to its lower limit.
+    CycleLowering,
+}
+
 pub struct GlobalDumperState {
     enabled: AtomicBool,
+    phase: Atomic<DumperPhase>,
     motor: Arc<GlobalMotorState>,
     upper_limit: Arc<AtomicBool>,
     lower_limit: Arc<AtomicBool>,
+    fault: Atomic<Option<MechanismFault>>,
 }
 
 impl GlobalDumperState {
     pub fn new() -> Self {
         Self {
             enabled: AtomicBool::new(false),
+            phase: Atomic::new(DumperPhase::Stopped),
             motor: Arc::new(GlobalMotorState::new()),
             upper_limit: Arc::new(AtomicBool::new(false)),
             lower_limit: Arc::new(AtomicBool::new(false)),
+            fault: Atomic::new(None),
         }
     }
 
     pub fn get_current_state(&self) -> DumperStateInstance {
         DumperStateInstance::new(
             self.enabled.load(Ordering::Relaxed),
+            self.phase.load(Ordering::Relaxed),
             self.motor.get_current_state(),
             self.upper_limit.load(Ordering::Relaxed),
             self.lower_limit.load(Ordering::Relaxed),
+            self.get_fault(),
         )
     }
 
@@ -50,23 +73,45 @@ impl GlobalDumperState {
     pub fn get_enabled(&self) -> bool {
         self.enabled.load(Ordering::Relaxed)
     }
+
+    pub fn set_phase(&self, phase: DumperPhase) {
+        self.phase.store(phase, Ordering::Relaxed)
+    }
+
+    pub fn get_phase(&self) -> DumperPhase {
+        self.phase.load(Ordering::Relaxed)
+    }
+
+    /// The fault which stopped the dumper, if it refuses to move until the fault is cleared.
+    pub fn get_fault(&self) -> Option<MechanismFault> {
+        self.fault.load(Ordering::Relaxed)
+    }
+
+    pub fn set_fault(&self, fault: Option<MechanismFault>) {
+        self.fault.store(fault, Ordering::Relaxed)
+    }
 }
 
 #[derive(Serialize)]
 pub struct DumperStateInstance {
     enabled: bool,
+    phase: DumperPhase,
     motor: MotorStateInstance,
     upper_limit: bool,
     lower_limit: bool,
+    fault: Option<MechanismFault>,
 }
 
 impl DumperStateInstance {
-    fn new(enabled: bool, motor: MotorStateInstance, upper_limit: bool, lower_limit: bool) -> Self {
+    fn new(enabled: bool, phase: DumperPhase, motor: MotorStateInstance, upper_limit: bool, lower_limit: bool,
+           fault: Option<MechanismFault>) -> Self {
         Self {
             enabled,
+            phase,
             motor,
             upper_limit,
             lower_limit,
+            fault,
         }
     }
 
@@ -74,7 +119,23 @@ impl DumperStateInstance {
         self.enabled
     }
 
+    pub fn get_phase(&self) -> DumperPhase {
+        self.phase
+    }
+
     pub fn get_motor(&self) -> &MotorStateInstance {
         &self.motor
     }
+
+    pub fn get_upper_limit(&self) -> bool {
+        self.upper_limit
+    }
+
+    pub fn get_lower_limit(&self) -> bool {
+        self.lower_limit
+    }
+
+    pub fn get_fault(&self) -> Option<MechanismFault> {
+        self.fault
+    }
 }
\ No newline at end of file
@@ -1,6 +1,10 @@
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::mechatronics::plausibility::MechanismFault;
 use crate::motor_controllers::test_motor::TestMotor;
+use crate::robot_config::LimitCheckConfig;
+use crate::robot_map::*;
 
 use super::*;
 
@@ -9,7 +13,32 @@ fn setup() -> (Arc<GlobalLifeState>, Arc<GlobalDumperState>, Dumper) {
     let state = Arc::new(GlobalDumperState::new());
     let motor = Box::new(TestMotor::new(state.get_motor()));
 
-    let dumper = Dumper::new(life.clone(), motor, state.clone());
+    let dumper = Dumper::new(life.clone(), motor, state.clone(), DumperConfig::default());
+
+    (life, state, dumper)
+}
+
+fn setup_cycle(dwell_millis: u64) -> (Arc<GlobalLifeState>, Arc<GlobalDumperState>, Dumper) {
+    let life = Arc::new(GlobalLifeState::new());
+    let state = Arc::new(GlobalDumperState::new());
+    let motor = Box::new(TestMotor::new(state.get_motor()));
+    let config = DumperConfig::default().with_cycle_dwell_millis(dwell_millis);
+
+    let mut dumper = Dumper::new(life.clone(), motor, state.clone(), config);
+    dumper.enable();
+
+    (life, state, dumper)
+}
+
+fn setup_limit_check() -> (Arc<GlobalLifeState>, Arc<GlobalDumperState>, Dumper) {
+    let life = Arc::new(GlobalLifeState::new());
+    let state = Arc::new(GlobalDumperState::new());
+    let motor = Box::new(TestMotor::new(state.get_motor()));
+    let limit_check = LimitCheckConfig::default().with_clear_millis(20).with_min_travel_millis(1000);
+    let config = DumperConfig::default().with_limit_check(limit_check).with_timeout_millis(100);
+
+    let mut dumper = Dumper::new(life.clone(), motor, state.clone(), config);
+    dumper.enable();
 
     (life, state, dumper)
 }
@@ -213,4 +242,194 @@ fn test_lower_limit_maintain_dump() {
     state.get_lower_limit().store(true, Ordering::SeqCst);
 
     assert_eq!(DUMPING_RATE, state.get_motor().get_speed());
-}
\ No newline at end of file
+}
+
+#[test]
+fn cycle() {
+    let (_, state, mut dumper) = setup_cycle(20);
+    let start = Instant::now();
+
+    dumper.cycle();
+    dumper.run_cycle_at(start);
+    assert_eq!(DUMPING_RATE, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::CycleRaising, state.get_current_state().get_phase());
+
+    state.get_upper_limit().store(true, Ordering::SeqCst);
+    dumper.run_cycle_at(start + Duration::from_millis(10));
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::CycleDwelling, state.get_phase());
+
+    dumper.run_cycle_at(start + Duration::from_millis(20));
+    assert_eq!(DumperPhase::CycleDwelling, state.get_phase());
+
+    state.get_upper_limit().store(false, Ordering::SeqCst);
+    dumper.run_cycle_at(start + Duration::from_millis(30));
+    assert_eq!(DUMPER_RESET_RATE, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::CycleLowering, state.get_phase());
+
+    state.get_lower_limit().store(true, Ordering::SeqCst);
+    dumper.run_cycle_at(start + Duration::from_millis(40));
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::Stopped, state.get_phase());
+}
+
+#[test]
+fn cycle_dwell() {
+    let (_, state, mut dumper) = setup_cycle(1000);
+
+    state.get_upper_limit().store(true, Ordering::SeqCst);
+    dumper.cycle();
+    assert_eq!(DumperPhase::CycleDwelling, state.get_phase());
+
+    dumper.run_cycle();
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::CycleDwelling, state.get_phase());
+}
+
+#[test]
+fn cycle_without_dwell() {
+    let (_, state, mut dumper) = setup_cycle(0);
+
+    state.get_upper_limit().store(true, Ordering::SeqCst);
+    dumper.cycle();
+    dumper.run_cycle();
+
+    assert_eq!(DUMPER_RESET_RATE, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::CycleLowering, state.get_phase());
+}
+
+#[test]
+fn cycle_stop() {
+    let (_, state, mut dumper) = setup_cycle(0);
+
+    dumper.cycle();
+    dumper.stop();
+    state.get_upper_limit().store(true, Ordering::SeqCst);
+    dumper.run_cycle();
+
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::Stopped, state.get_phase());
+}
+
+#[test]
+fn cycle_kill_stasis() {
+    let (life, state, mut dumper) = setup_cycle(0);
+
+    life.kill();
+    dumper.cycle();
+
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::Stopped, state.get_phase());
+}
+
+#[test]
+fn cycle_disable() {
+    let (_, state, mut dumper) = setup_cycle(0);
+
+    dumper.cycle();
+    dumper.disable();
+
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(DumperPhase::Stopped, state.get_phase());
+}
+
+#[test]
+fn scale() {
+    let (_, state, mut dumper) = setup();
+
+    dumper.enable();
+    dumper.dump();
+    dumper.set_scale(0.5);
+    assert_eq!(DUMPING_RATE * 0.5, state.get_motor().get_speed());
+
+    dumper.reset();
+    assert_eq!(DUMPER_RESET_RATE * 0.5, state.get_motor().get_speed());
+
+    dumper.stop();
+    dumper.set_scale(1.0);
+    assert_eq!(0.0, state.get_motor().get_speed());
+}
+
+#[test]
+fn both_limits_fault() {
+    let (_, state, mut dumper) = setup();
+    dumper.enable();
+    dumper.dump();
+
+    state.get_upper_limit().store(true, Ordering::SeqCst);
+    state.get_lower_limit().store(true, Ordering::SeqCst);
+    dumper.run_cycle();
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(Some(MechanismFault::BothLimits), state.get_current_state().get_fault());
+
+    state.get_upper_limit().store(false, Ordering::SeqCst);
+    dumper.run_cycle();
+    dumper.dump();
+    assert_eq!(0.0, state.get_motor().get_speed());
+
+    dumper.clear_fault();
+    dumper.dump();
+    assert_eq!(None, state.get_fault());
+    assert_eq!(DUMPING_RATE, state.get_motor().get_speed());
+}
+
+#[test]
+fn stuck_limit_fault() {
+    let (_, state, mut dumper) = setup_limit_check();
+    state.get_lower_limit().store(true, Ordering::SeqCst);
+    let start = Instant::now();
+
+    dumper.dump();
+    dumper.run_cycle_at(start);
+    assert_eq!(DUMPING_RATE, state.get_motor().get_speed());
+
+    dumper.run_cycle_at(start + Duration::from_millis(30));
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(Some(MechanismFault::LimitStuck), state.get_fault());
+}
+
+#[test]
+fn limit_too_soon_fault() {
+    let (_, state, mut dumper) = setup_limit_check();
+    state.get_lower_limit().store(true, Ordering::SeqCst);
+
+    dumper.dump();
+    dumper.run_cycle();
+    state.get_lower_limit().store(false, Ordering::SeqCst);
+    dumper.run_cycle();
+    state.get_upper_limit().store(true, Ordering::SeqCst);
+    dumper.run_cycle();
+
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(Some(MechanismFault::LimitTooSoon), state.get_fault());
+}
+
+#[test]
+fn timeout_fault() {
+    let (_, state, mut dumper) = setup_limit_check();
+    let start = Instant::now();
+
+    dumper.dump();
+    dumper.run_cycle_at(start);
+    dumper.run_cycle_at(start + Duration::from_millis(30));
+    assert_eq!(DUMPING_RATE, state.get_motor().get_speed());
+
+    dumper.run_cycle_at(start + Duration::from_millis(110));
+    assert_eq!(0.0, state.get_motor().get_speed());
+    assert_eq!(Some(MechanismFault::Timeout), state.get_current_state().get_fault());
+}
+
+#[test]
+fn timeout_allows_for_scale() {
+    let (_, state, mut dumper) = setup_limit_check();
+
+    let start = Instant::now();
+
+    dumper.dump();
+    dumper.set_scale(0.25);
+    dumper.run_cycle_at(start);
+    dumper.run_cycle_at(start + Duration::from_millis(120));
+
+    assert_eq!(DUMPING_RATE * 0.25, state.get_motor().get_speed());
+    assert_eq!(None, state.get_fault());
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use crate::robot_config::ShapingConfig;
+
+/// Shapes the driver's inputs to the drive train, and limits how fast each mode's mechanisms move.
+///
+/// The deadband and expo are applied to each drive input as it arrives, while the maximum speed is
+/// applied to the mechanisms as a limit on their output, so that it holds however the inputs are
+/// mixed. The other mechanisms are driven by commands rather than stick positions, so they only
+/// have a maximum speed.
+pub struct InputShaper {
+    config: ShapingConfig,
+    precision: Arc<AtomicBool>,
+}
+
+impl InputShaper {
+    pub fn new(config: ShapingConfig, precision: Arc<AtomicBool>) -> Self {
+        Self {
+            config,
+            precision,
+        }
+    }
+
+    /// Applies the driving profile's deadband and expo to a drive input between -1 and 1.
+    pub fn shape(&self, input: f32) -> f32 {
+        let profile = self.config.get_driving();
+        let deadband = profile.get_deadband();
+        let magnitude = input.abs();
+        if magnitude <= deadband {
+            return 0.0;
+        }
+
+        // Rescale what is left outside the deadband, so the output still starts from zero.
+        let scaled = (magnitude - deadband) / (1.0 - deadband);
+        let expo = profile.get_expo();
+        input.signum() * (expo * scaled.powi(3) + (1.0 - expo) * scaled)
+    }
+
+    /// The fraction of full speed the drive train may reach.
+    pub fn get_driving_limit(&self) -> f32 {
+        self.scale(self.config.get_driving().get_max_speed())
+    }
+
+    /// The fraction of full speed the digger and actuators may reach.
+    pub fn get_digging_limit(&self) -> f32 {
+        self.scale(self.config.get_digging().get_max_speed())
+    }
+
+    /// The fraction of full speed the dumper may reach.
+    pub fn get_dumping_limit(&self) -> f32 {
+        self.scale(self.config.get_dumping().get_max_speed())
+    }
+
+    pub fn get_precision(&self) -> bool {
+        self.precision.load(Ordering::SeqCst)
+    }
+
+    pub fn set_precision(&self, precision: bool) {
+        self.precision.store(precision, Ordering::SeqCst);
+    }
+
+    fn scale(&self, max_speed: f32) -> f32 {
+        if self.get_precision() {
+            max_speed * self.config.get_precision_scale()
+        } else {
+            max_speed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::robot_config::ShapingProfile;
+    use crate::robot_config::SpeedLimit;
+
+    use super::*;
+
+    fn setup(driving: ShapingProfile) -> InputShaper {
+        let config = ShapingConfig::default().with_driving(driving);
+        InputShaper::new(config, Arc::new(AtomicBool::new(false)))
+    }
+
+    #[test]
+    fn unshaped() {
+        let shaper = setup(ShapingProfile::default());
+
+        assert_eq!(0.5, shaper.shape(0.5));
+        assert_eq!(-1.0, shaper.shape(-1.0));
+        assert_eq!(1.0, shaper.get_digging_limit());
+    }
+
+    #[test]
+    fn deadband() {
+        let shaper = setup(ShapingProfile::default().with_deadband(0.5));
+
+        assert_eq!(0.0, shaper.shape(0.25));
+        assert_eq!(0.0, shaper.shape(-0.5));
+        assert_eq!(-0.5, shaper.shape(-0.75));
+        assert_eq!(1.0, shaper.shape(1.0));
+    }
+
+    #[test]
+    fn expo() {
+        let shaper = setup(ShapingProfile::default().with_expo(1.0));
+
+        assert_eq!(0.125, shaper.shape(0.5));
+        assert_eq!(-1.0, shaper.shape(-1.0));
+    }
+
+    #[test]
+    fn precision() {
+        let config = ShapingConfig::default().with_digging(SpeedLimit::default().with_max_speed(0.5)).with_precision_scale(0.5);
+        let shaper = InputShaper::new(config, Arc::new(AtomicBool::new(false)));
+
+        assert_eq!(0.5, shaper.get_digging_limit());
+        assert_eq!(1.0, shaper.get_driving_limit());
+
+        shaper.set_precision(true);
+        assert_eq!(0.25, shaper.get_digging_limit());
+        assert_eq!(0.5, shaper.get_driving_limit());
+    }
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::robot_config::CurrentConfig;
+use crate::status::current::CurrentUsageLevel;
+use crate::status::current::GlobalCurrentState;
+
+/// A group of motors whose output can be throttled to reduce the current drawn by the robot.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Load {
+    Digger,
+    Actuators,
+    Dumper,
+    Drive,
+}
+
+/// Throttles the robot's loads one at a time, in priority order, while the current is in brownout
+/// or critical, and restores them in reverse order once it has returned to normal.
+///
+/// The first load is shed as soon as a brownout begins. Each further change waits for the shed
+/// interval, so the current has time to settle before another load is throttled or restored.
+pub struct LoadShedder {
+    current: Arc<GlobalCurrentState>,
+    order: Vec<Load>,
+    scale: f32,
+    interval: Duration,
+    kill_on_critical: bool,
+    shed: usize,
+    last_change: Option<Instant>,
+}
+
+impl LoadShedder {
+    pub fn new(current: Arc<GlobalCurrentState>, config: &CurrentConfig) -> Self {
+        Self {
+            current,
+            order: config.get_shed_order().to_vec(),
+            scale: config.get_shed_scale(),
+            interval: config.get_shed_interval(),
+            kill_on_critical: config.get_kill_on_critical(),
+            shed: 0,
+            last_change: None,
+        }
+    }
+
+    pub fn get_level(&self) -> CurrentUsageLevel {
+        self.current.get_level()
+    }
+
+    /// Whether critical current should kill the robot instead of being shed like a brownout.
+    pub fn get_kill_on_critical(&self) -> bool {
+        self.kill_on_critical
+    }
+
+    /// Sheds or restores a load if one is due at `now`, returning `true` if any scale changed.
+    pub fn update(&mut self, now: Instant) -> bool {
+        let settled = self.last_change.map_or(true, |last| now.duration_since(last) >= self.interval);
+        let changed = match self.current.get_level() {
+            CurrentUsageLevel::Brownout | CurrentUsageLevel::Critical if self.shed < self.order.len() && (self.shed == 0 || settled) => {
+                warn!("Current is in brownout, throttling {:?}", self.order[self.shed]);
+                self.shed += 1;
+                true
+            }
+            CurrentUsageLevel::Normal if self.shed > 0 && settled => {
+                self.shed -= 1;
+                info!("Current is normal, restoring {:?}", self.order[self.shed]);
+                true
+            }
+            _ => false,
+        };
+
+        if changed {
+            self.last_change = Some(now);
+            self.current.set_shed(self.shed);
+        }
+        changed
+    }
+
+    /// Returns the fraction of its commanded output which `load` is currently allowed.
+    pub fn get_scale(&self, load: Load) -> f32 {
+        if self.order[..self.shed].contains(&load) {
+            self.scale
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(config: CurrentConfig) -> (Arc<GlobalCurrentState>, LoadShedder) {
+        let current = Arc::new(GlobalCurrentState::new());
+        let shedder = LoadShedder::new(current.clone(), &config);
+
+        (current, shedder)
+    }
+
+    #[test]
+    fn normal() {
+        let (_, mut shedder) = setup(CurrentConfig::default());
+
+        assert_eq!(false, shedder.update(Instant::now()));
+        assert_eq!(1.0, shedder.get_scale(Load::Digger));
+        assert_eq!(1.0, shedder.get_scale(Load::Drive));
+    }
+
+    #[test]
+    fn sheds_in_order() {
+        let (current, mut shedder) = setup(CurrentConfig::default()
+            .with_shed_order(&[Load::Digger, Load::Drive])
+            .with_shed_scale(0.25)
+            .with_shed_interval_millis(100));
+        let start = Instant::now();
+        current.update_current(current.get_brownout());
+
+        assert_eq!(true, shedder.update(start));
+        assert_eq!(0.25, shedder.get_scale(Load::Digger));
+        assert_eq!(1.0, shedder.get_scale(Load::Drive));
+
+        assert_eq!(false, shedder.update(start + Duration::from_millis(50)));
+        assert_eq!(true, shedder.update(start + Duration::from_millis(100)));
+        assert_eq!(0.25, shedder.get_scale(Load::Drive));
+        assert_eq!(1.0, shedder.get_scale(Load::Dumper));
+        assert_eq!(2, current.get_shed());
+
+        assert_eq!(false, shedder.update(start + Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn restores_in_reverse() {
+        let (current, mut shedder) = setup(CurrentConfig::default().with_shed_interval_millis(100));
+        let start = Instant::now();
+        current.update_current(current.get_brownout());
+        shedder.update(start);
+        shedder.update(start + Duration::from_millis(100));
+
+        current.update_current(0.0);
+        assert_eq!(false, shedder.update(start + Duration::from_millis(150)));
+        assert_eq!(true, shedder.update(start + Duration::from_millis(200)));
+        assert_eq!(1.0, shedder.get_scale(Load::Actuators));
+        assert!(shedder.get_scale(Load::Digger) < 1.0);
+
+        assert_eq!(true, shedder.update(start + Duration::from_millis(300)));
+        assert_eq!(1.0, shedder.get_scale(Load::Digger));
+        assert_eq!(0, current.get_shed());
+    }
+}
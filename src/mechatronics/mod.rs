@@ -17,23 +17,78 @@ pub mod dumper;
 
 pub mod bucket_ladder;
 
+/// Runs sequences of commands on the robot without a driver.
+pub mod routines;
+
+/// Throttles the mechanisms when the robot draws too much current.
+pub mod load_shedding;
+
+/// Applies deadband, expo, and speed limits to the driver's inputs.
+pub mod input_shaping;
+
+/// Checks that limit switches agree with how their mechanism is moving.
+pub mod plausibility;
+
 #[cfg(test)]
 mod tests;
 
+/// Identifies where a command sent to the controller came from.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CommandSource {
+    /// Sent by a client of the REST API.
+    Rest,
+    /// Sent while replaying a command journal.
+    Replay,
+    /// Handed directly to the controller, without going through a messenger.
+    Local,
+    /// Issued by a routine running inside the controller.
+    Routine,
+}
+
+/// A command on its way to the controller, tagged with where it came from.
+pub struct RobotMessage {
+    command: Box<RobotCommand>,
+    source: CommandSource,
+}
+
+impl RobotMessage {
+    pub fn new(command: Box<RobotCommand>, source: CommandSource) -> Self {
+        Self {
+            command,
+            source,
+        }
+    }
+
+    pub fn get_source(&self) -> CommandSource {
+        self.source
+    }
+
+    pub fn into_command(self) -> Box<RobotCommand> {
+        self.command
+    }
+}
+
 pub struct RobotMessenger {
-    channel: SyncSender<Box<RobotCommand>>,
+    channel: SyncSender<RobotMessage>,
+    source: CommandSource,
 }
 
 impl RobotMessenger {
-    pub fn new(channel: SyncSender<Box<RobotCommand>>) -> Self {
+    pub fn new(channel: SyncSender<RobotMessage>) -> Self {
+        Self::with_source(channel, CommandSource::Rest)
+    }
+
+    /// Creates a messenger whose commands are tagged as coming from `source`.
+    pub fn with_source(channel: SyncSender<RobotMessage>, source: CommandSource) -> Self {
         Self {
             channel,
+            source,
         }
     }
 
     #[inline]
     pub fn send_command(&self, command: Box<RobotCommand>) {
-        self.channel.send(command).unwrap();
+        self.channel.send(RobotMessage::new(command, self.source)).unwrap();
         thread::yield_now()
     }
 }
\ No newline at end of file
@@ -0,0 +1,173 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::framework::seconds;
+use crate::robot_config::LimitCheckConfig;
+
+/// Why a mechanism stopped and refuses to move until its fault is cleared.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum MechanismFault {
+    /// The upper and lower limits read asserted at the same time.
+    BothLimits,
+    /// A limit stayed asserted after the mechanism had been moving away from it for longer than it
+    /// takes to clear.
+    LimitStuck,
+    /// A limit was reached sooner after leaving the other one than the mechanism can travel.
+    LimitTooSoon,
+    /// The mechanism ran for longer than it takes to travel between its limits without reaching
+    /// the one it was heading for.
+    Timeout,
+}
+
+/// Which way a mechanism is being driven between its limits.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Travel {
+    Up,
+    Down,
+    Still,
+}
+
+/// Checks that a pair of limit switches behaves the way real switches on a moving mechanism can,
+/// and that the mechanism reaches the limit it is heading for within `timeout`.
+///
+/// The timeout is measured at full speed, so a mechanism running at half speed is allowed twice
+/// as long.
+pub struct LimitPlausibility {
+    clear_time: Duration,
+    min_travel: Duration,
+    timeout: f32,
+    travel: Travel,
+    started: Instant,
+    started_at_limit: bool,
+    left_limit: Option<Instant>,
+    /// How long the mechanism has been travelling, in seconds at full speed.
+    travelled: f32,
+    last_check: Instant,
+}
+
+impl LimitPlausibility {
+    pub fn new(config: &LimitCheckConfig, timeout: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            clear_time: config.get_clear_time(),
+            min_travel: config.get_min_travel(),
+            timeout: seconds(timeout),
+            travel: Travel::Still,
+            started: now,
+            started_at_limit: false,
+            left_limit: None,
+            travelled: 0.0,
+            last_check: now,
+        }
+    }
+
+    /// Checks the limits at `now`, while the mechanism is travelling in `travel` at `pace` of its
+    /// full speed, returning the fault they show, if any.
+    pub fn check_at(&mut self, now: Instant, travel: Travel, pace: f32, upper: bool, lower: bool) -> Option<MechanismFault> {
+        let elapsed = now.duration_since(self.last_check);
+        self.last_check = now;
+
+        if upper && lower {
+            return Some(MechanismFault::BothLimits);
+        }
+
+        let (target, origin) = match travel {
+            Travel::Up => (upper, lower),
+            Travel::Down => (lower, upper),
+            Travel::Still => {
+                self.travel = Travel::Still;
+                return None;
+            }
+        };
+
+        if travel != self.travel {
+            self.travel = travel;
+            self.started = now;
+            self.started_at_limit = origin;
+            self.left_limit = None;
+            self.travelled = 0.0;
+        } else {
+            self.travelled += seconds(elapsed) * pace.abs();
+        }
+
+        if !target && self.travelled >= self.timeout {
+            return Some(MechanismFault::Timeout);
+        }
+
+        if self.started_at_limit && self.left_limit.is_none() {
+            if !origin {
+                self.left_limit = Some(now);
+            } else if now.duration_since(self.started) >= self.clear_time {
+                return Some(MechanismFault::LimitStuck);
+            }
+        }
+
+        match self.left_limit {
+            Some(left) if target && now.duration_since(left) < self.min_travel => Some(MechanismFault::LimitTooSoon),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (LimitPlausibility, Instant) {
+        let config = LimitCheckConfig::default().with_clear_millis(100).with_min_travel_millis(500);
+        let check = LimitPlausibility::new(&config, Duration::from_millis(1000));
+        let start = check.started;
+
+        (check, start)
+    }
+
+    #[test]
+    fn both_limits() {
+        let (mut check, start) = setup();
+        assert_eq!(Some(MechanismFault::BothLimits), check.check_at(start, Travel::Still, 1.0, true, true));
+    }
+
+    #[test]
+    fn limit_stuck() {
+        let (mut check, start) = setup();
+
+        assert_eq!(None, check.check_at(start, Travel::Up, 1.0, false, true));
+        assert_eq!(None, check.check_at(start + Duration::from_millis(50), Travel::Up, 1.0, false, true));
+        assert_eq!(Some(MechanismFault::LimitStuck), check.check_at(start + Duration::from_millis(100), Travel::Up, 1.0, false, true));
+    }
+
+    #[test]
+    fn limit_too_soon() {
+        let (mut check, start) = setup();
+
+        check.check_at(start, Travel::Down, 1.0, true, false);
+        check.check_at(start + Duration::from_millis(50), Travel::Down, 1.0, false, false);
+        assert_eq!(Some(MechanismFault::LimitTooSoon), check.check_at(start + Duration::from_millis(300), Travel::Down, 1.0, false, true));
+    }
+
+    #[test]
+    fn full_stroke() {
+        let (mut check, start) = setup();
+
+        check.check_at(start, Travel::Down, 1.0, true, false);
+        check.check_at(start + Duration::from_millis(50), Travel::Down, 1.0, false, false);
+        assert_eq!(None, check.check_at(start + Duration::from_millis(600), Travel::Down, 1.0, false, true));
+
+        // Starting part way along, there is no way to tell how far the mechanism had to go.
+        check.check_at(start + Duration::from_millis(700), Travel::Still, 1.0, false, false);
+        assert_eq!(None, check.check_at(start + Duration::from_millis(800), Travel::Up, 1.0, false, false));
+        assert_eq!(None, check.check_at(start + Duration::from_millis(900), Travel::Up, 1.0, true, false));
+    }
+
+    #[test]
+    fn timeout() {
+        let (mut check, start) = setup();
+
+        check.check_at(start, Travel::Up, 0.5, false, false);
+        assert_eq!(None, check.check_at(start + Duration::from_millis(1500), Travel::Up, 0.5, false, false));
+        assert_eq!(Some(MechanismFault::Timeout), check.check_at(start + Duration::from_millis(2000), Travel::Up, 0.5, false, false));
+
+        // A mechanism which has reached its limit has not timed out, however long it is driven.
+        assert_eq!(None, check.check_at(start + Duration::from_millis(2500), Travel::Up, 0.5, true, false));
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::mechatronics::commands::CommandRecord;
+use crate::mechatronics::controller::MechState;
+use crate::mechatronics::routines::state::GlobalRoutineState;
+use crate::mechatronics::routines::state::RoutineStatus;
+use crate::robot_map::*;
+use crate::status::robot_state::GlobalRobotState;
+
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+/// Names the routines which the robot can run on its own.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RoutineId {
+    /// Raises the dumper to its upper limit, lets the load settle out and lowers it again.
+    Dump,
+    /// Lowers the intake until both actuators reach their lower limits.
+    LowerIntake,
+    /// Raises the intake until both actuators reach their upper limits.
+    RaiseIntake,
+    /// Lowers the intake, digs for a while and raises the intake again.
+    Dig,
+}
+
+/// A state of the robot which a routine can wait for.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RoutineCondition {
+    DumperRaised,
+    DumperLowered,
+    ActuatorsRaised,
+    ActuatorsLowered,
+}
+
+impl RoutineCondition {
+    pub fn is_met(self, state: &GlobalRobotState) -> bool {
+        let intake = state.get_intake();
+        let dumper = state.get_dumper();
+        match self {
+            RoutineCondition::DumperRaised => dumper.get_upper_limit().load(Ordering::SeqCst),
+            RoutineCondition::DumperLowered => dumper.get_lower_limit().load(Ordering::SeqCst),
+            RoutineCondition::ActuatorsRaised => intake.get_left_actuator().get_upper().load(Ordering::SeqCst)
+                && intake.get_right_actuator().get_upper().load(Ordering::SeqCst),
+            RoutineCondition::ActuatorsLowered => intake.get_left_actuator().get_lower().load(Ordering::SeqCst)
+                && intake.get_right_actuator().get_lower().load(Ordering::SeqCst),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RoutineStep {
+    /// Executes a command, as though it had been sent by the driver.
+    Command(CommandRecord),
+    /// Waits for a fixed amount of time.
+    Wait(Duration),
+    /// Waits until a condition is met.
+    WaitUntil(RoutineCondition),
+}
+
+pub struct Routine {
+    id: RoutineId,
+    steps: Vec<RoutineStep>,
+}
+
+impl Routine {
+    pub fn new(id: RoutineId, steps: Vec<RoutineStep>) -> Self {
+        Self {
+            id,
+            steps,
+        }
+    }
+
+    /// Creates the sequence of steps which make up the routine named `id`.
+    pub fn builtin(id: RoutineId) -> Self {
+        let steps = match id {
+            RoutineId::Dump => vec![
+                RoutineStep::Command(CommandRecord::SwitchMode { mode: MechState::Dumping }),
+                RoutineStep::Command(CommandRecord::Dump),
+                RoutineStep::WaitUntil(RoutineCondition::DumperRaised),
+                RoutineStep::Wait(Duration::from_millis(ROUTINE_DUMP_DWELL_MILLIS)),
+                RoutineStep::Command(CommandRecord::ResetDumper),
+                RoutineStep::WaitUntil(RoutineCondition::DumperLowered),
+                RoutineStep::Command(CommandRecord::StopDumper),
+            ],
+            RoutineId::LowerIntake => vec![
+                RoutineStep::Command(CommandRecord::SwitchMode { mode: MechState::Digging }),
+                RoutineStep::Command(CommandRecord::LowerActuators),
+                RoutineStep::WaitUntil(RoutineCondition::ActuatorsLowered),
+                RoutineStep::Command(CommandRecord::StopActuators),
+            ],
+            RoutineId::RaiseIntake => vec![
+                RoutineStep::Command(CommandRecord::SwitchMode { mode: MechState::Digging }),
+                RoutineStep::Command(CommandRecord::RaiseActuators),
+                RoutineStep::WaitUntil(RoutineCondition::ActuatorsRaised),
+                RoutineStep::Command(CommandRecord::StopActuators),
+            ],
+            RoutineId::Dig => vec![
+                RoutineStep::Command(CommandRecord::SwitchMode { mode: MechState::Digging }),
+                RoutineStep::Command(CommandRecord::LowerActuators),
+                RoutineStep::WaitUntil(RoutineCondition::ActuatorsLowered),
+                RoutineStep::Command(CommandRecord::StopActuators),
+                RoutineStep::Command(CommandRecord::Dig),
+                RoutineStep::Wait(Duration::from_millis(ROUTINE_DIG_MILLIS)),
+                RoutineStep::Command(CommandRecord::StopDigger),
+                RoutineStep::Command(CommandRecord::RaiseActuators),
+                RoutineStep::WaitUntil(RoutineCondition::ActuatorsRaised),
+                RoutineStep::Command(CommandRecord::StopActuators),
+            ],
+        };
+
+        Self::new(id, steps)
+    }
+
+    pub fn get_id(&self) -> RoutineId {
+        self.id
+    }
+
+    pub fn get_step(&self, index: usize) -> Option<RoutineStep> {
+        self.steps.get(index).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Steps through a routine from inside the controller, handing it one command at a time.
+pub struct RoutineEngine {
+    state: Arc<GlobalRobotState>,
+    routine_state: Arc<GlobalRoutineState>,
+    routine: Option<Routine>,
+    step: usize,
+    paused: bool,
+    step_started: Instant,
+    waited: Duration,
+    /// The commands issued since the last wait finished, which are issued again on resume.
+    segment: Vec<CommandRecord>,
+    pending: VecDeque<CommandRecord>,
+}
+
+impl RoutineEngine {
+    pub fn new(state: Arc<GlobalRobotState>) -> Self {
+        let routine_state = state.get_routine();
+        Self {
+            state,
+            routine_state,
+            routine: None,
+            step: 0,
+            paused: false,
+            step_started: Instant::now(),
+            waited: Duration::from_millis(0),
+            segment: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Starts running `routine`, replacing any routine which was already running.
+    pub fn start(&mut self, routine: Routine) {
+        info!("Starting routine {:?}", routine.get_id());
+        self.routine_state.start(routine.get_id(), routine.len());
+        self.routine = Some(routine);
+        self.step = 0;
+        self.paused = false;
+        self.step_started = Instant::now();
+        self.waited = Duration::from_millis(0);
+        self.segment.clear();
+        self.pending.clear();
+    }
+
+    /// Holds the routine at its current step. Returns `false` if there was no routine to pause.
+    pub fn pause(&mut self) -> bool {
+        if !self.is_running() {
+            return false;
+        }
+
+        self.waited += self.step_started.elapsed();
+        self.paused = true;
+        self.pending.clear();
+        self.routine_state.set_status(RoutineStatus::Paused);
+        true
+    }
+
+    /// Continues a paused routine, first issuing again the commands which led up to the step it
+    /// was paused on, since pausing stops the robot.
+    pub fn resume(&mut self) -> bool {
+        if self.routine.is_none() || !self.paused {
+            return false;
+        }
+
+        self.step_started = Instant::now();
+        self.paused = false;
+        self.pending = self.segment.iter().cloned().collect();
+        self.routine_state.set_status(RoutineStatus::Running);
+        true
+    }
+
+    /// Stops the routine at the driver's request. Returns `false` if there was no routine.
+    pub fn cancel(&mut self) -> bool {
+        self.finish(RoutineStatus::Cancelled)
+    }
+
+    /// Stops the routine because it is no longer safe to continue. Returns `false` if there was
+    /// no routine.
+    pub fn abort(&mut self) -> bool {
+        self.finish(RoutineStatus::Aborted)
+    }
+
+    /// Whether there is a routine which has not finished, even if it is paused.
+    pub fn is_active(&self) -> bool {
+        self.routine.is_some()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.routine.is_some() && !self.paused
+    }
+
+    /// Advances the routine as far as it can go, returning the next command to execute, if any.
+    pub fn poll(&mut self) -> Option<CommandRecord> {
+        if !self.is_running() {
+            return None;
+        }
+
+        if let Some(record) = self.pending.pop_front() {
+            return Some(record);
+        }
+
+        while let Some(routine) = &self.routine {
+            match routine.get_step(self.step) {
+                Some(RoutineStep::Command(record)) => {
+                    self.segment.push(record);
+                    self.advance();
+                    return Some(record);
+                }
+                Some(RoutineStep::Wait(duration)) => {
+                    if self.waited + self.step_started.elapsed() < duration {
+                        return None;
+                    }
+                    self.segment.clear();
+                    self.advance();
+                }
+                Some(RoutineStep::WaitUntil(condition)) => {
+                    if !condition.is_met(&self.state) {
+                        return None;
+                    }
+                    self.segment.clear();
+                    self.advance();
+                }
+                None => {
+                    info!("Routine {:?} completed", routine.get_id());
+                    self.finish(RoutineStatus::Completed);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn advance(&mut self) {
+        self.step += 1;
+        self.step_started = Instant::now();
+        self.waited = Duration::from_millis(0);
+        self.routine_state.set_step(self.step);
+    }
+
+    fn finish(&mut self, status: RoutineStatus) -> bool {
+        if self.routine.take().is_none() {
+            return false;
+        }
+
+        self.paused = false;
+        self.segment.clear();
+        self.pending.clear();
+        self.routine_state.set_status(status);
+        true
+    }
+}
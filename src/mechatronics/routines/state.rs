@@ -0,0 +1,111 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use atomic::Atomic;
+
+use crate::mechatronics::routines::RoutineId;
+
+/// Describes what the routine engine is currently doing.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RoutineStatus {
+    /// No routine has been started.
+    Idle,
+    Running,
+    Paused,
+    /// The last routine ran every one of its steps.
+    Completed,
+    /// The last routine was cancelled by the driver.
+    Cancelled,
+    /// The last routine was stopped because the robot was killed or lost communication.
+    Aborted,
+}
+
+pub struct GlobalRoutineState {
+    routine: Atomic<Option<RoutineId>>,
+    status: Atomic<RoutineStatus>,
+    step: AtomicUsize,
+    steps: AtomicUsize,
+}
+
+impl GlobalRoutineState {
+    pub fn new() -> Self {
+        Self {
+            routine: Atomic::new(None),
+            status: Atomic::new(RoutineStatus::Idle),
+            step: AtomicUsize::new(0),
+            steps: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get_current_state(&self) -> RoutineStateInstance {
+        RoutineStateInstance::new(
+            self.routine.load(Ordering::SeqCst),
+            self.status.load(Ordering::SeqCst),
+            self.step.load(Ordering::SeqCst),
+            self.steps.load(Ordering::SeqCst),
+        )
+    }
+
+    /// Records that `routine`, which has `steps` steps, has just been started.
+    pub fn start(&self, routine: RoutineId, steps: usize) {
+        self.routine.store(Some(routine), Ordering::SeqCst);
+        self.step.store(0, Ordering::SeqCst);
+        self.steps.store(steps, Ordering::SeqCst);
+        self.status.store(RoutineStatus::Running, Ordering::SeqCst);
+    }
+
+    pub fn set_step(&self, step: usize) {
+        self.step.store(step, Ordering::SeqCst);
+    }
+
+    pub fn set_status(&self, status: RoutineStatus) {
+        self.status.store(status, Ordering::SeqCst);
+    }
+
+    pub fn get_routine(&self) -> Option<RoutineId> {
+        self.routine.load(Ordering::SeqCst)
+    }
+
+    pub fn get_status(&self) -> RoutineStatus {
+        self.status.load(Ordering::SeqCst)
+    }
+
+    pub fn get_step(&self) -> usize {
+        self.step.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Serialize)]
+pub struct RoutineStateInstance {
+    routine: Option<RoutineId>,
+    status: RoutineStatus,
+    step: usize,
+    steps: usize,
+}
+
+impl RoutineStateInstance {
+    fn new(routine: Option<RoutineId>, status: RoutineStatus, step: usize, steps: usize) -> Self {
+        Self {
+            routine,
+            status,
+            step,
+            steps,
+        }
+    }
+
+    pub fn get_routine(&self) -> Option<RoutineId> {
+        self.routine
+    }
+
+    pub fn get_status(&self) -> RoutineStatus {
+        self.status
+    }
+
+    pub fn get_step(&self) -> usize {
+        self.step
+    }
+
+    pub fn get_steps(&self) -> usize {
+        self.steps
+    }
+}
@@ -0,0 +1,166 @@
+use std::thread::sleep;
+
+use super::*;
+
+fn setup() -> (Arc<GlobalRobotState>, RoutineEngine) {
+    let state = Arc::new(GlobalRobotState::new());
+    let engine = RoutineEngine::new(state.clone());
+
+    (state, engine)
+}
+
+fn set_dumper_limits(state: &GlobalRobotState, upper: bool, lower: bool) {
+    state.get_dumper().get_upper_limit().store(upper, Ordering::SeqCst);
+    state.get_dumper().get_lower_limit().store(lower, Ordering::SeqCst);
+}
+
+#[test]
+fn initial_state() {
+    let (state, mut engine) = setup();
+
+    assert_eq!(None, engine.poll());
+    assert_eq!(false, engine.is_active());
+    assert_eq!(RoutineStatus::Idle, state.get_routine().get_status());
+    assert_eq!(None, state.get_routine().get_routine());
+}
+
+#[test]
+fn commands_until_wait() {
+    let (state, mut engine) = setup();
+    engine.start(Routine::builtin(RoutineId::Dump));
+
+    assert_eq!(Some(CommandRecord::SwitchMode { mode: MechState::Dumping }), engine.poll());
+    assert_eq!(Some(CommandRecord::Dump), engine.poll());
+    assert_eq!(None, engine.poll());
+    assert_eq!(None, engine.poll());
+
+    let routine = state.get_routine().get_current_state();
+    assert_eq!(Some(RoutineId::Dump), routine.get_routine());
+    assert_eq!(RoutineStatus::Running, routine.get_status());
+    assert_eq!(2, routine.get_step());
+    assert_eq!(7, routine.get_steps());
+}
+
+#[test]
+fn wait_until_condition() {
+    let (state, mut engine) = setup();
+    engine.start(Routine::builtin(RoutineId::LowerIntake));
+    engine.poll();
+    engine.poll();
+
+    state.get_intake().get_left_actuator().set_lower(true);
+    assert_eq!(None, engine.poll());
+
+    state.get_intake().get_right_actuator().set_lower(true);
+    assert_eq!(Some(CommandRecord::StopActuators), engine.poll());
+    assert_eq!(None, engine.poll());
+    assert_eq!(RoutineStatus::Completed, state.get_routine().get_status());
+    assert_eq!(false, engine.is_active());
+}
+
+#[test]
+fn timed_wait() {
+    let (_, mut engine) = setup();
+    engine.start(Routine::new(RoutineId::Dig, vec![
+        RoutineStep::Wait(Duration::from_millis(30)),
+        RoutineStep::Command(CommandRecord::Brake),
+    ]));
+
+    assert_eq!(None, engine.poll());
+    sleep(Duration::from_millis(40));
+    assert_eq!(Some(CommandRecord::Brake), engine.poll());
+}
+
+#[test]
+fn full_dump() {
+    let (state, mut engine) = setup();
+    engine.start(Routine::new(RoutineId::Dump, vec![
+        RoutineStep::Command(CommandRecord::Dump),
+        RoutineStep::WaitUntil(RoutineCondition::DumperRaised),
+        RoutineStep::Command(CommandRecord::ResetDumper),
+        RoutineStep::WaitUntil(RoutineCondition::DumperLowered),
+    ]));
+
+    assert_eq!(Some(CommandRecord::Dump), engine.poll());
+    set_dumper_limits(&state, true, false);
+    assert_eq!(Some(CommandRecord::ResetDumper), engine.poll());
+    set_dumper_limits(&state, false, true);
+    assert_eq!(None, engine.poll());
+    assert_eq!(RoutineStatus::Completed, state.get_routine().get_status());
+    assert_eq!(4, state.get_routine().get_step());
+}
+
+#[test]
+fn pause_and_resume() {
+    let (state, mut engine) = setup();
+    engine.start(Routine::builtin(RoutineId::Dump));
+    engine.poll();
+    engine.poll();
+
+    assert!(engine.pause());
+    assert_eq!(RoutineStatus::Paused, state.get_routine().get_status());
+    set_dumper_limits(&state, true, false);
+    assert_eq!(None, engine.poll());
+
+    assert!(engine.resume());
+    assert_eq!(RoutineStatus::Running, state.get_routine().get_status());
+    assert_eq!(Some(CommandRecord::SwitchMode { mode: MechState::Dumping }), engine.poll());
+    assert_eq!(Some(CommandRecord::Dump), engine.poll());
+    assert_eq!(None, engine.poll());
+    assert_eq!(3, state.get_routine().get_step());
+}
+
+#[test]
+fn pause_holds_wait() {
+    let (_, mut engine) = setup();
+    engine.start(Routine::new(RoutineId::Dig, vec![
+        RoutineStep::Wait(Duration::from_millis(40)),
+        RoutineStep::Command(CommandRecord::Brake),
+    ]));
+    engine.poll();
+
+    engine.pause();
+    sleep(Duration::from_millis(50));
+    engine.resume();
+    assert_eq!(None, engine.poll());
+
+    sleep(Duration::from_millis(50));
+    assert_eq!(Some(CommandRecord::Brake), engine.poll());
+}
+
+#[test]
+fn cancel() {
+    let (state, mut engine) = setup();
+    engine.start(Routine::builtin(RoutineId::RaiseIntake));
+    engine.poll();
+
+    assert!(engine.cancel());
+    assert_eq!(None, engine.poll());
+    assert_eq!(RoutineStatus::Cancelled, state.get_routine().get_status());
+    assert_eq!(false, engine.cancel());
+    assert_eq!(false, engine.resume());
+}
+
+#[test]
+fn abort() {
+    let (state, mut engine) = setup();
+    engine.start(Routine::builtin(RoutineId::Dig));
+    engine.poll();
+    engine.pause();
+
+    assert!(engine.abort());
+    assert_eq!(RoutineStatus::Aborted, state.get_routine().get_status());
+    assert_eq!(false, engine.is_active());
+}
+
+#[test]
+fn restart() {
+    let (state, mut engine) = setup();
+    engine.start(Routine::builtin(RoutineId::Dump));
+    engine.poll();
+
+    engine.start(Routine::builtin(RoutineId::LowerIntake));
+    assert_eq!(Some(CommandRecord::SwitchMode { mode: MechState::Digging }), engine.poll());
+    assert_eq!(Some(RoutineId::LowerIntake), state.get_routine().get_routine());
+    assert_eq!(1, state.get_routine().get_step());
+}
@@ -1,37 +1,60 @@
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc::sync_channel;
+use std::thread::sleep;
+use std::time::Duration;
 
 use crate::framework::Runnable;
+use crate::mechatronics::bucket_ladder::ActuatorLayout;
 use crate::mechatronics::bucket_ladder::Intake;
 use crate::mechatronics::commands::RobotCommandFactory;
+use crate::mechatronics::controller::MechState;
 use crate::mechatronics::controller::RobotController;
 use crate::mechatronics::drive_train::DriveTrain;
 use crate::mechatronics::dumper::Dumper;
+use crate::mechatronics::dumper::state::DumperPhase;
+use crate::mechatronics::input_shaping::InputShaper;
+use crate::mechatronics::load_shedding::LoadShedder;
+use crate::mechatronics::routines::RoutineId;
+use crate::mechatronics::routines::state::RoutineStatus;
 use crate::motor_controllers::test_motor::TestMotor;
+use crate::robot_config::CurrentConfig;
+use crate::robot_config::RobotConfig;
+use crate::status::faults::Severity;
+use crate::status::faults::Subsystem;
 use crate::status::life::GlobalLifeState;
+use crate::status::life::KillReason;
 use crate::status::robot_state::GlobalRobotState;
 
 use super::*;
-use crate::robot_map::{DIGGING_RATE, MH_ACTUATOR_RATE, DUMPING_RATE, DUMPER_RESET_RATE};
+use crate::robot_map::{DIGGING_RATE, MH_ACTUATOR_RATE, DUMPING_RATE, DUMPER_RESET_RATE, LOAD_SHED_SCALE, PRECISION_SCALE};
 
 fn setup() -> (Arc<GlobalRobotState>, RobotController, RobotCommandFactory) {
+    setup_with_current(CurrentConfig::default())
+}
+
+fn setup_with_current(current: CurrentConfig) -> (Arc<GlobalRobotState>, RobotController, RobotCommandFactory) {
     let state = Arc::new(GlobalRobotState::new());
+    let config = RobotConfig::default();
 
     let digger = Box::new(TestMotor::new(state.get_intake().get_digger()));
     let intake_height = Box::new(TestMotor::new(state.get_intake().get_actuator()));
-    let intake = Intake::new(digger, intake_height, state.get_intake(), state.get_life());
+    let actuators = ActuatorLayout::Single { motor: intake_height, potentiometer: None };
+    let intake = Intake::new(digger, actuators, state.get_intake(), state.get_life(), config.get_intake().clone());
 
     let dumper_motor = Box::new(TestMotor::new(state.get_dumper().get_motor()));
-    let dumper = Dumper::new(state.get_life(), dumper_motor, state.get_dumper());
+    let dumper = Dumper::new(state.get_life(), dumper_motor, state.get_dumper(), config.get_dumper().clone());
 
     let left_drive = Box::new(TestMotor::new(state.get_drive().get_left()));
     let right_drive = Box::new(TestMotor::new(state.get_drive().get_right()));
-    let drive_train = DriveTrain::new(state.get_drive(), left_drive, right_drive, state.get_life());
+    let drive_train = DriveTrain::new(state.get_drive(), left_drive, right_drive, state.get_life(), config.get_drive().clone());
 
     let (sender, receiver) = sync_channel(10);
 
 //    let messenger = RobotMessenger::new(sender);
-    let controller = RobotController::new(receiver, drive_train, dumper, intake, state.get_life(), state.get_cycle_counter());
+    let controller = RobotController::new(receiver, drive_train, dumper, intake, state.clone(),
+                                          LoadShedder::new(state.get_current(), &current),
+                                          InputShaper::new(config.get_shaping().clone(), state.get_precision()));
 
     (state, controller, RobotCommandFactory::new())
 }
@@ -53,6 +76,54 @@ fn revive() {
     assert_eq!(true, state.get_life().is_alive());
 }
 
+#[test]
+fn switch_mode() {
+    let (state, mut controller, factory) = setup();
+    assert_eq!(MechState::Idle, state.get_current_state().get_mode());
+
+    controller.handle_message(Box::new(factory.generate_switch_mode_command(MechState::Driving)));
+    assert_eq!(true, state.get_drive().get_enabled());
+
+    controller.get_drive_train().drive(1.0, 1.0);
+    controller.handle_message(Box::new(factory.generate_switch_mode_command(MechState::Digging)));
+    assert_eq!(false, state.get_drive().get_enabled());
+    assert_eq!(0.0, state.get_drive().get_left().get_speed());
+    assert_eq!(true, state.get_intake().get_enabled());
+    assert_eq!(MechState::Digging, state.get_current_state().get_mode());
+
+    controller.handle_message(Box::new(factory.generate_switch_mode_command(MechState::Idle)));
+    assert_eq!(false, state.get_intake().get_enabled());
+    assert_eq!(MechState::Idle, controller.get_mode());
+}
+
+#[test]
+fn dead_robot_only_goes_idle() {
+    let (state, mut controller, factory) = setup();
+    controller.switch_mode(MechState::Driving);
+    controller.handle_message(Box::new(factory.generate_kill_command()));
+
+    assert_eq!(false, controller.switch_mode(MechState::Dumping));
+    assert_eq!(MechState::Driving, controller.get_mode());
+    assert_eq!(false, state.get_dumper().get_enabled());
+
+    assert_eq!(true, controller.switch_mode(MechState::Idle));
+    assert_eq!(false, state.get_drive().get_enabled());
+}
+
+#[test]
+fn dump_cycle_holds_mode() {
+    let (state, mut controller, factory) = setup();
+    controller.switch_mode(MechState::Dumping);
+    controller.handle_message(Box::new(factory.generate_cycle_dumper_command()));
+
+    assert_eq!(false, controller.switch_mode(MechState::Driving));
+    assert_eq!(DUMPING_RATE, state.get_dumper().get_motor().get_speed());
+
+    controller.handle_message(Box::new(factory.generate_stop_dumper_command()));
+    assert_eq!(true, controller.switch_mode(MechState::Driving));
+    assert_eq!(false, state.get_dumper().get_enabled());
+}
+
 #[test]
 fn kill_drive() {
     let (state, mut controller, factory) = setup();
@@ -80,6 +151,22 @@ fn kill_dumper() {
     assert_eq!(0.0, state.get_dumper().get_motor().get_speed());
 }
 
+#[test]
+fn kill_dumper_cycle() {
+    let (state, mut controller, factory) = setup();
+
+    controller.get_dumper().enable();
+    controller.handle_message(Box::new(factory.generate_cycle_dumper_command()));
+    assert_eq!(DUMPING_RATE, state.get_dumper().get_motor().get_speed());
+
+    controller.handle_message(Box::new(factory.generate_kill_command()));
+    state.get_dumper().get_upper_limit().store(true, Ordering::SeqCst);
+    controller.run();
+
+    assert_eq!(0.0, state.get_dumper().get_motor().get_speed());
+    assert_eq!(DumperPhase::Stopped, state.get_dumper().get_phase());
+}
+
 #[test]
 fn kill_intake() {
     let (state, mut controller, factory) = setup();
@@ -223,4 +310,289 @@ fn stop_dumper() {
     controller.handle_message(Box::new(factory.generate_stop_dumper_command()));
 
     assert_eq!(0.0, state.get_dumper().get_motor().get_speed());
-}
\ No newline at end of file
+}
+
+#[test]
+fn watchdog_trip() {
+    let (state, mut controller, factory) = setup();
+    state.get_watchdog().set_timeout(Duration::from_millis(10));
+
+    controller.get_drive_train().enable();
+    controller.handle_message(Box::new(factory.generate_drive_command(1.0, -1.0).unwrap()));
+    controller.run();
+    assert_eq!(false, state.get_watchdog().is_tripped());
+    assert_eq!(1.0, state.get_drive().get_left().get_speed());
+
+    sleep(Duration::from_millis(20));
+    controller.run();
+
+    assert_eq!(true, state.get_watchdog().is_tripped());
+    assert_eq!(true, state.get_current_state().get_watchdog().get_tripped());
+    assert_eq!(0.0, state.get_drive().get_left().get_speed());
+    assert_eq!(0.0, state.get_drive().get_right().get_speed());
+}
+
+#[test]
+fn watchdog_stops_intake_and_dumper() {
+    let (state, mut controller, factory) = setup();
+    state.get_watchdog().set_timeout(Duration::from_millis(10));
+
+    controller.get_intake().enable();
+    controller.handle_message(Box::new(factory.generate_dig_command()));
+    controller.handle_message(Box::new(factory.generate_raise_actuators_command()));
+    controller.get_dumper().enable();
+    controller.get_dumper().dump();
+
+    sleep(Duration::from_millis(20));
+    controller.run();
+
+    assert_eq!(0.0, state.get_intake().get_digger().get_speed());
+    assert_eq!(0.0, state.get_intake().get_actuator().get_speed());
+    assert_eq!(0.0, state.get_dumper().get_motor().get_speed());
+}
+
+#[test]
+fn heartbeat_clears_watchdog() {
+    let (state, mut controller, factory) = setup();
+    state.get_watchdog().set_timeout(Duration::from_millis(10));
+
+    sleep(Duration::from_millis(20));
+    controller.run();
+    assert_eq!(true, state.get_watchdog().is_tripped());
+
+    controller.handle_message(Box::new(factory.generate_heartbeat_command()));
+    controller.run();
+
+    assert_eq!(false, state.get_watchdog().is_tripped());
+    assert_eq!(1, state.get_watchdog().get_trips());
+}
+
+#[test]
+fn routine_issues_commands() {
+    let (state, mut controller, factory) = setup();
+
+    controller.handle_message(Box::new(factory.generate_start_routine_command(RoutineId::Dump)));
+    controller.run();
+    controller.run();
+
+    assert_eq!(true, state.get_dumper().get_enabled());
+    assert_eq!(DUMPING_RATE, state.get_dumper().get_motor().get_speed());
+    assert_eq!(RoutineStatus::Running, state.get_routine().get_status());
+}
+
+#[test]
+fn kill_aborts_routine() {
+    let (state, mut controller, factory) = setup();
+
+    controller.handle_message(Box::new(factory.generate_start_routine_command(RoutineId::LowerIntake)));
+    controller.run();
+    controller.handle_message(Box::new(factory.generate_kill_command()));
+    controller.run();
+
+    assert_eq!(RoutineStatus::Aborted, state.get_routine().get_status());
+    assert_eq!(0.0, state.get_intake().get_actuator().get_speed());
+}
+
+#[test]
+fn dead_robot_refuses_routine() {
+    let (state, mut controller, factory) = setup();
+
+    controller.handle_message(Box::new(factory.generate_kill_command()));
+    controller.handle_message(Box::new(factory.generate_start_routine_command(RoutineId::Dump)));
+    controller.run();
+
+    assert_eq!(RoutineStatus::Idle, state.get_routine().get_status());
+}
+
+#[test]
+fn pause_stops_robot() {
+    let (state, mut controller, factory) = setup();
+
+    controller.handle_message(Box::new(factory.generate_start_routine_command(RoutineId::LowerIntake)));
+    controller.run();
+    controller.run();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_intake().get_actuator().get_speed());
+
+    controller.handle_message(Box::new(factory.generate_pause_routine_command()));
+    controller.run();
+    assert_eq!(0.0, state.get_intake().get_actuator().get_speed());
+    assert_eq!(RoutineStatus::Paused, state.get_routine().get_status());
+
+    controller.handle_message(Box::new(factory.generate_resume_routine_command()));
+    controller.run();
+    controller.run();
+    assert_eq!(-MH_ACTUATOR_RATE, state.get_intake().get_actuator().get_speed());
+}
+
+#[test]
+fn brownout_throttles_digger() {
+    let (state, mut controller, _) = setup();
+
+    controller.get_intake().enable();
+    controller.get_intake().dig();
+    controller.get_drive_train().enable();
+    controller.get_drive_train().drive(1.0, 1.0);
+
+    state.get_current().update_current(state.get_current().get_brownout());
+    controller.run();
+
+    assert_eq!(DIGGING_RATE * LOAD_SHED_SCALE, state.get_intake().get_digger().get_speed());
+    assert_eq!(1.0, state.get_drive().get_left().get_speed());
+    assert_eq!(1, state.get_current().get_shed());
+
+    state.get_current().update_current(0.0);
+    controller.run();
+
+    assert_eq!(DIGGING_RATE, state.get_intake().get_digger().get_speed());
+    assert_eq!(0, state.get_current().get_shed());
+}
+
+#[test]
+fn critical_current_kills() {
+    let (state, mut controller, _) = setup_with_current(CurrentConfig::default().with_kill_on_critical(true));
+
+    controller.get_drive_train().enable();
+    controller.get_drive_train().drive(1.0, 1.0);
+    state.get_current().update_current(state.get_current().get_critical());
+    controller.run();
+
+    assert_eq!(false, state.get_life().is_alive());
+    assert_eq!(Some(KillReason::CriticalCurrent), state.get_life().get_reason());
+    assert_eq!(0.0, state.get_drive().get_left().get_speed());
+}
+
+#[test]
+fn critical_fault_kills() {
+    let (state, mut controller, factory) = setup();
+    state.get_faults().raise("critical_current", Subsystem::Power, Severity::Critical, "");
+    controller.run();
+    assert_eq!(true, state.get_life().is_alive());
+
+    state.get_faults().set_kill_on_critical(true);
+    controller.run();
+    assert_eq!(Some(KillReason::CriticalFault), state.get_life().get_reason());
+
+    // The robot cannot be revived until the fault is cleared.
+    controller.handle_message(Box::new(factory.generate_revive_command()));
+    controller.run();
+    assert_eq!(false, state.get_life().is_alive());
+
+    state.get_faults().clear("critical_current");
+    controller.handle_message(Box::new(factory.generate_revive_command()));
+    controller.run();
+    assert_eq!(true, state.get_life().is_alive());
+}
+
+#[test]
+fn critical_current_raises_fault() {
+    let (state, mut controller, _) = setup_with_current(CurrentConfig::default().with_kill_on_critical(true));
+
+    state.get_current().update_current(state.get_current().get_critical());
+    controller.run();
+
+    assert_eq!(Severity::Critical, state.get_faults().get_fault("critical_current").unwrap().get_severity());
+}
+
+#[test]
+fn critical_current_sheds_by_default() {
+    let (state, mut controller, _) = setup();
+
+    state.get_current().update_current(state.get_current().get_critical());
+    controller.run();
+    controller.run();
+
+    assert_eq!(true, state.get_life().is_alive());
+    assert_eq!(1, state.get_current().get_shed());
+    let fault = state.get_faults().get_fault("critical_current").unwrap();
+    assert_eq!(Severity::Warning, fault.get_severity());
+    assert_eq!(1, fault.get_count());
+}
+
+#[test]
+fn mechanism_fault_raised() {
+    let (state, mut controller, _) = setup();
+
+    state.get_dumper().get_upper_limit().store(true, Ordering::SeqCst);
+    state.get_dumper().get_lower_limit().store(true, Ordering::SeqCst);
+    controller.run();
+    controller.run();
+
+    let fault = state.get_faults().get_fault("dumper_limits").unwrap();
+    assert_eq!(Subsystem::Dumper, fault.get_subsystem());
+    assert_eq!(Severity::Error, fault.get_severity());
+    assert_eq!(1, fault.get_count());
+
+    // Clearing the fault from the registry lets the dumper move again.
+    state.get_dumper().get_upper_limit().store(false, Ordering::SeqCst);
+    state.get_faults().clear("dumper_limits");
+    controller.run();
+    assert_eq!(None, state.get_dumper().get_fault());
+}
+
+#[test]
+fn clear_fault_command_clears_registry() {
+    let (state, mut controller, factory) = setup();
+
+    state.get_intake().get_left_actuator().set_upper(true);
+    state.get_intake().get_left_actuator().set_lower(true);
+    controller.run();
+    assert_eq!(Subsystem::Intake, state.get_faults().get_fault("intake_limits").unwrap().get_subsystem());
+
+    state.get_intake().get_left_actuator().set_upper(false);
+    controller.handle_message(Box::new(factory.generate_clear_actuator_fault_command()));
+    assert_eq!(None, state.get_intake().get_fault());
+    assert_eq!(None, state.get_faults().get_fault("intake_limits"));
+}
+
+#[test]
+fn stall_raised() {
+    let (state, mut controller, _) = setup();
+
+    state.get_intake().get_digger().set_stalled(true);
+    controller.run();
+    controller.run();
+
+    let fault = state.get_faults().get_fault("digger_stall").unwrap();
+    assert_eq!(Severity::Warning, fault.get_severity());
+    assert_eq!(1, fault.get_count());
+}
+
+#[test]
+fn missed_edges_raised() {
+    let (state, mut controller, _) = setup();
+
+    state.get_drive().get_left_encoder().set_missed(3);
+    controller.run();
+    controller.run();
+    let fault = state.get_faults().get_fault("drive_encoders").unwrap();
+    assert_eq!(Subsystem::Drive, fault.get_subsystem());
+    assert_eq!(1, fault.get_count());
+
+    state.get_drive().get_right_encoder().set_missed(1);
+    controller.run();
+    assert_eq!(2, state.get_faults().get_fault("drive_encoders").unwrap().get_count());
+}
+
+#[test]
+fn operator_kill_reason() {
+    let (state, mut controller, factory) = setup();
+
+    controller.handle_message(Box::new(factory.generate_kill_command()));
+
+    assert_eq!(Some(KillReason::Operator), state.get_life().get_reason());
+}
+
+#[test]
+fn precision_slows_digger() {
+    let (state, mut controller, factory) = setup();
+
+    controller.get_intake().enable();
+    controller.handle_message(Box::new(factory.generate_dig_command()));
+    controller.handle_message(Box::new(factory.generate_set_precision_command(true)));
+
+    assert_eq!(true, state.get_precision().load(Ordering::SeqCst));
+    assert_eq!(DIGGING_RATE * PRECISION_SCALE, state.get_intake().get_digger().get_speed());
+
+    controller.handle_message(Box::new(factory.generate_set_precision_command(false)));
+    assert_eq!(DIGGING_RATE, state.get_intake().get_digger().get_speed());
+}
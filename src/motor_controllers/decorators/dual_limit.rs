@@ -29,6 +29,10 @@ impl MotorController for DualLimitMotor {
     fn get_motor_state(&self) -> &GlobalMotorState {
         self.motor.get_motor_state()
     }
+
+    fn run_cycle(&mut self) {
+        self.motor.run_cycle();
+    }
 }
 
 impl DualLimitMotor {
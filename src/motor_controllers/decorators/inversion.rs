@@ -17,6 +17,10 @@ impl MotorController for InvertedMotor {
     fn get_motor_state(&self) -> &GlobalMotorState {
         self.motor.get_motor_state()
     }
+
+    fn run_cycle(&mut self) {
+        self.motor.run_cycle();
+    }
 }
 
 impl InvertedMotor {
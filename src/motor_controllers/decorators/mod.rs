@@ -1,3 +1,6 @@
 pub mod inversion;
 pub mod limit;
-pub mod dual_limit;
\ No newline at end of file
+pub mod dual_limit;
+pub mod stall;
+pub mod ramp;
+pub mod pid;
\ No newline at end of file
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::framework::seconds;
+use crate::motor_controllers::GlobalMotorState;
+use crate::motor_controllers::MotorController;
+use crate::pinouts::encoder::GlobalEncoderState;
+use crate::robot_config::SpeedControlConfig;
+
+/// Holds a motor at the speed it is commanded, as measured by an encoder, so that it does not
+/// slow down as the battery drains or the load grows.
+///
+/// A command is taken as a fraction of `max_velocity`. The command is passed straight through to
+/// the motor, and a PID correction based on the measured speed is added to it on each cycle. The
+/// encoder is sampled by the monitor, which runs much faster than the controller, and its count is
+/// read from `encoder`.
+pub struct PidSpeedMotor {
+    motor: Box<MotorController>,
+    encoder: Arc<GlobalEncoderState>,
+    config: SpeedControlConfig,
+    command: f32,
+    correction: f32,
+    integral: f32,
+    last_error: Option<f32>,
+    last_count: i64,
+    last_update: Instant,
+}
+
+impl MotorController for PidSpeedMotor {
+    fn set_speed(&mut self, new_speed: f32) {
+        self.command = new_speed;
+        if new_speed == 0.0 {
+            self.reset();
+        }
+        self.apply();
+    }
+
+    fn stop(&mut self) {
+        self.command = 0.0;
+        self.reset();
+        self.motor.stop();
+    }
+
+    fn get_motor_state(&self) -> &GlobalMotorState {
+        self.motor.get_motor_state()
+    }
+
+    fn run_cycle(&mut self) {
+        self.update(Instant::now());
+    }
+}
+
+impl PidSpeedMotor {
+    pub fn new(motor: Box<MotorController>, encoder: Arc<GlobalEncoderState>, config: SpeedControlConfig) -> Self {
+        let last_count = encoder.get_count();
+        Self {
+            motor,
+            encoder,
+            config,
+            command: 0.0,
+            correction: 0.0,
+            integral: 0.0,
+            last_error: None,
+            last_count,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// Measures the speed of the motor and corrects its output towards the commanded speed.
+    fn update(&mut self, now: Instant) {
+        self.motor.run_cycle();
+
+        let elapsed = now.duration_since(self.last_update);
+        let dt = seconds(elapsed);
+        if dt <= 0.0 {
+            return;
+        }
+
+        let count = self.encoder.get_count();
+        let velocity = (count - self.last_count) as f32 / dt;
+        self.last_count = count;
+        self.last_update = now;
+        self.motor.get_motor_state().set_velocity(Some(velocity));
+        self.motor.get_motor_state().set_position(Some(count));
+
+        if self.command == 0.0 {
+            return;
+        }
+
+        let max_velocity = self.config.get_max_velocity();
+        let error = self.command * max_velocity - velocity;
+
+        // Keep the integral term from winding up beyond what could ever be useful.
+        let ki = self.config.get_ki();
+        self.integral += error * dt;
+        if ki > 0.0 {
+            let limit = max_velocity / ki;
+            self.integral = self.integral.max(-limit).min(limit);
+        }
+
+        let derivative = self.last_error.map_or(0.0, |last_error| (error - last_error) / dt);
+        self.last_error = Some(error);
+
+        self.correction = (self.config.get_kp() * error + ki * self.integral
+            + self.config.get_kd() * derivative) / max_velocity;
+        self.apply();
+    }
+
+    fn apply(&mut self) {
+        let output = (self.command + self.correction).max(-1.0).min(1.0);
+        self.motor.set_speed(output);
+    }
+
+    fn reset(&mut self) {
+        self.correction = 0.0;
+        self.integral = 0.0;
+        self.last_error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::motor_controllers::test_motor::TestMotor;
+
+    use super::*;
+
+    fn setup(kp: f32, ki: f32, kd: f32) -> (Arc<GlobalMotorState>, Arc<GlobalEncoderState>, PidSpeedMotor, Instant) {
+        let state = Arc::new(GlobalMotorState::new());
+        let encoder = Arc::new(GlobalEncoderState::new());
+        let config = SpeedControlConfig::default().with_kp(kp).with_ki(ki).with_kd(kd).with_max_velocity(128.0);
+        let motor = PidSpeedMotor::new(Box::new(TestMotor::new(state.clone())), encoder.clone(), config);
+        let start = motor.last_update;
+
+        (state, encoder, motor, start)
+    }
+
+    #[test]
+    fn passes_command_through() {
+        let (state, _, mut motor, _) = setup(1.0, 0.0, 0.0);
+
+        motor.set_speed(0.5);
+        assert_eq!(0.5, state.get_speed());
+    }
+
+    #[test]
+    fn proportional() {
+        let (state, encoder, mut motor, start) = setup(1.0, 0.0, 0.0);
+        motor.set_speed(0.5);
+
+        encoder.set_count(16);
+        motor.update(start + Duration::from_millis(500));
+        assert_eq!(Some(32.0), state.get_velocity());
+        assert_eq!(Some(16), state.get_position());
+        assert_eq!(0.75, state.get_speed());
+
+        // The correction is kept when the command changes.
+        motor.set_speed(0.25);
+        assert_eq!(0.5, state.get_speed());
+    }
+
+    #[test]
+    fn integral() {
+        let (state, encoder, mut motor, start) = setup(0.0, 2.0, 0.0);
+        motor.set_speed(0.5);
+
+        encoder.set_count(16);
+        motor.update(start + Duration::from_millis(500));
+        assert_eq!(0.75, state.get_speed());
+    }
+
+    #[test]
+    fn saturates() {
+        let (state, _, mut motor, start) = setup(4.0, 0.0, 0.0);
+        motor.set_speed(0.5);
+
+        motor.update(start + Duration::from_millis(500));
+        assert_eq!(1.0, state.get_speed());
+    }
+
+    #[test]
+    fn stop_resets() {
+        let (state, _, mut motor, start) = setup(1.0, 1.0, 0.0);
+        motor.set_speed(0.5);
+        motor.update(start + Duration::from_millis(500));
+
+        motor.stop();
+        assert_eq!(0.0, state.get_speed());
+
+        motor.set_speed(0.5);
+        assert_eq!(0.5, state.get_speed());
+    }
+}
@@ -0,0 +1,174 @@
+use std::time::Instant;
+
+use crate::framework::seconds;
+use crate::motor_controllers::GlobalMotorState;
+use crate::motor_controllers::MotorController;
+
+/// Limits how quickly the speed of a motor can change, so that it is not slammed from one
+/// direction to the other.
+///
+/// New speeds are approached on each cycle, at `acceleration` while the motor speeds up and at
+/// `deceleration` while it slows down, both in units of output per second. Stopping the motor
+/// still takes effect immediately.
+pub struct RampedMotor {
+    motor: Box<MotorController>,
+    acceleration: f32,
+    deceleration: f32,
+    target: f32,
+    output: f32,
+    last_update: Instant,
+}
+
+impl MotorController for RampedMotor {
+    fn set_speed(&mut self, new_speed: f32) {
+        self.advance(Instant::now());
+        self.target = new_speed;
+    }
+
+    fn stop(&mut self) {
+        self.target = 0.0;
+        self.output = 0.0;
+        self.last_update = Instant::now();
+        self.motor.stop();
+    }
+
+    fn get_motor_state(&self) -> &GlobalMotorState {
+        self.motor.get_motor_state()
+    }
+
+    fn run_cycle(&mut self) {
+        self.advance(Instant::now());
+        self.motor.run_cycle();
+    }
+}
+
+impl RampedMotor {
+    pub fn new(motor: Box<MotorController>, acceleration: f32, deceleration: f32) -> Self {
+        Self {
+            motor,
+            acceleration,
+            deceleration,
+            target: 0.0,
+            output: 0.0,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// Moves the output as far towards the target as the time since the last update allows.
+    fn advance(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_update);
+        let mut remaining = seconds(elapsed);
+        self.last_update = now;
+
+        let previous = self.output;
+        let reversing = self.output * self.target < 0.0;
+        if reversing || self.target.abs() < self.output.abs() {
+            // Slow down first, through zero if the motor is changing direction.
+            let slowest = if reversing { 0.0 } else { self.target };
+            let needed = (self.output - slowest).abs() / self.deceleration;
+            if needed > remaining {
+                self.output -= self.output.signum() * self.deceleration * remaining;
+                remaining = 0.0;
+            } else {
+                self.output = slowest;
+                remaining -= needed;
+            }
+        }
+
+        let distance = self.target - self.output;
+        let step = self.acceleration * remaining;
+        if step >= distance.abs() {
+            self.output = self.target;
+        } else {
+            self.output += distance.signum() * step;
+        }
+
+        if (self.output - previous).abs() > 0.0 {
+            self.motor.set_speed(self.output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::motor_controllers::test_motor::TestMotor;
+
+    use super::*;
+
+    fn setup() -> (Arc<GlobalMotorState>, RampedMotor, Instant) {
+        let state = Arc::new(GlobalMotorState::new());
+        let motor = RampedMotor::new(Box::new(TestMotor::new(state.clone())), 2.0, 4.0);
+        let start = motor.last_update;
+
+        (state, motor, start)
+    }
+
+    #[test]
+    fn accelerate() {
+        let (state, mut motor, start) = setup();
+
+        motor.target = 1.0;
+        motor.advance(start + Duration::from_millis(250));
+        assert_eq!(0.5, state.get_speed());
+
+        motor.advance(start + Duration::from_millis(500));
+        assert_eq!(1.0, state.get_speed());
+
+        motor.advance(start + Duration::from_millis(750));
+        assert_eq!(1.0, state.get_speed());
+    }
+
+    #[test]
+    fn decelerate() {
+        let (state, mut motor, start) = setup();
+        motor.target = 1.0;
+        motor.advance(start + Duration::from_millis(500));
+
+        motor.target = 0.5;
+        motor.advance(start + Duration::from_millis(625));
+        assert_eq!(0.5, state.get_speed());
+    }
+
+    #[test]
+    fn reverse() {
+        let (state, mut motor, start) = setup();
+        motor.target = 1.0;
+        motor.advance(start + Duration::from_millis(500));
+
+        motor.target = -1.0;
+        motor.advance(start + Duration::from_millis(625));
+        assert_eq!(0.5, state.get_speed());
+
+        // A quarter of a second to slow to a stop, and another quarter to reach half speed backwards.
+        motor.advance(start + Duration::from_millis(1000));
+        assert_eq!(-0.5, state.get_speed());
+    }
+
+    #[test]
+    fn stop_is_immediate() {
+        let (state, mut motor, start) = setup();
+        motor.target = 1.0;
+        motor.advance(start + Duration::from_millis(500));
+
+        motor.stop();
+        assert_eq!(0.0, state.get_speed());
+
+        motor.run_cycle();
+        assert_eq!(0.0, state.get_speed());
+    }
+
+    #[test]
+    fn set_speed_ramps() {
+        let (state, mut motor, start) = setup();
+
+        motor.set_speed(1.0);
+        assert_eq!(1.0, motor.target);
+        assert!(state.get_speed() < 0.5);
+
+        motor.advance(start + Duration::from_millis(600));
+        assert_eq!(1.0, state.get_speed());
+    }
+}
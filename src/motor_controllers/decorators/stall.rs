@@ -0,0 +1,176 @@
+use std::time::Instant;
+
+use crate::motor_controllers::GlobalMotorState;
+use crate::motor_controllers::MotorController;
+use crate::pinouts::analog::input::AnalogInput;
+use crate::robot_config::StallConfig;
+
+/// Stops a motor which draws more than its stall current for too long while it is commanded to
+/// move. The motor is reported as stalled and refuses to move until it is next told to stop.
+///
+/// The current is read from `sensor` when there is one. Otherwise it is taken from the motor
+/// state, where a smart motor controller can report it.
+pub struct StallDetectingMotor {
+    motor: Box<MotorController>,
+    sensor: Option<Box<AnalogInput>>,
+    config: StallConfig,
+    commanded: f32,
+    straining_since: Option<Instant>,
+}
+
+impl MotorController for StallDetectingMotor {
+    fn set_speed(&mut self, new_speed: f32) {
+        if self.motor.get_motor_state().get_stalled() {
+            self.motor.stop();
+        } else {
+            self.commanded = new_speed;
+            self.motor.set_speed(new_speed);
+        }
+    }
+
+    fn stop(&mut self) {
+        self.commanded = 0.0;
+        self.straining_since = None;
+        self.motor.get_motor_state().set_stalled(false);
+        self.motor.stop();
+    }
+
+    fn get_motor_state(&self) -> &GlobalMotorState {
+        self.motor.get_motor_state()
+    }
+
+    fn run_cycle(&mut self) {
+        self.motor.run_cycle();
+        self.check_at(Instant::now());
+    }
+}
+
+impl StallDetectingMotor {
+    pub fn new(motor: Box<MotorController>, sensor: Option<Box<AnalogInput>>, config: StallConfig) -> Self {
+        Self {
+            motor,
+            sensor,
+            config,
+            commanded: 0.0,
+            straining_since: None,
+        }
+    }
+
+    /// Reads the current and stops the motor if it has been straining for too long at `now`.
+    fn check_at(&mut self, now: Instant) {
+        let zero_volts = self.config.get_zero_volts();
+        let amps_per_volt = self.config.get_amps_per_volt();
+        if let Some(sensor) = &mut self.sensor {
+            let current = sensor.get_value().map(|volts| (volts - zero_volts) * amps_per_volt);
+            self.motor.get_motor_state().set_current(current);
+        }
+
+        let current = self.motor.get_motor_state().get_current();
+        let straining = self.commanded.abs() > 0.0
+            && current.map_or(false, |current| current >= self.config.get_current());
+        if !straining {
+            self.straining_since = None;
+            return;
+        }
+
+        let since = *self.straining_since.get_or_insert(now);
+        if now.duration_since(since) >= self.config.get_time() {
+            error!("Motor stalled drawing {:?} amps, stopping it!", current);
+            self.motor.stop();
+            self.motor.get_motor_state().set_stalled(true);
+            self.commanded = 0.0;
+            self.straining_since = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use atomic::Atomic;
+
+    use crate::motor_controllers::test_motor::TestMotor;
+    use crate::pinouts::analog::input::TestAnalogInput;
+
+    use super::*;
+
+    fn setup(sensor: Option<Box<AnalogInput>>) -> (Arc<GlobalMotorState>, StallDetectingMotor) {
+        let state = Arc::new(GlobalMotorState::new());
+        let motor = Box::new(TestMotor::new(state.clone()));
+        let config = StallConfig::default().with_current(1.5).with_time_millis(20).with_zero_volts(0.5).with_amps_per_volt(2.0);
+        let motor = StallDetectingMotor::new(motor, sensor, config);
+
+        (state, motor)
+    }
+
+    #[test]
+    fn stall() {
+        let (state, mut motor) = setup(None);
+        let start = Instant::now();
+        state.set_current(Some(2.0));
+
+        motor.set_speed(1.0);
+        motor.check_at(start);
+        assert_eq!(1.0, state.get_speed());
+        assert_eq!(false, state.get_stalled());
+
+        motor.check_at(start + Duration::from_millis(10));
+        assert_eq!(1.0, state.get_speed());
+
+        motor.check_at(start + Duration::from_millis(20));
+        assert_eq!(0.0, state.get_speed());
+        assert_eq!(true, state.get_current_state().get_stalled());
+
+        motor.set_speed(1.0);
+        assert_eq!(0.0, state.get_speed());
+
+        motor.stop();
+        motor.set_speed(1.0);
+        assert_eq!(false, state.get_stalled());
+        assert_eq!(1.0, state.get_speed());
+    }
+
+    #[test]
+    fn stopped_motor_does_not_stall() {
+        let (state, mut motor) = setup(None);
+        let start = Instant::now();
+        state.set_current(Some(2.0));
+
+        motor.check_at(start);
+        motor.check_at(start + Duration::from_millis(30));
+
+        assert_eq!(false, state.get_stalled());
+    }
+
+    #[test]
+    fn strain_must_be_continuous() {
+        let (state, mut motor) = setup(None);
+        let start = Instant::now();
+        motor.set_speed(1.0);
+
+        state.set_current(Some(2.0));
+        motor.check_at(start);
+        state.set_current(Some(1.0));
+        motor.check_at(start + Duration::from_millis(30));
+        state.set_current(Some(2.0));
+        motor.check_at(start + Duration::from_millis(40));
+
+        assert_eq!(false, state.get_stalled());
+        assert_eq!(1.0, state.get_speed());
+    }
+
+    #[test]
+    fn reads_sensor() {
+        let volts = Arc::new(Atomic::new(Some(1.0)));
+        let (state, mut motor) = setup(Some(Box::new(TestAnalogInput::new(volts.clone()))));
+
+        motor.run_cycle();
+        assert_eq!(Some(1.0), state.get_current());
+
+        volts.store(None, atomic::Ordering::SeqCst);
+        motor.run_cycle();
+        assert_eq!(None, state.get_current_state().get_current());
+    }
+}
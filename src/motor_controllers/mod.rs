@@ -1,3 +1,5 @@
+use std::sync::atomic::AtomicBool;
+
 use atomic::Atomic;
 use atomic::Ordering as AtOrd;
 
@@ -19,21 +21,34 @@ pub trait MotorController: Send {
 
     /// Returns the current motor state
     fn get_motor_state(&self) -> &GlobalMotorState;
+
+    /// Does any periodic work the motor controller needs, such as reading its current.
+    /// Called once per cycle by the mechanism which owns the motor.
+    fn run_cycle(&mut self) {}
 }
 
 pub struct GlobalMotorState {
     speed: Atomic<f32>,
+    current: Atomic<Option<f32>>,
+    stalled: AtomicBool,
+    velocity: Atomic<Option<f32>>,
+    position: Atomic<Option<i64>>,
 }
 
 impl GlobalMotorState {
     pub fn new() -> Self {
         GlobalMotorState {
             speed: Atomic::new(0.0),
+            current: Atomic::new(None),
+            stalled: AtomicBool::new(false),
+            velocity: Atomic::new(None),
+            position: Atomic::new(None),
         }
     }
 
     pub fn get_current_state(&self) -> MotorStateInstance {
-        MotorStateInstance::new(self.get_speed())
+        MotorStateInstance::new(self.get_speed(), self.get_current(), self.get_stalled(), self.get_velocity(),
+                                self.get_position())
     }
 
     pub fn get_speed(&self) -> f32 {
@@ -43,21 +58,83 @@ impl GlobalMotorState {
     pub fn set_speed(&self, value: f32) {
         self.speed.store(value, AtOrd::Relaxed);
     }
+
+    /// The current drawn by the motor in amps, if it is measured.
+    pub fn get_current(&self) -> Option<f32> {
+        self.current.load(AtOrd::Relaxed)
+    }
+
+    /// Records the current drawn by the motor, either from a sensor or as reported by a smart
+    /// motor controller.
+    pub fn set_current(&self, value: Option<f32>) {
+        self.current.store(value, AtOrd::Relaxed);
+    }
+
+    /// Whether the motor has been stopped because it stalled.
+    pub fn get_stalled(&self) -> bool {
+        self.stalled.load(AtOrd::Relaxed)
+    }
+
+    pub fn set_stalled(&self, value: bool) {
+        self.stalled.store(value, AtOrd::Relaxed);
+    }
+
+    /// The speed of the motor in encoder counts per second, if it is measured.
+    pub fn get_velocity(&self) -> Option<f32> {
+        self.velocity.load(AtOrd::Relaxed)
+    }
+
+    pub fn set_velocity(&self, value: Option<f32>) {
+        self.velocity.store(value, AtOrd::Relaxed);
+    }
+
+    /// How far the motor has turned in encoder counts, if it is measured.
+    pub fn get_position(&self) -> Option<i64> {
+        self.position.load(AtOrd::Relaxed)
+    }
+
+    pub fn set_position(&self, value: Option<i64>) {
+        self.position.store(value, AtOrd::Relaxed);
+    }
 }
 
 #[derive(Serialize)]
 pub struct MotorStateInstance {
     speed: f32,
+    current: Option<f32>,
+    stalled: bool,
+    velocity: Option<f32>,
+    position: Option<i64>,
 }
 
 impl MotorStateInstance {
-    pub fn new(speed: f32) -> Self {
+    pub fn new(speed: f32, current: Option<f32>, stalled: bool, velocity: Option<f32>, position: Option<i64>) -> Self {
         Self {
             speed,
+            current,
+            stalled,
+            velocity,
+            position,
         }
     }
 
     pub fn get_speed(&self) -> f32 {
         self.speed
     }
+
+    pub fn get_current(&self) -> Option<f32> {
+        self.current
+    }
+
+    pub fn get_stalled(&self) -> bool {
+        self.stalled
+    }
+
+    pub fn get_velocity(&self) -> Option<f32> {
+        self.velocity
+    }
+
+    pub fn get_position(&self) -> Option<i64> {
+        self.position
+    }
 }
\ No newline at end of file
@@ -28,6 +28,12 @@ impl MotorController for MotorGroup {
     fn get_motor_state(&self) -> &GlobalMotorState {
         &self.state
     }
+
+    fn run_cycle(&mut self) {
+        for motor in &mut self.motors {
+            motor.run_cycle();
+        }
+    }
 }
 
 impl MotorGroup {
@@ -0,0 +1,97 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::pinouts::analog::input::AnalogInput;
+use crate::pinouts::sysfs;
+use crate::robot_map::ADC_MAX_RAW;
+use crate::robot_map::ADC_REFERENCE_VOLTS;
+
+/// An input channel of the BeagleBone's ADC, read through the IIO sysfs interface.
+///
+/// Values are reported in volts, between 0 and `ADC_REFERENCE_VOLTS`.
+pub struct BeagleBoneAdc {
+    path: PathBuf,
+    failed: bool,
+}
+
+impl AnalogInput for BeagleBoneAdc {
+    fn get_value(&mut self) -> Option<f32> {
+        let value = sysfs::read_attribute(&self.path).and_then(|raw| parse_raw(&raw));
+        match value {
+            Ok(raw) => {
+                self.failed = false;
+                Some(raw_to_volts(raw))
+            }
+            Err(e) => {
+                // Only report the first failure, as the ADC is read on every monitor cycle.
+                if !self.failed {
+                    error!("Failed to read ADC: {}", e);
+                    self.failed = true;
+                }
+                None
+            }
+        }
+    }
+}
+
+impl BeagleBoneAdc {
+    pub fn new(iio_root: &Path, device: u8, channel: u8) -> Self {
+        Self {
+            path: sysfs::adc_path(iio_root, device, channel),
+            failed: false,
+        }
+    }
+}
+
+fn parse_raw(raw: &str) -> std::io::Result<u16> {
+    raw.parse().map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData,
+                                                format!("ADC reading is not a number: {}", raw)))
+}
+
+/// Converts a raw 12 bit ADC reading to volts.
+pub fn raw_to_volts(raw: u16) -> f32 {
+    f32::from(raw.min(ADC_MAX_RAW)) / f32::from(ADC_MAX_RAW) * ADC_REFERENCE_VOLTS
+}
+
+/// Converts a voltage to the raw reading the ADC would report for it.
+pub fn volts_to_raw(volts: f32) -> u16 {
+    (volts.max(0.0).min(ADC_REFERENCE_VOLTS) / ADC_REFERENCE_VOLTS * f32::from(ADC_MAX_RAW)).round() as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pinouts::sysfs::FakeSysfs;
+    use crate::robot_config::RobotConfig;
+
+    use super::*;
+
+    #[test]
+    fn conversion() {
+        assert_eq!(0.0, raw_to_volts(0));
+        assert_eq!(ADC_REFERENCE_VOLTS, raw_to_volts(ADC_MAX_RAW));
+        assert_eq!(ADC_REFERENCE_VOLTS, raw_to_volts(u16::max_value()));
+        assert_eq!(ADC_MAX_RAW, volts_to_raw(5.0));
+        assert_eq!(1024, volts_to_raw(0.45));
+    }
+
+    #[test]
+    fn read_fake_adc() {
+        let sysfs = FakeSysfs::new(&RobotConfig::default()).unwrap();
+        sysfs.add_adc(0, 3).unwrap();
+        let mut adc = BeagleBoneAdc::new(sysfs.get_iio_root(), 0, 3);
+
+        sysfs.set_adc(0, 3, ADC_MAX_RAW).unwrap();
+        assert_eq!(Some(ADC_REFERENCE_VOLTS), adc.get_value());
+
+        sysfs.set_adc(0, 3, 0).unwrap();
+        assert_eq!(Some(0.0), adc.get_value());
+    }
+
+    #[test]
+    fn missing_adc() {
+        let sysfs = FakeSysfs::new(&RobotConfig::default()).unwrap();
+        let mut adc = BeagleBoneAdc::new(sysfs.get_iio_root(), 1, 6);
+
+        assert_eq!(None, adc.get_value());
+    }
+}
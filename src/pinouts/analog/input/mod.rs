@@ -1,3 +1,28 @@
+use std::sync::Arc;
+
+use atomic::Atomic;
+use atomic::Ordering;
+
+pub mod libbeaglebone;
+
 pub trait AnalogInput: Send {
     fn get_value(&mut self) -> Option<f32>;
-}
\ No newline at end of file
+}
+
+pub struct TestAnalogInput {
+    value: Arc<Atomic<Option<f32>>>,
+}
+
+impl AnalogInput for TestAnalogInput {
+    fn get_value(&mut self) -> Option<f32> {
+        self.value.load(Ordering::SeqCst)
+    }
+}
+
+impl TestAnalogInput {
+    pub fn new(value: Arc<Atomic<Option<f32>>>) -> Self {
+        Self {
+            value
+        }
+    }
+}
@@ -1,37 +1,47 @@
-use libbeaglebone::enums::DeviceState;
-use libbeaglebone::pwm::PWM;
-use libbeaglebone::pwm::PWMState;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::pinouts::analog::output::AnalogOutput;
 use crate::pinouts::analog::output::PwmOutput;
+use crate::pinouts::sysfs;
+use crate::status::faults::GlobalFaultState;
+use crate::status::faults::Severity;
+use crate::status::faults::Subsystem;
 
+/// A PWM channel controlled through the `pwm` class of a sysfs tree.
+///
+/// Failures to access the channel are raised in `faults` under the code `pwm<chip>_<num>`.
 pub struct LibBeagleBonePwm {
-    pwm: PWM,
+    dir: PathBuf,
     period: u32,
+    code: String,
+    faults: Arc<GlobalFaultState>,
 }
 
 impl AnalogOutput for LibBeagleBonePwm {
     fn set_value(&mut self, val: f32) {
-        if let Err(e) = self.pwm.set_duty_cycle((val * self.period as f32) as u32) {
-            error!("{}", e);
+        if let Err(e) = self.set_duty_cycle((val * self.period as f32) as u32) {
+            self.report(&e);
         }
     }
 }
 
 impl PwmOutput for LibBeagleBonePwm {
     fn set_pulse_duty_cycle(&mut self, val: u32) {
-        if let Err(e) = self.pwm.set_duty_cycle(val) {
-            error!("{}", e);
+        if let Err(e) = self.set_duty_cycle(val) {
+            self.report(&e);
         }
 
-        if let Err(e) = self.pwm.set_period(val) {
-            error!("{}", e);
+        if let Err(e) = self.write_period(val) {
+            self.report(&e);
         }
     }
 
     fn set_period(&mut self, val: u32) {
-        if let Err(e) = self.pwm.set_period(val) {
-            error!("{}", e);
+        if let Err(e) = self.write_period(val) {
+            self.report(&e);
         }
 
         self.period = val;
@@ -39,22 +49,38 @@ impl PwmOutput for LibBeagleBonePwm {
 }
 
 impl LibBeagleBonePwm {
-    pub fn new(chip: u8, num: u8) -> Self {
-        let mut pwm = PWM::new(chip, num);
+    pub fn new(root: &Path, chip: u8, num: u8, faults: Arc<GlobalFaultState>) -> Self {
+        let pwm = Self {
+            dir: sysfs::pwm_dir(root, chip, num),
+            period: 20_000,
+            code: format!("pwm{}_{}", chip, num),
+            faults,
+        };
 
-        if let Err(e) = pwm.set_period(20_000) {
-            error!("{}", e);
+        let export = sysfs::pwm_chip_dir(root, chip).join("export");
+        if let Err(e) = sysfs::write_attribute(&export, &num.to_string()) {
+            pwm.report(&e);
         }
-        if let Err(e) = pwm.set_export(DeviceState::Exported) {
-            error!("{}", e);
+        if let Err(e) = pwm.write_period(20_000) {
+            pwm.report(&e);
         }
-        if let Err(e) = pwm.set_state(PWMState::Enabled) {
-            error!("{}", e);
+        if let Err(e) = sysfs::write_attribute(&pwm.dir.join("enable"), "1") {
+            pwm.report(&e);
         }
 
-        Self {
-            pwm,
-            period: 20_000,
-        }
+        pwm
+    }
+
+    fn set_duty_cycle(&self, val: u32) -> io::Result<()> {
+        sysfs::write_attribute(&self.dir.join("duty_cycle"), &val.to_string())
     }
-}
\ No newline at end of file
+
+    fn write_period(&self, val: u32) -> io::Result<()> {
+        sysfs::write_attribute(&self.dir.join("period"), &val.to_string())
+    }
+
+    fn report(&self, error: &io::Error) {
+        error!("{}", error);
+        self.faults.raise(&self.code, Subsystem::Pinouts, Severity::Error, &error.to_string());
+    }
+}
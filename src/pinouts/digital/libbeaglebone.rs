@@ -1,40 +1,44 @@
-use libbeaglebone::enums::DeviceState;
-use libbeaglebone::gpio::GPIO;
-use libbeaglebone::gpio::PinDirection;
-use libbeaglebone::gpio::PinState;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use libbeaglebone::pins::Pin;
 
 use crate::pinouts::digital::input::DigitalInput;
 use crate::pinouts::digital::output::DigitalOutput;
+use crate::pinouts::sysfs;
+use crate::status::faults::GlobalFaultState;
+use crate::status::faults::Severity;
+use crate::status::faults::Subsystem;
 
+/// A GPIO pin controlled through the `gpio` class of a sysfs tree.
+///
+/// Failures to access the pin are raised in `faults` under the code `gpio<number>`.
 pub struct GpioPinout {
-    pin: GPIO,
+    root: PathBuf,
+    pin: Pin,
+    faults: Arc<GlobalFaultState>,
 }
 
 impl DigitalOutput for GpioPinout {
     fn set_value(&mut self, val: bool) {
-        let mut set_pin = |state| {
-            if let Err(e) = self.pin.write(state) {
-                error!("{}", e);
-                self.set_output();
-            }
-        };
-        if val {
-            set_pin(PinState::High);
-        } else {
-            set_pin(PinState::Low);
+        let value = if val { "1" } else { "0" };
+        if let Err(e) = sysfs::write_attribute(&self.attribute("value"), value) {
+            self.report(&e);
+            self.set_output();
         }
     }
 }
 
 impl DigitalInput for GpioPinout {
     fn get_value(&self) -> bool {
-        match self.pin.read() {
+        match sysfs::read_attribute(&self.attribute("value")) {
             Ok(val) => {
-                PinState::High == val
+                "1" == val
             }
             Err(e) => {
-                error!("{}", e);
+                self.report(&e);
                 false
             }
         }
@@ -42,45 +46,62 @@ impl DigitalInput for GpioPinout {
 }
 
 impl GpioPinout {
-    pub fn new(pin_number: Pin) -> Self {
-        let pin = GPIO::new(pin_number);
-        if let Err(e) = pin.set_export(DeviceState::Exported) {
-            error!("{}", e);
-        }
-        Self {
-            pin,
+    pub fn new(root: &Path, pin_number: Pin, faults: Arc<GlobalFaultState>) -> Self {
+        let pinout = Self {
+            root: root.to_owned(),
+            pin: pin_number,
+            faults,
+        };
+        let export = pinout.root.join("gpio").join("export");
+        if let Err(e) = sysfs::write_attribute(&export, &(pin_number as u8).to_string()) {
+            pinout.report(&e);
         }
+        pinout
     }
 
     pub fn set_output(&mut self) {
-        if let Err(error) = self.pin.set_direction(PinDirection::Out) {
-            error!("{}", error);
+        if let Err(error) = self.set_direction("out") {
+            self.report(&error);
         }
     }
 
     pub fn set_output_twice(&mut self) {
-        if self.pin.set_direction(PinDirection::Out).is_err() {
+        if self.set_direction("out").is_err() {
             self.set_output();
         }
     }
 
     pub fn set_input(&mut self) {
-        if let Err(error) = self.pin.set_direction(PinDirection::In) {
-            error!("{}", error);
+        if let Err(error) = self.set_direction("in") {
+            self.report(&error);
         }
     }
 
     pub fn set_input_twice(&mut self) {
-        if self.pin.set_direction(PinDirection::In).is_err() {
+        if self.set_direction("in").is_err() {
             self.set_input();
         }
     }
+
+    fn set_direction(&self, direction: &str) -> std::io::Result<()> {
+        sysfs::write_attribute(&self.attribute("direction"), direction)
+    }
+
+    fn attribute(&self, name: &str) -> PathBuf {
+        sysfs::gpio_dir(&self.root, self.pin).join(name)
+    }
+
+    fn report(&self, error: &io::Error) {
+        error!("{}", error);
+        self.faults.raise(&format!("gpio{}", self.pin as u8), Subsystem::Pinouts, Severity::Error, &error.to_string());
+    }
 }
 
 impl Drop for GpioPinout {
     fn drop(&mut self) {
-        if let Err(e) = self.pin.set_export(DeviceState::Unexported) {
-            error!("{}", e);
+        let unexport = self.root.join("gpio").join("unexport");
+        if let Err(e) = sysfs::write_attribute(&unexport, &(self.pin as u8).to_string()) {
+            self.report(&e);
         }
     }
-}
\ No newline at end of file
+}
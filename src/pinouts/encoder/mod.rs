@@ -0,0 +1,77 @@
+use std::sync::atomic::AtomicUsize;
+
+use atomic::Atomic;
+use atomic::Ordering;
+
+pub mod quadrature;
+
+/// Measures how far a shaft has turned.
+pub trait Encoder: Send {
+    /// Samples the encoder's inputs. Encoders which are decoded in software must be sampled more
+    /// often than their signals change, or counts will be lost.
+    fn update(&mut self) {}
+
+    /// The distance the shaft has turned since the encoder was created, in counts.
+    /// A positive count indicates that the shaft turned forwards.
+    fn get_count(&self) -> i64;
+
+    /// How many times the encoder was sampled too slowly to tell which way it turned.
+    fn get_missed(&self) -> usize {
+        0
+    }
+}
+
+/// The latest count of an encoder, shared between the monitor which samples it and the
+/// mechanisms which use it.
+pub struct GlobalEncoderState {
+    count: Atomic<i64>,
+    missed: AtomicUsize,
+}
+
+impl GlobalEncoderState {
+    pub fn new() -> Self {
+        Self {
+            count: Atomic::new(0),
+            missed: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get_count(&self) -> i64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn set_count(&self, count: i64) {
+        self.count.store(count, Ordering::Relaxed)
+    }
+
+    pub fn get_missed(&self) -> usize {
+        self.missed.load(Ordering::Relaxed)
+    }
+
+    pub fn set_missed(&self, missed: usize) {
+        self.missed.store(missed, Ordering::Relaxed)
+    }
+
+    pub fn get_current_state(&self) -> EncoderStateInstance {
+        EncoderStateInstance {
+            count: self.get_count(),
+            missed: self.get_missed(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct EncoderStateInstance {
+    count: i64,
+    missed: usize,
+}
+
+impl EncoderStateInstance {
+    pub fn get_count(&self) -> i64 {
+        self.count
+    }
+
+    pub fn get_missed(&self) -> usize {
+        self.missed
+    }
+}
@@ -0,0 +1,114 @@
+use crate::pinouts::digital::input::DigitalInput;
+use crate::pinouts::encoder::Encoder;
+
+/// The change in count for each transition between quadrature states, indexed by the previous
+/// state in the upper two bits and the new state in the lower two. Each state holds channel A in
+/// its upper bit and channel B in its lower bit.
+const TRANSITIONS: [i64; 16] = [0, -1, 1, 0, 1, 0, 0, -1, -1, 0, 0, 1, 0, 1, -1, 0];
+
+/// Decodes a quadrature encoder wired to two digital inputs, counting every edge of both
+/// channels. The count increases when channel A leads channel B.
+pub struct QuadratureEncoder {
+    a: Box<DigitalInput>,
+    b: Box<DigitalInput>,
+    state: usize,
+    count: i64,
+    missed: usize,
+}
+
+impl Encoder for QuadratureEncoder {
+    fn update(&mut self) {
+        let state = self.read_state();
+        if state ^ self.state == 0b11 {
+            // Both channels changed at once, so at least one edge was missed and the direction
+            // can not be known.
+            self.missed += 1;
+        } else {
+            self.count += TRANSITIONS[self.state << 2 | state];
+        }
+        self.state = state;
+    }
+
+    fn get_count(&self) -> i64 {
+        self.count
+    }
+
+    fn get_missed(&self) -> usize {
+        self.missed
+    }
+}
+
+impl QuadratureEncoder {
+    pub fn new(a: Box<DigitalInput>, b: Box<DigitalInput>) -> Self {
+        let mut encoder = Self {
+            a,
+            b,
+            state: 0,
+            count: 0,
+            missed: 0,
+        };
+
+        encoder.state = encoder.read_state();
+        encoder
+    }
+
+    fn read_state(&self) -> usize {
+        (self.a.get_value() as usize) << 1 | self.b.get_value() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+
+    use crate::pinouts::digital::TestPin;
+
+    use super::*;
+
+    fn setup() -> (Arc<AtomicBool>, Arc<AtomicBool>, QuadratureEncoder) {
+        let a = Arc::new(AtomicBool::new(false));
+        let b = Arc::new(AtomicBool::new(false));
+        let encoder = QuadratureEncoder::new(Box::new(TestPin::new(a.clone())), Box::new(TestPin::new(b.clone())));
+
+        (a, b, encoder)
+    }
+
+    fn step(encoder: &mut QuadratureEncoder, a: &AtomicBool, b: &AtomicBool, states: &[(bool, bool)]) {
+        for (a_value, b_value) in states {
+            a.store(*a_value, Ordering::SeqCst);
+            b.store(*b_value, Ordering::SeqCst);
+            encoder.update();
+        }
+    }
+
+    #[test]
+    fn forwards() {
+        let (a, b, mut encoder) = setup();
+
+        step(&mut encoder, &a, &b, &[(true, false), (true, true), (false, true), (false, false)]);
+        assert_eq!(4, encoder.get_count());
+
+        step(&mut encoder, &a, &b, &[(true, false), (true, false)]);
+        assert_eq!(5, encoder.get_count());
+    }
+
+    #[test]
+    fn backwards() {
+        let (a, b, mut encoder) = setup();
+
+        step(&mut encoder, &a, &b, &[(false, true), (true, true), (true, false), (false, false), (false, true)]);
+        assert_eq!(-5, encoder.get_count());
+        assert_eq!(0, encoder.get_missed());
+    }
+
+    #[test]
+    fn missed_edge() {
+        let (a, b, mut encoder) = setup();
+
+        step(&mut encoder, &a, &b, &[(true, true), (true, false)]);
+        assert_eq!(-1, encoder.get_count());
+        assert_eq!(1, encoder.get_missed());
+    }
+}
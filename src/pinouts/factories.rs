@@ -1,35 +1,82 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::pinouts::analog::input::AnalogInput;
+use crate::pinouts::analog::input::libbeaglebone::BeagleBoneAdc;
 use crate::pinouts::analog::output::AnalogOutput;
 use crate::pinouts::analog::output::pwm::LibBeagleBonePwm;
 use crate::pinouts::analog::output::PwmOutput;
 use crate::pinouts::digital::input::DigitalInput;
 use crate::pinouts::digital::libbeaglebone::GpioPinout;
 use crate::pinouts::digital::output::DigitalOutput;
+use crate::robot_map::IIO_ROOT;
+use crate::robot_map::SYSFS_ROOT;
+use crate::status::faults::GlobalFaultState;
 use libbeaglebone::pins::Pin;
 
-pub struct IoFactory {}
+pub struct IoFactory {
+    root: PathBuf,
+    iio_root: PathBuf,
+    faults: Arc<GlobalFaultState>,
+}
 
 impl IoFactory {
-    pub fn new() -> Self {
-        Self {}
+    /// Creates a factory whose pins raise any failure to access them in `faults`.
+    pub fn new(faults: Arc<GlobalFaultState>) -> Self {
+        Self::with_root(SYSFS_ROOT, faults)
+    }
+
+    /// Creates a factory whose pins are controlled through the sysfs tree at `root` rather than
+    /// the real one at `/sys/class`.
+    pub fn with_root<P: AsRef<Path>>(root: P, faults: Arc<GlobalFaultState>) -> Self {
+        Self::with_roots(root, IIO_ROOT, faults)
+    }
+
+    /// Creates a factory which also reads the ADC through the IIO tree at `iio_root` rather than
+    /// the real one at `/sys/bus/iio/devices`.
+    pub fn with_roots<P: AsRef<Path>, Q: AsRef<Path>>(root: P, iio_root: Q, faults: Arc<GlobalFaultState>) -> Self {
+        Self {
+            root: root.as_ref().to_owned(),
+            iio_root: iio_root.as_ref().to_owned(),
+            faults,
+        }
+    }
+
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn get_iio_root(&self) -> &Path {
+        &self.iio_root
+    }
+
+    pub fn get_faults(&self) -> Arc<GlobalFaultState> {
+        self.faults.clone()
     }
 
     pub fn generate_pwm(&self, chip: u8, num: u8) -> Box<PwmOutput> {
-        Box::new(LibBeagleBonePwm::new(chip, num))
+        Box::new(LibBeagleBonePwm::new(&self.root, chip, num, self.faults.clone()))
     }
 
     pub fn generate_analog_output(&self, chip: u8, num: u8) -> Box<AnalogOutput> {
-        Box::new(LibBeagleBonePwm::new(chip, num))
+        Box::new(LibBeagleBonePwm::new(&self.root, chip, num, self.faults.clone()))
+    }
+
+    /// Reads a channel of the ADC, in volts.
+    pub fn generate_analog_input(&self, device: u8, channel: u8) -> Box<AnalogInput> {
+        Box::new(BeagleBoneAdc::new(&self.iio_root, device, channel))
     }
 
     pub fn generate_digital_input(&self, num: Pin) -> Box<DigitalInput> {
-        let mut pin = GpioPinout::new(num);
+        let mut pin = GpioPinout::new(&self.root, num, self.faults.clone());
         pin.set_input_twice();
         Box::new(pin)
     }
 
     pub fn generate_digital_output(&self, num: Pin) -> Box<DigitalOutput> {
-        let mut pin = GpioPinout::new(num);
+        let mut pin = GpioPinout::new(&self.root, num, self.faults.clone());
         pin.set_output_twice();
         Box::new(pin)
     }
-}
\ No newline at end of file
+}
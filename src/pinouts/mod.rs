@@ -7,9 +7,15 @@ pub mod analog;
 /// Contains all code for digital pinout.
 pub mod digital;
 
+/// Contains the encoders which measure how far the motors have turned.
+pub mod encoder;
+
 /// Contains factory functions for producing the pinouts used by the robot.
 pub mod factories;
 
+/// Contains helpers for reading and writing sysfs attributes, and a fake sysfs tree for testing.
+pub mod sysfs;
+
 /// Runs a bash script which will enable the PWM drivers and configure the pins used by the program.
 pub fn enable_pins() -> Result<(), ()> {
     let command_result = Command::new("sh").arg("enable-pwm.sh").output();
@@ -0,0 +1,216 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use libbeaglebone::pins::Pin;
+
+use crate::robot_config::PwmConfig;
+use crate::robot_config::RobotConfig;
+
+static FAKE_SYSFS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes a single value to a sysfs attribute file.
+pub fn write_attribute(path: &Path, value: &str) -> io::Result<()> {
+    fs::write(path, value).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+/// Reads a sysfs attribute file, with surrounding whitespace removed.
+pub fn read_attribute(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map(|value| value.trim().to_owned())
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+pub fn gpio_dir(root: &Path, pin: Pin) -> PathBuf {
+    root.join("gpio").join(format!("gpio{}", pin as u8))
+}
+
+pub fn pwm_chip_dir(root: &Path, chip: u8) -> PathBuf {
+    root.join("pwm").join(format!("pwmchip{}", chip))
+}
+
+pub fn pwm_dir(root: &Path, chip: u8, num: u8) -> PathBuf {
+    pwm_chip_dir(root, chip).join(format!("pwm{}", num))
+}
+
+pub fn adc_path(iio_root: &Path, device: u8, channel: u8) -> PathBuf {
+    iio_root.join(format!("iio:device{}", device)).join(format!("in_voltage{}_raw", channel))
+}
+
+/// A temporary directory laid out like `/sys/class`, containing the `gpio` and `pwm` entries for
+/// every pin in a `RobotConfig`, along with an `iio` directory laid out like `/sys/bus/iio/devices`
+/// containing the ADC channels of the current sensor and any other analog sensors.
+///
+/// The kernel creates the per-pin directories when a pin is exported, so the fake tree creates them
+/// up front instead. The tree is deleted when this is dropped.
+pub struct FakeSysfs {
+    root: PathBuf,
+    iio_root: PathBuf,
+}
+
+impl FakeSysfs {
+    pub fn new(config: &RobotConfig) -> io::Result<Self> {
+        let root = env::temp_dir().join(format!("rmc-sysfs-{}-{}", process::id(),
+                                                FAKE_SYSFS_COUNT.fetch_add(1, Ordering::SeqCst)));
+        let sysfs = Self {
+            iio_root: root.join("iio"),
+            root,
+        };
+
+        let gpio = sysfs.root.join("gpio");
+        fs::create_dir_all(&gpio)?;
+        fs::write(gpio.join("export"), "")?;
+        fs::write(gpio.join("unexport"), "")?;
+
+        let drive = config.get_drive();
+        let intake = config.get_intake();
+        let dumper = config.get_dumper();
+
+        let pwms = [drive.get_front_left_pwm(), drive.get_front_right_pwm(), drive.get_rear_left_pwm(),
+            drive.get_rear_right_pwm(), intake.get_digger_pwm(), intake.get_actuator_pwm(), dumper.get_pwm()];
+        for pwm in pwms.iter() {
+            sysfs.add_pwm(*pwm)?;
+        }
+        if let Some(pwm) = intake.get_right_actuator_pwm() {
+            sysfs.add_pwm(pwm)?;
+        }
+
+        let pins = [drive.get_front_left_direction(), drive.get_front_right_direction(),
+            drive.get_rear_left_direction(), drive.get_rear_right_direction(), intake.get_left_upper_limit(),
+            intake.get_left_lower_limit(), intake.get_right_upper_limit(), intake.get_right_lower_limit(),
+            dumper.get_upper_limit(), dumper.get_lower_limit()];
+        for pin in pins.iter() {
+            sysfs.add_gpio(*pin)?;
+        }
+        for encoder in [drive.get_left_encoder(), drive.get_right_encoder()].iter().flatten() {
+            sysfs.add_gpio(encoder.get_a())?;
+            sysfs.add_gpio(encoder.get_b())?;
+        }
+
+        let current = config.get_current();
+        sysfs.add_adc(current.get_adc_device(), current.get_adc_channel())?;
+        for stall in [intake.get_digger_stall(), intake.get_actuator_stall()].iter() {
+            for channel in [stall.get_adc_channel(), stall.get_right_adc_channel()].iter().flatten() {
+                sysfs.add_adc(stall.get_adc_device(), *channel)?;
+            }
+        }
+        let position = intake.get_actuator_position();
+        for channel in [position.get_adc_channel(), position.get_right_adc_channel()].iter().flatten() {
+            sysfs.add_adc(position.get_adc_device(), *channel)?;
+        }
+
+        Ok(sysfs)
+    }
+
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn get_iio_root(&self) -> &Path {
+        &self.iio_root
+    }
+
+    pub fn add_gpio(&self, pin: Pin) -> io::Result<()> {
+        let dir = gpio_dir(&self.root, pin);
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("direction"), "in")?;
+        fs::write(dir.join("value"), "0")
+    }
+
+    pub fn add_pwm(&self, pwm: PwmConfig) -> io::Result<()> {
+        let chip = pwm_chip_dir(&self.root, pwm.get_chip());
+        fs::create_dir_all(&chip)?;
+        fs::write(chip.join("export"), "")?;
+        fs::write(chip.join("unexport"), "")?;
+
+        let dir = pwm_dir(&self.root, pwm.get_chip(), pwm.get_num());
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("period"), "0")?;
+        fs::write(dir.join("duty_cycle"), "0")?;
+        fs::write(dir.join("enable"), "0")
+    }
+
+    pub fn add_adc(&self, device: u8, channel: u8) -> io::Result<()> {
+        let path = adc_path(&self.iio_root, device, channel);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, "0")
+    }
+
+    /// Sets the raw reading of an ADC channel, as a sensor wired to it would.
+    pub fn set_adc(&self, device: u8, channel: u8, raw: u16) -> io::Result<()> {
+        write_attribute(&adc_path(&self.iio_root, device, channel), &raw.to_string())
+    }
+
+    /// Drives a GPIO pin from the outside, as a limit switch would.
+    pub fn set_gpio(&self, pin: Pin, value: bool) -> io::Result<()> {
+        write_attribute(&gpio_dir(&self.root, pin).join("value"), if value { "1" } else { "0" })
+    }
+
+    pub fn get_gpio(&self, pin: Pin) -> io::Result<bool> {
+        read_attribute(&gpio_dir(&self.root, pin).join("value")).map(|value| value == "1")
+    }
+
+    pub fn get_direction(&self, pin: Pin) -> io::Result<String> {
+        read_attribute(&gpio_dir(&self.root, pin).join("direction"))
+    }
+
+    pub fn get_duty_cycle(&self, pwm: PwmConfig) -> io::Result<u32> {
+        self.read_pwm(pwm, "duty_cycle")
+    }
+
+    pub fn get_period(&self, pwm: PwmConfig) -> io::Result<u32> {
+        self.read_pwm(pwm, "period")
+    }
+
+    pub fn is_enabled(&self, pwm: PwmConfig) -> io::Result<bool> {
+        self.read_pwm(pwm, "enable").map(|value| value == 1)
+    }
+
+    fn read_pwm(&self, pwm: PwmConfig, attribute: &str) -> io::Result<u32> {
+        let value = read_attribute(&pwm_dir(&self.root, pwm.get_chip(), pwm.get_num()).join(attribute))?;
+        value.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData,
+                                                 format!("{} is not a number: {}", attribute, value)))
+    }
+}
+
+impl Drop for FakeSysfs {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_tree() {
+        let config = RobotConfig::default();
+        let sysfs = FakeSysfs::new(&config).unwrap();
+        let pin = config.get_drive().get_front_left_direction();
+
+        assert_eq!("in", sysfs.get_direction(pin).unwrap());
+        assert_eq!(false, sysfs.get_gpio(pin).unwrap());
+        assert_eq!(0, sysfs.get_duty_cycle(config.get_dumper().get_pwm()).unwrap());
+
+        sysfs.set_gpio(pin, true).unwrap();
+        assert_eq!(true, sysfs.get_gpio(pin).unwrap());
+    }
+
+    #[test]
+    fn removed_on_drop() {
+        let sysfs = FakeSysfs::new(&RobotConfig::default()).unwrap();
+        let root = sysfs.get_root().to_owned();
+        assert_eq!(true, root.exists());
+
+        drop(sysfs);
+        assert_eq!(false, root.exists());
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::mechatronics::CommandSource;
+use crate::mechatronics::commands::CommandRecord;
+use crate::recording::unix_millis;
+
+/// A single command handled by the controller.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct JournalEntry {
+    unix_millis: u64,
+    run_millis: u64,
+    source: CommandSource,
+    command: CommandRecord,
+}
+
+impl JournalEntry {
+    pub fn new(unix_millis: u64, run_millis: u64, source: CommandSource, command: CommandRecord) -> Self {
+        Self {
+            unix_millis,
+            run_millis,
+            source,
+            command,
+        }
+    }
+
+    /// Returns how long after the journal was started this command was handled.
+    pub fn get_run_millis(&self) -> u64 {
+        self.run_millis
+    }
+
+    pub fn get_source(&self) -> CommandSource {
+        self.source
+    }
+
+    pub fn get_command(&self) -> CommandRecord {
+        self.command
+    }
+}
+
+/// Appends every command handled by the controller to a JSON-lines file.
+pub struct CommandJournal {
+    path: PathBuf,
+    out: BufWriter<File>,
+    start: Instant,
+}
+
+impl CommandJournal {
+    /// Creates a new journal file named after the current time in the folder at `path`.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        fs::create_dir_all(path)?;
+        let name = format!("commands-{}", unix_millis());
+        let mut file = path.join(format!("{}.jsonl", name));
+        let mut suffix = 1;
+        while file.exists() {
+            file = path.join(format!("{}-{}.jsonl", name, suffix));
+            suffix += 1;
+        }
+
+        Ok(Self {
+            out: BufWriter::new(File::create(&file)?),
+            path: file,
+            start: Instant::now(),
+        })
+    }
+
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&mut self, command: CommandRecord, source: CommandSource) -> io::Result<()> {
+        let elapsed = self.start.elapsed();
+        let run_millis = elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_millis());
+        let entry = JournalEntry::new(unix_millis(), run_millis, source, command);
+
+        serde_json::to_writer(&mut self.out, &entry)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+}
+
+/// Reads every entry of the journal file at `path`.
+pub fn read_journal<P: AsRef<Path>>(path: P) -> io::Result<Vec<JournalEntry>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            entries.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(entries)
+}
@@ -0,0 +1,208 @@
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::framework::Runnable;
+use crate::status::robot_state::GlobalRobotState;
+use crate::status::timing::GlobalLoopState;
+
+/// Contains the journal which records every command handled by the controller.
+pub mod journal;
+
+/// Contains the replayer which feeds a recorded journal back into a robot.
+pub mod replay;
+
+#[cfg(test)]
+mod tests;
+
+/// Records the robot state to disk for the length of a run.
+///
+/// Every launch gets its own directory under the configured path, named after the time the robot
+/// was launched, so that earlier runs are never overwritten.
+pub struct StateRecorder {
+    state: Arc<GlobalRobotState>,
+    path: PathBuf,
+    period: Duration,
+    csv: bool,
+}
+
+impl StateRecorder {
+    pub fn new(state: Arc<GlobalRobotState>, path: PathBuf, period: Duration, csv: bool) -> Self {
+        Self {
+            state,
+            path,
+            period,
+            csv,
+        }
+    }
+
+    /// Creates the run directory and starts recording, taking over the current thread.
+    pub fn launch(self) {
+        let mut recorder = match RunRecorder::create(self.state, &self.path, self.csv) {
+            Ok(recorder) => recorder,
+            Err(e) => {
+                error!("Failed to start recording in {}: {}", self.path.display(), e);
+                return;
+            }
+        };
+
+        info!("Recording run to {}", recorder.get_directory().display());
+        recorder.start_fixed_rate(self.period, Arc::new(GlobalLoopState::new()));
+    }
+}
+
+/// Writes one sample of the robot state per cycle to the files of a single run.
+pub struct RunRecorder {
+    state: Arc<GlobalRobotState>,
+    directory: PathBuf,
+    jsonl: BufWriter<File>,
+    csv: Option<CsvWriter>,
+    start: Instant,
+}
+
+impl Runnable for RunRecorder {
+    fn init(&mut self) {
+        self.start = Instant::now();
+    }
+
+    fn run(&mut self) {
+        if let Err(e) = self.record() {
+            error!("Failed to record robot state: {}", e);
+        }
+    }
+}
+
+impl RunRecorder {
+    /// Creates a new run directory under `path`, containing `state.jsonl` and, if `csv` is set,
+    /// `state.csv`.
+    pub fn create(state: Arc<GlobalRobotState>, path: &Path, csv: bool) -> io::Result<Self> {
+        let directory = create_run_directory(path)?;
+        let jsonl = BufWriter::new(File::create(directory.join("state.jsonl"))?);
+        let csv = if csv {
+            Some(CsvWriter::new(BufWriter::new(File::create(directory.join("state.csv"))?)))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            state,
+            directory,
+            jsonl,
+            csv,
+            start: Instant::now(),
+        })
+    }
+
+    pub fn get_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Samples the robot state and appends it to the run's files.
+    pub fn record(&mut self) -> io::Result<()> {
+        let elapsed = self.start.elapsed();
+        let mut sample = Map::new();
+        sample.insert("unix_millis".to_owned(), Value::from(unix_millis()));
+        sample.insert("run_millis".to_owned(),
+                      Value::from(elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_millis())));
+        sample.insert("state".to_owned(), serde_json::to_value(self.state.get_current_state())?);
+        let sample = Value::Object(sample);
+
+        serde_json::to_writer(&mut self.jsonl, &sample)?;
+        self.jsonl.write_all(b"\n")?;
+        self.jsonl.flush()?;
+
+        if let Some(csv) = &mut self.csv {
+            csv.write_sample(&sample)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes samples as CSV rows, with one column per leaf of the flattened sample.
+///
+/// The columns are fixed by the first sample written.
+struct CsvWriter {
+    out: BufWriter<File>,
+    columns: Option<Vec<String>>,
+}
+
+impl CsvWriter {
+    fn new(out: BufWriter<File>) -> Self {
+        Self {
+            out,
+            columns: None,
+        }
+    }
+
+    fn write_sample(&mut self, sample: &Value) -> io::Result<()> {
+        let mut fields = Vec::new();
+        flatten("", sample, &mut fields);
+
+        if self.columns.is_none() {
+            let columns: Vec<String> = fields.iter().map(|(name, _)| name.clone()).collect();
+            writeln!(self.out, "{}", columns.join(","))?;
+            self.columns = Some(columns);
+        }
+
+        if let Some(columns) = &self.columns {
+            let row: Vec<String> = columns.iter()
+                .map(|column| fields.iter()
+                    .find(|(name, _)| name == column)
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default())
+                .collect();
+            writeln!(self.out, "{}", row.join(","))?;
+        }
+
+        self.out.flush()
+    }
+}
+
+/// Flattens a JSON value into `(name, value)` pairs, joining nested names with dots.
+fn flatten(prefix: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&name, value, fields);
+            }
+        }
+        Value::Null => fields.push((prefix.to_owned(), String::new())),
+        Value::String(text) => fields.push((prefix.to_owned(), text.replace(',', ";"))),
+        other => fields.push((prefix.to_owned(), other.to_string())),
+    }
+}
+
+fn create_run_directory(path: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(path)?;
+    let name = format!("run-{}", unix_millis());
+    let mut directory = path.join(&name);
+    let mut suffix = 1;
+    while directory.exists() {
+        directory = path.join(format!("{}-{}", name, suffix));
+        suffix += 1;
+    }
+    fs::create_dir(&directory)?;
+    Ok(directory)
+}
+
+/// Returns the number of milliseconds since the Unix epoch.
+pub fn unix_millis() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(time) => time.as_secs() * 1000 + u64::from(time.subsec_millis()),
+        Err(_) => 0,
+    }
+}
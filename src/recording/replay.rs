@@ -0,0 +1,59 @@
+use std::thread::sleep;
+use std::time::Duration;
+
+use crate::mechatronics::commands::RobotCommandFactory;
+use crate::mechatronics::CommandSource;
+use crate::mechatronics::RobotMessenger;
+use crate::recording::journal::JournalEntry;
+
+/// Controls how quickly a journal is replayed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ReplayTiming {
+    /// Waits between commands for as long as passed between them when they were recorded.
+    Original,
+    /// Sends every command as soon as the controller can accept it.
+    AsFastAsPossible,
+}
+
+/// Feeds the commands of a recorded journal back into a robot.
+pub struct CommandReplayer {
+    entries: Vec<JournalEntry>,
+    timing: ReplayTiming,
+    factory: RobotCommandFactory,
+}
+
+impl CommandReplayer {
+    pub fn new(entries: Vec<JournalEntry>, timing: ReplayTiming) -> Self {
+        Self {
+            entries,
+            timing,
+            factory: RobotCommandFactory::new(),
+        }
+    }
+
+    /// Sends every journaled command through `messenger`, blocking until the last one is sent.
+    /// Commands issued by routines are skipped, since replaying the command which started the
+    /// routine issues them again. Returns the number of commands which were replayed.
+    pub fn replay(&self, messenger: &RobotMessenger) -> usize {
+        let mut replayed = 0;
+        let mut last_millis = self.entries.first().map(|entry| entry.get_run_millis()).unwrap_or(0);
+
+        for entry in self.entries.iter().filter(|entry| entry.get_source() != CommandSource::Routine) {
+            if self.timing == ReplayTiming::Original {
+                let millis = entry.get_run_millis();
+                sleep(Duration::from_millis(millis.saturating_sub(last_millis)));
+                last_millis = millis;
+            }
+
+            match self.factory.from_record(entry.get_command()) {
+                Some(command) => {
+                    messenger.send_command(command);
+                    replayed += 1;
+                }
+                None => warn!("Skipping invalid journaled command {:?}", entry.get_command()),
+            }
+        }
+
+        replayed
+    }
+}
@@ -0,0 +1,81 @@
+use std::env;
+use std::process;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use super::*;
+
+static TEST_DIRECTORY_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+struct TestDirectory {
+    path: PathBuf,
+}
+
+impl TestDirectory {
+    fn new() -> Self {
+        let path = env::temp_dir().join(format!("rmc-recording-{}-{}", process::id(),
+                                                TEST_DIRECTORY_COUNT.fetch_add(1, Ordering::SeqCst)));
+        Self {
+            path,
+        }
+    }
+}
+
+impl Drop for TestDirectory {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[test]
+fn writes_jsonl() {
+    let directory = TestDirectory::new();
+    let state = Arc::new(GlobalRobotState::new());
+    let mut recorder = RunRecorder::create(state.clone(), &directory.path, false).unwrap();
+
+    recorder.record().unwrap();
+    state.get_life().kill();
+    recorder.record().unwrap();
+
+    let text = fs::read_to_string(recorder.get_directory().join("state.jsonl")).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(2, lines.len());
+
+    let first: Value = serde_json::from_str(lines[0]).unwrap();
+    let second: Value = serde_json::from_str(lines[1]).unwrap();
+    assert_eq!(Value::Bool(true), first["state"]["life"]["life"]);
+    assert_eq!(Value::Bool(false), second["state"]["life"]["life"]);
+    assert!(first["unix_millis"].is_u64());
+    assert_eq!(false, recorder.get_directory().join("state.csv").exists());
+}
+
+#[test]
+fn writes_csv() {
+    let directory = TestDirectory::new();
+    let state = Arc::new(GlobalRobotState::new());
+    let mut recorder = RunRecorder::create(state.clone(), &directory.path, true).unwrap();
+
+    recorder.record().unwrap();
+    state.get_drive().get_left().set_speed(0.5);
+    recorder.record().unwrap();
+
+    let text = fs::read_to_string(recorder.get_directory().join("state.csv")).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(3, lines.len());
+
+    let columns: Vec<&str> = lines[0].split(',').collect();
+    let column = columns.iter().position(|name| *name == "state.drive.left.speed").unwrap();
+    assert_eq!("0.0", lines[1].split(',').nth(column).unwrap());
+    assert_eq!("0.5", lines[2].split(',').nth(column).unwrap());
+}
+
+#[test]
+fn new_directory_per_run() {
+    let directory = TestDirectory::new();
+    let state = Arc::new(GlobalRobotState::new());
+    let first = RunRecorder::create(state.clone(), &directory.path, false).unwrap();
+    let second = RunRecorder::create(state, &directory.path, false).unwrap();
+
+    assert_ne!(first.get_directory(), second.get_directory());
+    assert_eq!(2, fs::read_dir(&directory.path).unwrap().count());
+}
@@ -0,0 +1,2043 @@
+use std::collections::HashSet;
+use std::f32::consts::PI;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use libbeaglebone::pins::Pin;
+use serde::de::Error as DeError;
+use serde::Deserialize;
+use serde::Deserializer;
+use slog::Level;
+
+use crate::mechatronics::load_shedding::Load;
+use crate::robot_map::*;
+
+/// The runtime configuration of the robot, loaded from a TOML file at startup.
+///
+/// Every field is optional in the file; anything left out falls back to the constants in the
+/// robot map.
+#[derive(Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct RobotConfig {
+    drive: DriveConfig,
+    intake: IntakeConfig,
+    dumper: DumperConfig,
+    limits: LimitsConfig,
+    current: CurrentConfig,
+    controller: ControllerConfig,
+    shaping: ShapingConfig,
+    telemetry: TelemetryConfig,
+    recorder: RecorderConfig,
+    journal: JournalConfig,
+    logging: LoggingConfig,
+}
+
+/// Identifies a single PWM output by its chip and channel number.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct PwmConfig {
+    chip: u8,
+    num: u8,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct DriveConfig {
+    front_left_pwm: PwmConfig,
+    front_right_pwm: PwmConfig,
+    rear_left_pwm: PwmConfig,
+    rear_right_pwm: PwmConfig,
+    #[serde(deserialize_with = "deserialize_pin")]
+    front_left_direction: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    front_right_direction: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    rear_left_direction: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    rear_right_direction: Pin,
+    acceleration: f32,
+    deceleration: f32,
+    left_encoder: Option<EncoderConfig>,
+    right_encoder: Option<EncoderConfig>,
+    speed_control: SpeedControlConfig,
+    odometry: OdometryConfig,
+}
+
+/// The pins a quadrature encoder's two channels are wired to.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+pub struct EncoderConfig {
+    #[serde(deserialize_with = "deserialize_pin")]
+    a: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    b: Pin,
+}
+
+/// The geometry used to work out how far the robot has moved from its drive encoders.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct OdometryConfig {
+    wheel_radius: f32,
+    track_width: f32,
+    counts_per_revolution: f32,
+}
+
+/// The gains used to hold a motor at the speed it is commanded, measured by its encoder.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpeedControlConfig {
+    kp: f32,
+    ki: f32,
+    kd: f32,
+    max_velocity: f32,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct IntakeConfig {
+    digger_pwm: PwmConfig,
+    actuator_pwm: PwmConfig,
+    right_actuator_pwm: Option<PwmConfig>,
+    #[serde(deserialize_with = "deserialize_pin")]
+    left_upper_limit: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    left_lower_limit: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    right_upper_limit: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    right_lower_limit: Pin,
+    digging_rate: f32,
+    actuator_rate: f32,
+    tilt_tolerance: f32,
+    tilt_scale: f32,
+    digger_stall: StallConfig,
+    actuator_stall: StallConfig,
+    actuator_position: ActuatorPositionConfig,
+    limit_check: LimitCheckConfig,
+    actuator_timeout_millis: u64,
+}
+
+/// How to estimate how far the intake actuators have travelled between their limits.
+///
+/// The position is read from a potentiometer on `adc_channel` when there is one. Otherwise it is
+/// worked out from how long the actuators have run, calibrated by `stroke_millis`. When each
+/// actuator has its own motor, `adc_channel` belongs to the left one and `right_adc_channel` to the
+/// right one.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ActuatorPositionConfig {
+    stroke_millis: u64,
+    tolerance: f32,
+    adc_device: u8,
+    adc_channel: Option<u8>,
+    right_adc_channel: Option<u8>,
+    upper_volts: f32,
+    lower_volts: f32,
+}
+
+/// How to measure the current drawn by a motor and when to consider it stalled.
+///
+/// When each intake actuator has its own motor, the actuator stall's `adc_channel` belongs to the
+/// left one and `right_adc_channel` to the right one.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct StallConfig {
+    current: f32,
+    time_millis: u64,
+    adc_device: u8,
+    adc_channel: Option<u8>,
+    right_adc_channel: Option<u8>,
+    zero_volts: f32,
+    amps_per_volt: f32,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct DumperConfig {
+    pwm: PwmConfig,
+    #[serde(deserialize_with = "deserialize_pin")]
+    upper_limit: Pin,
+    #[serde(deserialize_with = "deserialize_pin")]
+    lower_limit: Pin,
+    dumping_rate: f32,
+    reset_rate: f32,
+    cycle_dwell_millis: u64,
+    limit_check: LimitCheckConfig,
+    timeout_millis: u64,
+}
+
+/// How quickly a mechanism's limit switches must clear and how soon they may be reached, beyond
+/// which the switches are not believed and the mechanism is stopped.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitCheckConfig {
+    clear_millis: u64,
+    min_travel_millis: u64,
+}
+
+/// How the reading of each limit switch is debounced.
+#[derive(Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    intake_left_upper: DebounceConfig,
+    intake_left_lower: DebounceConfig,
+    intake_right_upper: DebounceConfig,
+    intake_right_lower: DebounceConfig,
+    dumper_upper: DebounceConfig,
+    dumper_lower: DebounceConfig,
+}
+
+/// A new reading from a digital input is accepted once it has lasted for `samples` consecutive reads
+/// or for `millis` milliseconds, whichever comes first. Either rule is turned off by setting it to 0.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct DebounceConfig {
+    samples: u32,
+    millis: u64,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct CurrentConfig {
+    brownout: f32,
+    critical: f32,
+    kill_on_critical: bool,
+    adc_device: u8,
+    adc_channel: u8,
+    zero_volts: f32,
+    amps_per_volt: f32,
+    shed_order: Vec<Load>,
+    shed_scale: f32,
+    shed_interval_millis: u64,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ControllerConfig {
+    watchdog_timeout_millis: u64,
+    /// Zero runs the controller as fast as possible.
+    loop_period_micros: u64,
+    /// Zero runs the monitor as fast as possible.
+    monitor_period_micros: u64,
+    kill_on_critical_fault: bool,
+}
+
+/// How driver inputs are shaped in each mode, and how far precision mode slows the robot.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShapingConfig {
+    driving: ShapingProfile,
+    digging: SpeedLimit,
+    dumping: SpeedLimit,
+    precision_scale: f32,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShapingProfile {
+    deadband: f32,
+    expo: f32,
+    max_speed: f32,
+}
+
+/// The fastest a mechanism may move, for mechanisms whose inputs are commands rather than stick
+/// positions and so are not shaped.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpeedLimit {
+    max_speed: f32,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryConfig {
+    enabled: bool,
+    port: u16,
+    period_millis: u64,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct RecorderConfig {
+    enabled: bool,
+    path: String,
+    period_millis: u64,
+    csv: bool,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct JournalConfig {
+    enabled: bool,
+    path: String,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    path: String,
+    #[serde(deserialize_with = "deserialize_level")]
+    level: Level,
+}
+
+/// Describes why a configuration file could not be used.
+pub enum ConfigError {
+    Io(String, io::Error),
+    Parse(toml::de::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "could not read {}: {}", path, e),
+            ConfigError::Parse(e) => write!(f, "could not parse the configuration: {}", e),
+            ConfigError::Invalid(reason) => write!(f, "invalid configuration: {}", reason),
+        }
+    }
+}
+
+impl fmt::Debug for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl RobotConfig {
+    /// Loads and validates the configuration file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| ConfigError::Io(path.display().to_string(), e))?;
+        Self::from_toml(&text)
+    }
+
+    /// Loads the configuration file at `path`, falling back to the defaults if it does not exist.
+    pub fn load_or_default<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Parses and validates a configuration from TOML text.
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        let config: RobotConfig = toml::from_str(text).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the configuration describes a robot we can actually run.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.drive.validate()?;
+        self.intake.validate()?;
+        self.dumper.validate()?;
+        self.limits.validate()?;
+        self.current.validate()?;
+        self.controller.validate()?;
+        self.shaping.validate()?;
+        self.telemetry.validate()?;
+        self.recorder.validate()?;
+
+        self.check_pwms()?;
+        self.check_pins()?;
+        self.check_adc_channels()
+    }
+
+    /// Checks that every motor has a PWM channel of its own.
+    fn check_pwms(&self) -> Result<(), ConfigError> {
+        let mut pwms = vec![
+            ("drive.front_left_pwm", self.drive.front_left_pwm),
+            ("drive.front_right_pwm", self.drive.front_right_pwm),
+            ("drive.rear_left_pwm", self.drive.rear_left_pwm),
+            ("drive.rear_right_pwm", self.drive.rear_right_pwm),
+            ("intake.digger_pwm", self.intake.digger_pwm),
+            ("intake.actuator_pwm", self.intake.actuator_pwm),
+            ("dumper.pwm", self.dumper.pwm),
+        ];
+        if let Some(pwm) = self.intake.right_actuator_pwm {
+            pwms.push(("intake.right_actuator_pwm", pwm));
+        }
+        let mut used_pwms = HashSet::new();
+        for (name, pwm) in pwms.iter() {
+            if pwm.num > 1 {
+                return Err(ConfigError::Invalid(format!(
+                    "{} uses channel {}, but each PWM chip only has channels 0 and 1", name, pwm.num)));
+            }
+            if !used_pwms.insert(*pwm) {
+                return Err(ConfigError::Invalid(format!(
+                    "{} uses PWM chip {} channel {}, which is already assigned", name, pwm.chip, pwm.num)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that no two direction outputs, limit switches or encoders share a pin.
+    fn check_pins(&self) -> Result<(), ConfigError> {
+        let mut pins = vec![
+            ("drive.front_left_direction", self.drive.front_left_direction),
+            ("drive.front_right_direction", self.drive.front_right_direction),
+            ("drive.rear_left_direction", self.drive.rear_left_direction),
+            ("drive.rear_right_direction", self.drive.rear_right_direction),
+            ("intake.left_upper_limit", self.intake.left_upper_limit),
+            ("intake.left_lower_limit", self.intake.left_lower_limit),
+            ("intake.right_upper_limit", self.intake.right_upper_limit),
+            ("intake.right_lower_limit", self.intake.right_lower_limit),
+            ("dumper.upper_limit", self.dumper.upper_limit),
+            ("dumper.lower_limit", self.dumper.lower_limit),
+        ];
+        if let Some(encoder) = self.drive.left_encoder {
+            pins.push(("drive.left_encoder.a", encoder.a));
+            pins.push(("drive.left_encoder.b", encoder.b));
+        }
+        if let Some(encoder) = self.drive.right_encoder {
+            pins.push(("drive.right_encoder.a", encoder.a));
+            pins.push(("drive.right_encoder.b", encoder.b));
+        }
+        let mut used_pins = HashSet::new();
+        for (name, pin) in pins.iter() {
+            if !used_pins.insert(*pin as u8) {
+                return Err(ConfigError::Invalid(format!("{} uses {:?}, which is already assigned", name, pin)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the current sensor, the stall sensors and the potentiometers each have an ADC
+    /// channel of their own.
+    fn check_adc_channels(&self) -> Result<(), ConfigError> {
+        let current = &self.current;
+        let digger_stall = &self.intake.digger_stall;
+        let actuator_stall = &self.intake.actuator_stall;
+        let position = &self.intake.actuator_position;
+        let channels = [
+            ("current.adc_channel", current.adc_device, Some(current.adc_channel)),
+            ("intake.digger_stall.adc_channel", digger_stall.adc_device, digger_stall.adc_channel),
+            ("intake.actuator_stall.adc_channel", actuator_stall.adc_device, actuator_stall.adc_channel),
+            ("intake.actuator_stall.right_adc_channel", actuator_stall.adc_device, actuator_stall.right_adc_channel),
+            ("intake.actuator_position.adc_channel", position.adc_device, position.adc_channel),
+            ("intake.actuator_position.right_adc_channel", position.adc_device, position.right_adc_channel),
+        ];
+        let mut used_channels = HashSet::new();
+        for (name, device, channel) in channels.iter() {
+            if let Some(channel) = channel {
+                if !used_channels.insert((*device, *channel)) {
+                    return Err(ConfigError::Invalid(format!(
+                        "{} uses ADC {} channel {}, which is already assigned", name, device, channel)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_drive(&self) -> &DriveConfig {
+        &self.drive
+    }
+
+    pub fn get_intake(&self) -> &IntakeConfig {
+        &self.intake
+    }
+
+    pub fn get_dumper(&self) -> &DumperConfig {
+        &self.dumper
+    }
+
+    pub fn get_limits(&self) -> &LimitsConfig {
+        &self.limits
+    }
+
+    pub fn get_current(&self) -> &CurrentConfig {
+        &self.current
+    }
+
+    pub fn get_controller(&self) -> &ControllerConfig {
+        &self.controller
+    }
+
+    pub fn get_telemetry(&self) -> &TelemetryConfig {
+        &self.telemetry
+    }
+
+    pub fn get_recorder(&self) -> &RecorderConfig {
+        &self.recorder
+    }
+
+    pub fn get_journal(&self) -> &JournalConfig {
+        &self.journal
+    }
+
+    pub fn get_shaping(&self) -> &ShapingConfig {
+        &self.shaping
+    }
+
+    pub fn get_logging(&self) -> &LoggingConfig {
+        &self.logging
+    }
+}
+
+#[cfg(test)]
+impl RobotConfig {
+    pub fn with_limits(mut self, limits: LimitsConfig) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_current(mut self, current: CurrentConfig) -> Self {
+        self.current = current;
+        self
+    }
+
+    pub fn with_controller(mut self, controller: ControllerConfig) -> Self {
+        self.controller = controller;
+        self
+    }
+}
+
+impl PwmConfig {
+    pub fn new(chip: u8, num: u8) -> Self {
+        Self {
+            chip,
+            num,
+        }
+    }
+
+    pub fn get_chip(&self) -> u8 {
+        self.chip
+    }
+
+    pub fn get_num(&self) -> u8 {
+        self.num
+    }
+}
+
+impl DriveConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.left_encoder.is_some() != self.right_encoder.is_some() {
+            return Err(ConfigError::Invalid(
+                "drive.left_encoder and drive.right_encoder must be configured together".to_owned()));
+        }
+        self.speed_control.validate()?;
+        self.odometry.validate()?;
+
+        if self.acceleration <= 0.0 || self.deceleration <= 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "drive.acceleration and drive.deceleration must be positive, got {} and {}",
+                self.acceleration, self.deceleration)));
+        }
+        Ok(())
+    }
+
+    pub fn get_front_left_pwm(&self) -> PwmConfig {
+        self.front_left_pwm
+    }
+
+    pub fn get_front_right_pwm(&self) -> PwmConfig {
+        self.front_right_pwm
+    }
+
+    pub fn get_rear_left_pwm(&self) -> PwmConfig {
+        self.rear_left_pwm
+    }
+
+    pub fn get_rear_right_pwm(&self) -> PwmConfig {
+        self.rear_right_pwm
+    }
+
+    pub fn get_front_left_direction(&self) -> Pin {
+        self.front_left_direction
+    }
+
+    pub fn get_front_right_direction(&self) -> Pin {
+        self.front_right_direction
+    }
+
+    pub fn get_rear_left_direction(&self) -> Pin {
+        self.rear_left_direction
+    }
+
+    pub fn get_rear_right_direction(&self) -> Pin {
+        self.rear_right_direction
+    }
+
+    /// How quickly the drive motors may speed up, as a fraction of full speed per second.
+    pub fn get_acceleration(&self) -> f32 {
+        self.acceleration
+    }
+
+    /// How quickly the drive motors may slow down, as a fraction of full speed per second.
+    pub fn get_deceleration(&self) -> f32 {
+        self.deceleration
+    }
+
+    pub fn get_left_encoder(&self) -> Option<EncoderConfig> {
+        self.left_encoder
+    }
+
+    pub fn get_right_encoder(&self) -> Option<EncoderConfig> {
+        self.right_encoder
+    }
+
+    pub fn get_speed_control(&self) -> &SpeedControlConfig {
+        &self.speed_control
+    }
+
+    pub fn get_odometry(&self) -> &OdometryConfig {
+        &self.odometry
+    }
+}
+
+impl OdometryConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.wheel_radius <= 0.0 || self.track_width <= 0.0 || self.counts_per_revolution <= 0.0 {
+            return Err(ConfigError::Invalid(
+                "drive.odometry wheel_radius, track_width and counts_per_revolution must be positive".to_owned()));
+        }
+        Ok(())
+    }
+
+    /// The radius of the drive wheels, in meters.
+    pub fn get_wheel_radius(&self) -> f32 {
+        self.wheel_radius
+    }
+
+    /// The distance between the left and right wheels, in meters.
+    pub fn get_track_width(&self) -> f32 {
+        self.track_width
+    }
+
+    /// The number of encoder counts in one turn of a drive wheel.
+    pub fn get_counts_per_revolution(&self) -> f32 {
+        self.counts_per_revolution
+    }
+
+    /// How far the robot travels on a wheel for each encoder count, in meters.
+    pub fn get_meters_per_count(&self) -> f32 {
+        2.0 * PI * self.wheel_radius / self.counts_per_revolution
+    }
+}
+
+impl Default for OdometryConfig {
+    fn default() -> Self {
+        Self {
+            wheel_radius: WHEEL_RADIUS,
+            track_width: TRACK_WIDTH,
+            counts_per_revolution: DRIVE_COUNTS_PER_REVOLUTION,
+        }
+    }
+}
+
+#[cfg(test)]
+impl OdometryConfig {
+    pub fn with_wheel_radius(mut self, wheel_radius: f32) -> Self {
+        self.wheel_radius = wheel_radius;
+        self
+    }
+
+    pub fn with_track_width(mut self, track_width: f32) -> Self {
+        self.track_width = track_width;
+        self
+    }
+
+    pub fn with_counts_per_revolution(mut self, counts_per_revolution: f32) -> Self {
+        self.counts_per_revolution = counts_per_revolution;
+        self
+    }
+}
+
+impl EncoderConfig {
+    pub fn get_a(&self) -> Pin {
+        self.a
+    }
+
+    pub fn get_b(&self) -> Pin {
+        self.b
+    }
+}
+
+impl SpeedControlConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.kp < 0.0 || self.ki < 0.0 || self.kd < 0.0 {
+            return Err(ConfigError::Invalid("drive.speed_control gains must not be negative".to_owned()));
+        }
+        if self.max_velocity <= 0.0 {
+            return Err(ConfigError::Invalid(format!(
+                "drive.speed_control.max_velocity must be positive, got {}", self.max_velocity)));
+        }
+        Ok(())
+    }
+
+    /// The gains act on the speed error as a fraction of `max_velocity`, so a proportional gain
+    /// of 1 adds full output for an error of full speed.
+    pub fn get_kp(&self) -> f32 {
+        self.kp
+    }
+
+    pub fn get_ki(&self) -> f32 {
+        self.ki
+    }
+
+    pub fn get_kd(&self) -> f32 {
+        self.kd
+    }
+
+    /// The speed in encoder counts per second which a command of full speed asks for.
+    pub fn get_max_velocity(&self) -> f32 {
+        self.max_velocity
+    }
+}
+
+impl Default for SpeedControlConfig {
+    fn default() -> Self {
+        Self {
+            kp: SPEED_CONTROL_KP,
+            ki: SPEED_CONTROL_KI,
+            kd: SPEED_CONTROL_KD,
+            max_velocity: DRIVE_MAX_VELOCITY,
+        }
+    }
+}
+
+#[cfg(test)]
+impl SpeedControlConfig {
+    pub fn with_kp(mut self, kp: f32) -> Self {
+        self.kp = kp;
+        self
+    }
+
+    pub fn with_ki(mut self, ki: f32) -> Self {
+        self.ki = ki;
+        self
+    }
+
+    pub fn with_kd(mut self, kd: f32) -> Self {
+        self.kd = kd;
+        self
+    }
+
+    pub fn with_max_velocity(mut self, max_velocity: f32) -> Self {
+        self.max_velocity = max_velocity;
+        self
+    }
+}
+
+impl Default for DriveConfig {
+    fn default() -> Self {
+        Self {
+            front_left_pwm: PwmConfig::new(FRONT_LEFT_PWM_CHIP, FRONT_LEFT_PWM_NUMBER),
+            front_right_pwm: PwmConfig::new(FRONT_RIGHT_PWM_CHIP, FRONT_RIGHT_PWM_NUMBER),
+            rear_left_pwm: PwmConfig::new(REAR_LEFT_PWM_CHIP, REAR_LEFT_PWM_NUMBER),
+            rear_right_pwm: PwmConfig::new(REAR_RIGHT_PWM_CHIP, REAR_RIGHT_PWM_NUMBER),
+            front_left_direction: FRONT_LEFT_DIRECTION,
+            front_right_direction: FRONT_RIGHT_DIRECTION,
+            rear_left_direction: REAR_LEFT_DIRECTION,
+            rear_right_direction: REAR_RIGHT_DIRECTION,
+            acceleration: DRIVE_ACCELERATION,
+            deceleration: DRIVE_DECELERATION,
+            left_encoder: None,
+            right_encoder: None,
+            speed_control: SpeedControlConfig::default(),
+            odometry: OdometryConfig::default(),
+        }
+    }
+}
+
+impl IntakeConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        check_rate("intake.digging_rate", self.digging_rate)?;
+        check_rate("intake.actuator_rate", self.actuator_rate)?;
+        self.limit_check.validate("intake.limit_check")?;
+        if self.actuator_timeout_millis == 0 {
+            return Err(ConfigError::Invalid("intake.actuator_timeout_millis must be positive".to_owned()));
+        }
+
+        self.digger_stall.validate("intake.digger_stall")?;
+        self.actuator_stall.validate("intake.actuator_stall")?;
+        if self.digger_stall.right_adc_channel.is_some() {
+            return Err(ConfigError::Invalid("intake.digger_stall.right_adc_channel is only for the actuators".to_owned()));
+        }
+        if self.actuator_stall.right_adc_channel.is_some() && self.right_actuator_pwm.is_none() {
+            return Err(ConfigError::Invalid(
+                "intake.actuator_stall.right_adc_channel needs intake.right_actuator_pwm".to_owned()));
+        }
+
+        self.actuator_position.validate()?;
+        if self.actuator_position.right_adc_channel.is_some() && self.right_actuator_pwm.is_none() {
+            return Err(ConfigError::Invalid(
+                "intake.actuator_position.right_adc_channel needs intake.right_actuator_pwm".to_owned()));
+        }
+        if !(0.0..1.0).contains(&self.tilt_tolerance) {
+            return Err(ConfigError::Invalid(format!(
+                "intake.tilt_tolerance must be at least 0 and less than 1, got {}", self.tilt_tolerance)));
+        }
+        if !(0.0..=1.0).contains(&self.tilt_scale) {
+            return Err(ConfigError::Invalid(format!(
+                "intake.tilt_scale must be between 0 and 1, got {}", self.tilt_scale)));
+        }
+        Ok(())
+    }
+
+    pub fn get_digger_pwm(&self) -> PwmConfig {
+        self.digger_pwm
+    }
+
+    pub fn get_actuator_pwm(&self) -> PwmConfig {
+        self.actuator_pwm
+    }
+
+    pub fn get_left_upper_limit(&self) -> Pin {
+        self.left_upper_limit
+    }
+
+    pub fn get_left_lower_limit(&self) -> Pin {
+        self.left_lower_limit
+    }
+
+    pub fn get_right_upper_limit(&self) -> Pin {
+        self.right_upper_limit
+    }
+
+    pub fn get_right_lower_limit(&self) -> Pin {
+        self.right_lower_limit
+    }
+
+    /// The speed given to the bucket ladder as it digs.
+    pub fn get_digging_rate(&self) -> f32 {
+        self.digging_rate
+    }
+
+    /// The speed at which the actuators move.
+    pub fn get_actuator_rate(&self) -> f32 {
+        self.actuator_rate
+    }
+
+    /// The PWM output of the right actuator's motor, when each actuator has its own motor.
+    pub fn get_right_actuator_pwm(&self) -> Option<PwmConfig> {
+        self.right_actuator_pwm
+    }
+
+    /// How far apart, as a fraction of their stroke, independently driven actuators may get before
+    /// the one ahead is slowed down. Tilt is only corrected when both actuators have a
+    /// potentiometer, since timing them cannot tell when one has fallen behind.
+    pub fn get_tilt_tolerance(&self) -> f32 {
+        self.tilt_tolerance
+    }
+
+    /// The fraction of its usual speed given to an actuator which is ahead of the other.
+    pub fn get_tilt_scale(&self) -> f32 {
+        self.tilt_scale
+    }
+
+    pub fn get_digger_stall(&self) -> &StallConfig {
+        &self.digger_stall
+    }
+
+    pub fn get_actuator_stall(&self) -> &StallConfig {
+        &self.actuator_stall
+    }
+
+    pub fn get_actuator_position(&self) -> &ActuatorPositionConfig {
+        &self.actuator_position
+    }
+
+    pub fn get_limit_check(&self) -> &LimitCheckConfig {
+        &self.limit_check
+    }
+
+    /// The longest the actuators may run at full speed without reaching the limit they are
+    /// heading for.
+    pub fn get_actuator_timeout(&self) -> Duration {
+        Duration::from_millis(self.actuator_timeout_millis)
+    }
+}
+
+impl Default for IntakeConfig {
+    fn default() -> Self {
+        Self {
+            digger_pwm: PwmConfig::new(DIGGER_PWM_CHIP, DIGGER_PWM_NUM),
+            actuator_pwm: PwmConfig::new(ACTUATOR_PWM_CHIP, ACTUATOR_PWM_NUM),
+            right_actuator_pwm: None,
+            left_upper_limit: LEFT_UPPER_ACTUATOR_LIMIT,
+            left_lower_limit: LEFT_LOWER_ACTUATOR_LIMIT,
+            right_upper_limit: RIGHT_UPPER_ACTUATOR_LIMIT,
+            right_lower_limit: RIGHT_LOWER_ACTUATOR_LIMIT,
+            digging_rate: DIGGING_RATE,
+            actuator_rate: MH_ACTUATOR_RATE,
+            tilt_tolerance: ACTUATOR_TILT_TOLERANCE,
+            tilt_scale: ACTUATOR_TILT_SCALE,
+            digger_stall: StallConfig::default(),
+            actuator_stall: StallConfig::default(),
+            actuator_position: ActuatorPositionConfig::default(),
+            limit_check: LimitCheckConfig::default(),
+            actuator_timeout_millis: ACTUATOR_TIMEOUT_MILLIS,
+        }
+    }
+}
+
+#[cfg(test)]
+impl IntakeConfig {
+    pub fn with_digger_stall(mut self, digger_stall: StallConfig) -> Self {
+        self.digger_stall = digger_stall;
+        self
+    }
+
+    pub fn with_actuator_timeout_millis(mut self, actuator_timeout_millis: u64) -> Self {
+        self.actuator_timeout_millis = actuator_timeout_millis;
+        self
+    }
+
+    pub fn with_actuator_position(mut self, actuator_position: ActuatorPositionConfig) -> Self {
+        self.actuator_position = actuator_position;
+        self
+    }
+
+    pub fn with_tilt_tolerance(mut self, tilt_tolerance: f32) -> Self {
+        self.tilt_tolerance = tilt_tolerance;
+        self
+    }
+
+    pub fn with_tilt_scale(mut self, tilt_scale: f32) -> Self {
+        self.tilt_scale = tilt_scale;
+        self
+    }
+}
+
+impl ActuatorPositionConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.stroke_millis == 0 {
+            return Err(ConfigError::Invalid("intake.actuator_position.stroke_millis must be positive".to_owned()));
+        }
+        if !(0.0..0.5).contains(&self.tolerance) {
+            return Err(ConfigError::Invalid(format!(
+                "intake.actuator_position.tolerance must be at least 0 and less than 0.5, got {}", self.tolerance)));
+        }
+        let channels = [
+            ("intake.actuator_position.adc_channel", self.adc_channel),
+            ("intake.actuator_position.right_adc_channel", self.right_adc_channel),
+        ];
+        for (name, channel) in channels.iter() {
+            if let Some(channel) = channel {
+                if *channel >= ADC_CHANNELS {
+                    return Err(ConfigError::Invalid(format!("{} must be less than {}, got {}", name, ADC_CHANNELS, channel)));
+                }
+            }
+        }
+        if (self.upper_volts - self.lower_volts).abs() < ACTUATOR_MIN_VOLTS_SPAN {
+            return Err(ConfigError::Invalid(format!(
+                "intake.actuator_position.upper_volts and lower_volts must be at least {} apart, got {} and {}",
+                ACTUATOR_MIN_VOLTS_SPAN, self.upper_volts, self.lower_volts)));
+        }
+        Ok(())
+    }
+
+    /// How long the actuators take to travel from one limit to the other at full speed.
+    pub fn get_stroke_time(&self) -> Duration {
+        Duration::from_millis(self.stroke_millis)
+    }
+
+    /// How close to its target, as a fraction of the full stroke, a moving actuator must come.
+    pub fn get_tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    pub fn get_adc_device(&self) -> u8 {
+        self.adc_device
+    }
+
+    /// The ADC channel of the potentiometer, if there is one.
+    pub fn get_adc_channel(&self) -> Option<u8> {
+        self.adc_channel
+    }
+
+    /// The ADC channel of the right actuator's potentiometer, if it has its own motor and one.
+    pub fn get_right_adc_channel(&self) -> Option<u8> {
+        self.right_adc_channel
+    }
+
+    /// The potentiometer's voltage with the actuators at their upper limit.
+    pub fn get_upper_volts(&self) -> f32 {
+        self.upper_volts
+    }
+
+    /// The potentiometer's voltage with the actuators at their lower limit.
+    pub fn get_lower_volts(&self) -> f32 {
+        self.lower_volts
+    }
+}
+
+impl Default for ActuatorPositionConfig {
+    fn default() -> Self {
+        Self {
+            stroke_millis: ACTUATOR_STROKE_MILLIS,
+            tolerance: ACTUATOR_POSITION_TOLERANCE,
+            adc_device: CURRENT_ADC_DEVICE,
+            adc_channel: None,
+            right_adc_channel: None,
+            upper_volts: 0.0,
+            lower_volts: ADC_REFERENCE_VOLTS,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ActuatorPositionConfig {
+    pub fn with_stroke_millis(mut self, stroke_millis: u64) -> Self {
+        self.stroke_millis = stroke_millis;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_upper_volts(mut self, upper_volts: f32) -> Self {
+        self.upper_volts = upper_volts;
+        self
+    }
+
+    pub fn with_lower_volts(mut self, lower_volts: f32) -> Self {
+        self.lower_volts = lower_volts;
+        self
+    }
+}
+
+impl StallConfig {
+    /// Checks the settings of the stall sensor configured under `name`.
+    fn validate(&self, name: &str) -> Result<(), ConfigError> {
+        if self.current <= 0.0 {
+            return Err(ConfigError::Invalid(format!("{}.current must be positive, got {}", name, self.current)));
+        }
+        for (field, channel) in [("adc_channel", self.adc_channel), ("right_adc_channel", self.right_adc_channel)].iter() {
+            if let Some(channel) = channel {
+                if *channel >= ADC_CHANNELS {
+                    return Err(ConfigError::Invalid(format!(
+                        "{}.{} must be less than {}, got {}", name, field, ADC_CHANNELS, channel)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The current above which a running motor is straining.
+    pub fn get_current(&self) -> f32 {
+        self.current
+    }
+
+    /// How long the motor may strain before it is stopped.
+    pub fn get_time(&self) -> Duration {
+        Duration::from_millis(self.time_millis)
+    }
+
+    pub fn get_adc_device(&self) -> u8 {
+        self.adc_device
+    }
+
+    /// The ADC channel the motor's current sensor is wired to. Without one, the current must be
+    /// reported by the motor controller itself.
+    pub fn get_adc_channel(&self) -> Option<u8> {
+        self.adc_channel
+    }
+
+    /// The ADC channel of the right intake actuator's current sensor, when it has its own motor.
+    pub fn get_right_adc_channel(&self) -> Option<u8> {
+        self.right_adc_channel
+    }
+
+    /// The voltage the current sensor outputs when no current is flowing.
+    pub fn get_zero_volts(&self) -> f32 {
+        self.zero_volts
+    }
+
+    /// How many amps each volt above `zero_volts` represents.
+    pub fn get_amps_per_volt(&self) -> f32 {
+        self.amps_per_volt
+    }
+}
+
+impl Default for StallConfig {
+    fn default() -> Self {
+        Self {
+            current: MOTOR_STALL_CURRENT,
+            time_millis: MOTOR_STALL_MILLIS,
+            adc_device: CURRENT_ADC_DEVICE,
+            adc_channel: None,
+            right_adc_channel: None,
+            zero_volts: CURRENT_ZERO_VOLTS,
+            amps_per_volt: CURRENT_AMPS_PER_VOLT,
+        }
+    }
+}
+
+#[cfg(test)]
+impl StallConfig {
+    pub fn with_current(mut self, current: f32) -> Self {
+        self.current = current;
+        self
+    }
+
+    pub fn with_time_millis(mut self, time_millis: u64) -> Self {
+        self.time_millis = time_millis;
+        self
+    }
+
+    pub fn with_zero_volts(mut self, zero_volts: f32) -> Self {
+        self.zero_volts = zero_volts;
+        self
+    }
+
+    pub fn with_amps_per_volt(mut self, amps_per_volt: f32) -> Self {
+        self.amps_per_volt = amps_per_volt;
+        self
+    }
+}
+
+impl DumperConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        check_rate("dumper.dumping_rate", self.dumping_rate)?;
+        check_rate("dumper.reset_rate", self.reset_rate)?;
+        self.limit_check.validate("dumper.limit_check")?;
+        if self.timeout_millis == 0 {
+            return Err(ConfigError::Invalid("dumper.timeout_millis must be positive".to_owned()));
+        }
+        Ok(())
+    }
+
+    pub fn get_pwm(&self) -> PwmConfig {
+        self.pwm
+    }
+
+    pub fn get_upper_limit(&self) -> Pin {
+        self.upper_limit
+    }
+
+    pub fn get_lower_limit(&self) -> Pin {
+        self.lower_limit
+    }
+
+    /// The speed used by the dumper to dump material.
+    pub fn get_dumping_rate(&self) -> f32 {
+        self.dumping_rate
+    }
+
+    /// The speed used by the dumper to reset its position.
+    pub fn get_reset_rate(&self) -> f32 {
+        self.reset_rate
+    }
+
+    /// How long a dump cycle holds the dumper at its upper limit before resetting it.
+    pub fn get_cycle_dwell(&self) -> Duration {
+        Duration::from_millis(self.cycle_dwell_millis)
+    }
+
+    pub fn get_limit_check(&self) -> &LimitCheckConfig {
+        &self.limit_check
+    }
+
+    /// The longest the dumper may run at full speed without reaching the limit it is heading for.
+    pub fn get_timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_millis)
+    }
+}
+
+impl Default for DumperConfig {
+    fn default() -> Self {
+        Self {
+            pwm: PwmConfig::new(DUMPER_PWM_CHIP, DUMPER_PWM_NUM),
+            upper_limit: DUMPER_UPPER_ACTUATOR_LIMIT,
+            lower_limit: DUMPER_LOWER_ACTUATOR_LIMIT,
+            dumping_rate: DUMPING_RATE,
+            reset_rate: DUMPER_RESET_RATE,
+            cycle_dwell_millis: DUMPER_CYCLE_DWELL_MILLIS,
+            limit_check: LimitCheckConfig::default(),
+            timeout_millis: DUMPER_TIMEOUT_MILLIS,
+        }
+    }
+}
+
+#[cfg(test)]
+impl DumperConfig {
+    pub fn with_cycle_dwell_millis(mut self, cycle_dwell_millis: u64) -> Self {
+        self.cycle_dwell_millis = cycle_dwell_millis;
+        self
+    }
+
+    pub fn with_limit_check(mut self, limit_check: LimitCheckConfig) -> Self {
+        self.limit_check = limit_check;
+        self
+    }
+
+    pub fn with_timeout_millis(mut self, timeout_millis: u64) -> Self {
+        self.timeout_millis = timeout_millis;
+        self
+    }
+}
+
+impl LimitCheckConfig {
+    /// Checks the settings of the limit check configured under `name`.
+    fn validate(&self, name: &str) -> Result<(), ConfigError> {
+        if self.clear_millis == 0 {
+            return Err(ConfigError::Invalid(format!("{}.clear_millis must be positive", name)));
+        }
+        Ok(())
+    }
+
+    /// How long a limit may stay asserted after the mechanism starts moving away from it.
+    pub fn get_clear_time(&self) -> Duration {
+        Duration::from_millis(self.clear_millis)
+    }
+
+    /// The least time the mechanism can take to travel from one limit to the other.
+    pub fn get_min_travel(&self) -> Duration {
+        Duration::from_millis(self.min_travel_millis)
+    }
+}
+
+impl Default for LimitCheckConfig {
+    fn default() -> Self {
+        Self {
+            clear_millis: LIMIT_CLEAR_MILLIS,
+            min_travel_millis: LIMIT_MIN_TRAVEL_MILLIS,
+        }
+    }
+}
+
+#[cfg(test)]
+impl LimitCheckConfig {
+    pub fn with_clear_millis(mut self, clear_millis: u64) -> Self {
+        self.clear_millis = clear_millis;
+        self
+    }
+
+    pub fn with_min_travel_millis(mut self, min_travel_millis: u64) -> Self {
+        self.min_travel_millis = min_travel_millis;
+        self
+    }
+}
+
+impl CurrentConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.brownout <= 0.0 {
+            return Err(ConfigError::Invalid(format!("current.brownout must be positive, got {}", self.brownout)));
+        }
+        if self.critical <= self.brownout {
+            return Err(ConfigError::Invalid(format!(
+                "current.critical ({}) must be greater than current.brownout ({})", self.critical, self.brownout)));
+        }
+
+        if !(0.0..=1.0).contains(&self.shed_scale) {
+            return Err(ConfigError::Invalid(format!(
+                "current.shed_scale must be between 0 and 1, got {}", self.shed_scale)));
+        }
+        let mut loads = HashSet::new();
+        for load in &self.shed_order {
+            if !loads.insert(load) {
+                return Err(ConfigError::Invalid(format!("current.shed_order lists {:?} more than once", load)));
+            }
+        }
+        if self.adc_channel >= ADC_CHANNELS {
+            return Err(ConfigError::Invalid(format!(
+                "current.adc_channel must be less than {}, got {}", ADC_CHANNELS, self.adc_channel)));
+        }
+        Ok(())
+    }
+
+    pub fn get_brownout(&self) -> f32 {
+        self.brownout
+    }
+
+    pub fn get_critical(&self) -> f32 {
+        self.critical
+    }
+
+    /// Whether the robot is killed when the current becomes critical, rather than only shedding load.
+    pub fn get_kill_on_critical(&self) -> bool {
+        self.kill_on_critical
+    }
+
+    /// The IIO device number of the ADC the current sensor is wired to.
+    pub fn get_adc_device(&self) -> u8 {
+        self.adc_device
+    }
+
+    pub fn get_adc_channel(&self) -> u8 {
+        self.adc_channel
+    }
+
+    /// The voltage the current sensor outputs when no current is flowing.
+    pub fn get_zero_volts(&self) -> f32 {
+        self.zero_volts
+    }
+
+    /// How many amps each volt above `zero_volts` represents.
+    pub fn get_amps_per_volt(&self) -> f32 {
+        self.amps_per_volt
+    }
+
+    /// The loads to throttle during a brownout, the first to be throttled first.
+    pub fn get_shed_order(&self) -> &[Load] {
+        &self.shed_order
+    }
+
+    /// The fraction of their commanded output which throttled loads are allowed.
+    pub fn get_shed_scale(&self) -> f32 {
+        self.shed_scale
+    }
+
+    /// How long to wait between throttling or restoring successive loads.
+    pub fn get_shed_interval(&self) -> Duration {
+        Duration::from_millis(self.shed_interval_millis)
+    }
+}
+
+impl Default for CurrentConfig {
+    fn default() -> Self {
+        Self {
+            brownout: BROWN_CURRENT,
+            critical: CRITICAL_CURRENT,
+            kill_on_critical: KILL_ON_CRITICAL_CURRENT,
+            adc_device: CURRENT_ADC_DEVICE,
+            adc_channel: CURRENT_ADC_CHANNEL,
+            zero_volts: CURRENT_ZERO_VOLTS,
+            amps_per_volt: CURRENT_AMPS_PER_VOLT,
+            shed_order: LOAD_SHED_ORDER.to_vec(),
+            shed_scale: LOAD_SHED_SCALE,
+            shed_interval_millis: LOAD_SHED_INTERVAL_MILLIS,
+        }
+    }
+}
+
+#[cfg(test)]
+impl CurrentConfig {
+    pub fn with_zero_volts(mut self, zero_volts: f32) -> Self {
+        self.zero_volts = zero_volts;
+        self
+    }
+
+    pub fn with_amps_per_volt(mut self, amps_per_volt: f32) -> Self {
+        self.amps_per_volt = amps_per_volt;
+        self
+    }
+
+    pub fn with_kill_on_critical(mut self, kill_on_critical: bool) -> Self {
+        self.kill_on_critical = kill_on_critical;
+        self
+    }
+
+    pub fn with_shed_order(mut self, shed_order: &[Load]) -> Self {
+        self.shed_order = shed_order.to_vec();
+        self
+    }
+
+    pub fn with_shed_scale(mut self, shed_scale: f32) -> Self {
+        self.shed_scale = shed_scale;
+        self
+    }
+
+    pub fn with_shed_interval_millis(mut self, shed_interval_millis: u64) -> Self {
+        self.shed_interval_millis = shed_interval_millis;
+        self
+    }
+}
+
+impl LimitsConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        let limits = [
+            ("limits.intake_left_upper", &self.intake_left_upper),
+            ("limits.intake_left_lower", &self.intake_left_lower),
+            ("limits.intake_right_upper", &self.intake_right_upper),
+            ("limits.intake_right_lower", &self.intake_right_lower),
+            ("limits.dumper_upper", &self.dumper_upper),
+            ("limits.dumper_lower", &self.dumper_lower),
+        ];
+        for (name, debounce) in limits.iter() {
+            if debounce.samples == 0 && debounce.millis == 0 {
+                return Err(ConfigError::Invalid(format!("{}.samples and millis must not both be 0", name)));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_intake_left_upper(&self) -> &DebounceConfig {
+        &self.intake_left_upper
+    }
+
+    pub fn get_intake_left_lower(&self) -> &DebounceConfig {
+        &self.intake_left_lower
+    }
+
+    pub fn get_intake_right_upper(&self) -> &DebounceConfig {
+        &self.intake_right_upper
+    }
+
+    pub fn get_intake_right_lower(&self) -> &DebounceConfig {
+        &self.intake_right_lower
+    }
+
+    pub fn get_dumper_upper(&self) -> &DebounceConfig {
+        &self.dumper_upper
+    }
+
+    pub fn get_dumper_lower(&self) -> &DebounceConfig {
+        &self.dumper_lower
+    }
+}
+
+#[cfg(test)]
+impl LimitsConfig {
+    pub fn with_dumper_lower(mut self, dumper_lower: DebounceConfig) -> Self {
+        self.dumper_lower = dumper_lower;
+        self
+    }
+}
+
+impl DebounceConfig {
+    pub fn get_samples(&self) -> u32 {
+        self.samples
+    }
+
+    pub fn get_millis(&self) -> u64 {
+        self.millis
+    }
+}
+
+impl Default for DebounceConfig {
+    fn default() -> Self {
+        Self {
+            samples: LIMIT_DEBOUNCE_SAMPLES,
+            millis: LIMIT_DEBOUNCE_MILLIS,
+        }
+    }
+}
+
+#[cfg(test)]
+impl DebounceConfig {
+    pub fn with_samples(mut self, samples: u32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn with_millis(mut self, millis: u64) -> Self {
+        self.millis = millis;
+        self
+    }
+}
+
+impl ControllerConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.watchdog_timeout_millis == 0 {
+            return Err(ConfigError::Invalid("controller.watchdog_timeout_millis must not be zero".to_owned()));
+        }
+        Ok(())
+    }
+
+    pub fn get_watchdog_timeout_millis(&self) -> u64 {
+        self.watchdog_timeout_millis
+    }
+
+    /// Returns the period of the controller loop, or `None` if it should run as fast as it can.
+    pub fn get_loop_period(&self) -> Option<Duration> {
+        period_or_free_running(self.loop_period_micros)
+    }
+
+    /// Returns the period of the sensor monitor loop, or `None` if it should run as fast as it can.
+    pub fn get_monitor_period(&self) -> Option<Duration> {
+        period_or_free_running(self.monitor_period_micros)
+    }
+
+    /// Whether the robot is killed while a critical fault has been raised and not cleared.
+    pub fn get_kill_on_critical_fault(&self) -> bool {
+        self.kill_on_critical_fault
+    }
+}
+
+impl Default for ControllerConfig {
+    fn default() -> Self {
+        Self {
+            watchdog_timeout_millis: WATCHDOG_TIMEOUT_MILLIS,
+            loop_period_micros: CONTROLLER_PERIOD_MICROS,
+            monitor_period_micros: MONITOR_PERIOD_MICROS,
+            kill_on_critical_fault: false,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ControllerConfig {
+    pub fn with_watchdog_timeout_millis(mut self, watchdog_timeout_millis: u64) -> Self {
+        self.watchdog_timeout_millis = watchdog_timeout_millis;
+        self
+    }
+
+    pub fn with_kill_on_critical_fault(mut self, kill_on_critical_fault: bool) -> Self {
+        self.kill_on_critical_fault = kill_on_critical_fault;
+        self
+    }
+}
+
+impl TelemetryConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.period_millis == 0 {
+            return Err(ConfigError::Invalid("telemetry.period_millis must not be zero".to_owned()));
+        }
+        Ok(())
+    }
+
+    pub fn get_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn get_period(&self) -> Duration {
+        Duration::from_millis(self.period_millis)
+    }
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: TELEMETRY_PORT,
+            period_millis: TELEMETRY_PERIOD_MILLIS,
+        }
+    }
+}
+
+impl RecorderConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.period_millis == 0 {
+            return Err(ConfigError::Invalid("recorder.period_millis must not be zero".to_owned()));
+        }
+        Ok(())
+    }
+
+    pub fn get_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get_period(&self) -> Duration {
+        Duration::from_millis(self.period_millis)
+    }
+
+    pub fn get_csv(&self) -> bool {
+        self.csv
+    }
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: RECORDING_PATH.to_owned(),
+            period_millis: RECORDING_PERIOD_MILLIS,
+            csv: false,
+        }
+    }
+}
+
+impl JournalConfig {
+    pub fn get_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: RECORDING_PATH.to_owned(),
+        }
+    }
+}
+
+impl ShapingConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        let driving = &self.driving;
+        if !(0.0..1.0).contains(&driving.deadband) {
+            return Err(ConfigError::Invalid(format!(
+                "shaping.driving.deadband must be at least 0 and less than 1, got {}", driving.deadband)));
+        }
+        if !(0.0..=1.0).contains(&driving.expo) {
+            return Err(ConfigError::Invalid(format!("shaping.driving.expo must be between 0 and 1, got {}", driving.expo)));
+        }
+        let max_speeds = [
+            ("shaping.driving", driving.max_speed),
+            ("shaping.digging", self.digging.max_speed),
+            ("shaping.dumping", self.dumping.max_speed),
+        ];
+        for (name, max_speed) in max_speeds.iter() {
+            if *max_speed <= 0.0 || *max_speed > 1.0 {
+                return Err(ConfigError::Invalid(format!(
+                    "{}.max_speed must be greater than 0 and at most 1, got {}", name, max_speed)));
+            }
+        }
+        if self.precision_scale <= 0.0 || self.precision_scale > 1.0 {
+            return Err(ConfigError::Invalid(format!(
+                "shaping.precision_scale must be greater than 0 and at most 1, got {}", self.precision_scale)));
+        }
+        Ok(())
+    }
+
+    /// The profile for inputs to the drive train, which only moves in driving mode.
+    pub fn get_driving(&self) -> &ShapingProfile {
+        &self.driving
+    }
+
+    /// The speed limit of the digger and actuators, which only move in digging mode.
+    pub fn get_digging(&self) -> &SpeedLimit {
+        &self.digging
+    }
+
+    /// The speed limit of the dumper, which only moves in dumping mode.
+    pub fn get_dumping(&self) -> &SpeedLimit {
+        &self.dumping
+    }
+
+    /// The fraction of each mode's maximum speed allowed in precision mode.
+    pub fn get_precision_scale(&self) -> f32 {
+        self.precision_scale
+    }
+}
+
+impl Default for ShapingConfig {
+    fn default() -> Self {
+        Self {
+            driving: ShapingProfile::default(),
+            digging: SpeedLimit::default(),
+            dumping: SpeedLimit::default(),
+            precision_scale: PRECISION_SCALE,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ShapingConfig {
+    pub fn with_driving(mut self, driving: ShapingProfile) -> Self {
+        self.driving = driving;
+        self
+    }
+
+    pub fn with_digging(mut self, digging: SpeedLimit) -> Self {
+        self.digging = digging;
+        self
+    }
+
+    pub fn with_precision_scale(mut self, precision_scale: f32) -> Self {
+        self.precision_scale = precision_scale;
+        self
+    }
+}
+
+impl ShapingProfile {
+    /// Inputs smaller than this are treated as zero.
+    pub fn get_deadband(&self) -> f32 {
+        self.deadband
+    }
+
+    /// How far the response is bent from linear towards cubic, between 0 and 1.
+    pub fn get_expo(&self) -> f32 {
+        self.expo
+    }
+
+    /// The fraction of full speed given at full input.
+    pub fn get_max_speed(&self) -> f32 {
+        self.max_speed
+    }
+}
+
+impl Default for ShapingProfile {
+    fn default() -> Self {
+        Self {
+            deadband: INPUT_DEADBAND,
+            expo: INPUT_EXPO,
+            max_speed: INPUT_MAX_SPEED,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ShapingProfile {
+    pub fn with_deadband(mut self, deadband: f32) -> Self {
+        self.deadband = deadband;
+        self
+    }
+
+    pub fn with_expo(mut self, expo: f32) -> Self {
+        self.expo = expo;
+        self
+    }
+}
+
+impl SpeedLimit {
+    /// The fraction of full speed the mechanism may reach.
+    pub fn get_max_speed(&self) -> f32 {
+        self.max_speed
+    }
+}
+
+impl Default for SpeedLimit {
+    fn default() -> Self {
+        Self {
+            max_speed: INPUT_MAX_SPEED,
+        }
+    }
+}
+
+#[cfg(test)]
+impl SpeedLimit {
+    pub fn with_max_speed(mut self, max_speed: f32) -> Self {
+        self.max_speed = max_speed;
+        self
+    }
+}
+
+impl LoggingConfig {
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get_level(&self) -> Level {
+        self.level
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            path: LOG_PATH.to_owned(),
+            level: LOG_FILTER_LEVEL,
+        }
+    }
+}
+
+fn check_rate(name: &str, rate: f32) -> Result<(), ConfigError> {
+    if (-1.0..=1.0).contains(&rate) {
+        Ok(())
+    } else {
+        Err(ConfigError::Invalid(format!("{} must be between -1.0 and 1.0, got {}", name, rate)))
+    }
+}
+
+/// Turns a loop period into a `Duration`, or `None` if it is zero and the loop should run as fast
+/// as it can.
+fn period_or_free_running(micros: u64) -> Option<Duration> {
+    if micros == 0 {
+        None
+    } else {
+        Some(Duration::from_micros(micros))
+    }
+}
+
+fn deserialize_level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Level, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    match name.to_lowercase().as_str() {
+        "critical" => Ok(Level::Critical),
+        "error" => Ok(Level::Error),
+        "warning" | "warn" => Ok(Level::Warning),
+        "info" => Ok(Level::Info),
+        "debug" => Ok(Level::Debug),
+        "trace" => Ok(Level::Trace),
+        _ => Err(D::Error::custom(format!("unknown log level `{}`", name))),
+    }
+}
+
+fn deserialize_pin<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pin, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    parse_pin(&name).ok_or_else(|| D::Error::custom(format!("unknown GPIO pin `{}`, expected a header pin such as \"P9_12\"", name)))
+}
+
+/// Parses a header pin name such as `P9_12` or `GPIO_P9_12` into a GPIO pin.
+pub fn parse_pin(name: &str) -> Option<Pin> {
+    let name = name.trim().to_uppercase();
+    let name = name.trim_start_matches("GPIO_");
+    let pin = match name {
+        "P8_3" => Pin::GPIO_P8_3,
+        "P8_4" => Pin::GPIO_P8_4,
+        "P8_5" => Pin::GPIO_P8_5,
+        "P8_6" => Pin::GPIO_P8_6,
+        "P8_7" => Pin::GPIO_P8_7,
+        "P8_8" => Pin::GPIO_P8_8,
+        "P8_9" => Pin::GPIO_P8_9,
+        "P8_10" => Pin::GPIO_P8_10,
+        "P8_11" => Pin::GPIO_P8_11,
+        "P8_12" => Pin::GPIO_P8_12,
+        "P8_13" => Pin::GPIO_P8_13,
+        "P8_14" => Pin::GPIO_P8_14,
+        "P8_15" => Pin::GPIO_P8_15,
+        "P8_16" => Pin::GPIO_P8_16,
+        "P8_17" => Pin::GPIO_P8_17,
+        "P8_18" => Pin::GPIO_P8_18,
+        "P8_19" => Pin::GPIO_P8_19,
+        "P8_20" => Pin::GPIO_P8_20,
+        "P8_21" => Pin::GPIO_P8_21,
+        "P8_22" => Pin::GPIO_P8_22,
+        "P8_23" => Pin::GPIO_P8_23,
+        "P8_24" => Pin::GPIO_P8_24,
+        "P8_25" => Pin::GPIO_P8_25,
+        "P8_26" => Pin::GPIO_P8_26,
+        "P8_27" => Pin::GPIO_P8_27,
+        "P8_28" => Pin::GPIO_P8_28,
+        "P8_29" => Pin::GPIO_P8_29,
+        "P8_30" => Pin::GPIO_P8_30,
+        "P8_31" => Pin::GPIO_P8_31,
+        "P8_32" => Pin::GPIO_P8_32,
+        "P8_33" => Pin::GPIO_P8_33,
+        "P8_34" => Pin::GPIO_P8_34,
+        "P8_35" => Pin::GPIO_P8_35,
+        "P8_36" => Pin::GPIO_P8_36,
+        "P8_37" => Pin::GPIO_P8_37,
+        "P8_38" => Pin::GPIO_P8_38,
+        "P8_39" => Pin::GPIO_P8_39,
+        "P8_40" => Pin::GPIO_P8_40,
+        "P8_41" => Pin::GPIO_P8_41,
+        "P8_42" => Pin::GPIO_P8_42,
+        "P8_43" => Pin::GPIO_P8_43,
+        "P8_44" => Pin::GPIO_P8_44,
+        "P8_45" => Pin::GPIO_P8_45,
+        "P8_46" => Pin::GPIO_P8_46,
+        "P9_11" => Pin::GPIO_P9_11,
+        "P9_12" => Pin::GPIO_P9_12,
+        "P9_13" => Pin::GPIO_P9_13,
+        "P9_14" => Pin::GPIO_P9_14,
+        "P9_15" => Pin::GPIO_P9_15,
+        "P9_16" => Pin::GPIO_P9_16,
+        "P9_17" => Pin::GPIO_P9_17,
+        "P9_18" => Pin::GPIO_P9_18,
+        "P9_21" => Pin::GPIO_P9_21,
+        "P9_22" => Pin::GPIO_P9_22,
+        "P9_23" => Pin::GPIO_P9_23,
+        "P9_24" => Pin::GPIO_P9_24,
+        "P9_25" => Pin::GPIO_P9_25,
+        "P9_26" => Pin::GPIO_P9_26,
+        "P9_27" => Pin::GPIO_P9_27,
+        "P9_28" => Pin::GPIO_P9_28,
+        "P9_29" => Pin::GPIO_P9_29,
+        "P9_30" => Pin::GPIO_P9_30,
+        "P9_31" => Pin::GPIO_P9_31,
+        "P9_41" => Pin::GPIO_P9_41,
+        "P9_42" => Pin::GPIO_P9_42,
+        _ => return None,
+    };
+    Some(pin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_uses_defaults() {
+        let config = RobotConfig::from_toml("").unwrap();
+        assert_eq!(DIGGING_RATE, config.get_intake().get_digging_rate());
+        assert_eq!(DUMPING_RATE, config.get_dumper().get_dumping_rate());
+        assert_eq!(DUMPER_RESET_RATE, config.get_dumper().get_reset_rate());
+        assert_eq!(BROWN_CURRENT, config.get_current().get_brownout());
+        assert_eq!(CRITICAL_CURRENT, config.get_current().get_critical());
+        assert_eq!(false, config.get_current().get_kill_on_critical());
+        assert_eq!(LOG_PATH, config.get_logging().get_path());
+        assert_eq!(DUMPER_PWM_CHIP, config.get_dumper().get_pwm().get_chip());
+        assert_eq!(DUMPER_UPPER_ACTUATOR_LIMIT as u8, config.get_dumper().get_upper_limit() as u8);
+    }
+
+    #[test]
+    fn loop_periods() {
+        let config = RobotConfig::from_toml("[controller]\nloop_period_micros = 2000\nmonitor_period_micros = 0\n").unwrap();
+        assert_eq!(Some(Duration::from_millis(2)), config.get_controller().get_loop_period());
+        assert_eq!(None, config.get_controller().get_monitor_period());
+
+        let config = RobotConfig::default();
+        assert_eq!(Some(Duration::from_micros(CONTROLLER_PERIOD_MICROS)), config.get_controller().get_loop_period());
+        assert_eq!(Some(Duration::from_micros(MONITOR_PERIOD_MICROS)), config.get_controller().get_monitor_period());
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(RobotConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn overrides() {
+        let config = RobotConfig::from_toml(r#"
+            [intake]
+            digging_rate = 0.5
+            left_upper_limit = "P8_26"
+
+            [dumper]
+            pwm = { chip = 4, num = 1 }
+
+            [logging]
+            path = "/tmp/robot.log"
+            level = "debug"
+        "#).unwrap();
+
+        assert_eq!(0.5, config.get_intake().get_digging_rate());
+        assert_eq!(MH_ACTUATOR_RATE, config.get_intake().get_actuator_rate());
+        assert_eq!(Pin::GPIO_P8_26 as u8, config.get_intake().get_left_upper_limit() as u8);
+        assert_eq!(4, config.get_dumper().get_pwm().get_chip());
+        assert_eq!(1, config.get_dumper().get_pwm().get_num());
+        assert_eq!("/tmp/robot.log", config.get_logging().get_path());
+        assert_eq!(Level::Debug, config.get_logging().get_level());
+    }
+
+    #[test]
+    fn rate_out_of_range() {
+        assert!(RobotConfig::from_toml("[intake]\ndigging_rate = 1.5").is_err());
+        assert!(RobotConfig::from_toml("[drive]\ndeceleration = 0.0").is_err());
+        assert!(RobotConfig::from_toml("[dumper]\nreset_rate = -2.0").is_err());
+    }
+
+    #[test]
+    fn unknown_pin() {
+        assert!(RobotConfig::from_toml("[dumper]\nupper_limit = \"P10_1\"").is_err());
+    }
+
+    #[test]
+    fn unknown_field() {
+        assert!(RobotConfig::from_toml("[dumper]\ndumping_speed = 1.0").is_err());
+    }
+
+    #[test]
+    fn duplicate_pin() {
+        let result = RobotConfig::from_toml("[dumper]\nupper_limit = \"P9_12\"");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn duplicate_pwm() {
+        let result = RobotConfig::from_toml("[intake]\ndigger_pwm = { chip = 3, num = 0 }");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn inverted_current_thresholds() {
+        assert!(RobotConfig::from_toml("[current]\nbrownout = 3.0\ncritical = 2.0").is_err());
+    }
+
+    #[test]
+    fn shed_order() {
+        let config = RobotConfig::from_toml("[current]\nshed_order = [\"Drive\", \"Digger\"]").unwrap();
+        assert_eq!(&[Load::Drive, Load::Digger], config.get_current().get_shed_order());
+
+        assert!(RobotConfig::from_toml("[current]\nshed_order = [\"Drive\", \"Drive\"]").is_err());
+        assert!(RobotConfig::from_toml("[current]\nshed_order = [\"Wheels\"]").is_err());
+        assert!(RobotConfig::from_toml("[current]\nshed_scale = 1.5").is_err());
+    }
+
+    #[test]
+    fn stall() {
+        let config = RobotConfig::from_toml("[intake.digger_stall]\ncurrent = 4.0\nadc_channel = 2").unwrap();
+        assert_eq!(4.0, config.get_intake().get_digger_stall().get_current());
+        assert_eq!(Some(2), config.get_intake().get_digger_stall().get_adc_channel());
+        assert_eq!(None, config.get_intake().get_actuator_stall().get_adc_channel());
+        assert_eq!(Duration::from_millis(MOTOR_STALL_MILLIS), config.get_intake().get_actuator_stall().get_time());
+
+        assert!(RobotConfig::from_toml("[intake.actuator_stall]\ncurrent = 0.0").is_err());
+        assert!(RobotConfig::from_toml("[intake.actuator_stall]\nadc_channel = 7").is_err());
+
+        let config = RobotConfig::from_toml(
+            "[intake]\nright_actuator_pwm = { chip = 4, num = 0 }\n[intake.actuator_stall]\nright_adc_channel = 3").unwrap();
+        assert_eq!(Some(3), config.get_intake().get_actuator_stall().get_right_adc_channel());
+        assert!(RobotConfig::from_toml("[intake.actuator_stall]\nright_adc_channel = 3").is_err());
+        assert!(RobotConfig::from_toml(
+            "[intake]\nright_actuator_pwm = { chip = 4, num = 0 }\n[intake.digger_stall]\nright_adc_channel = 3").is_err());
+    }
+
+    #[test]
+    fn shaping() {
+        let config = RobotConfig::from_toml("[shaping]\nprecision_scale = 0.25\n\n[shaping.driving]\ndeadband = 0.1").unwrap();
+        assert_eq!(0.1, config.get_shaping().get_driving().get_deadband());
+        assert_eq!(INPUT_MAX_SPEED, config.get_shaping().get_digging().get_max_speed());
+        assert_eq!(0.25, config.get_shaping().get_precision_scale());
+
+        // Only the drive train's inputs are shaped, so the other modes only have a speed limit.
+        assert!(RobotConfig::from_toml("[shaping.digging]\ndeadband = 0.1").is_err());
+        assert!(RobotConfig::from_toml("[shaping.driving]\ndeadband = 1.0").is_err());
+        assert!(RobotConfig::from_toml("[shaping.dumping]\nmax_speed = 0.0").is_err());
+        assert!(RobotConfig::from_toml("[shaping.driving]\nexpo = -0.5").is_err());
+    }
+
+    #[test]
+    fn encoders() {
+        let config = RobotConfig::from_toml(r#"
+            [drive]
+            left_encoder = { a = "P8_26", b = "P8_27" }
+            right_encoder = { a = "P8_28", b = "P8_29" }
+        "#).unwrap();
+        assert_eq!(Pin::GPIO_P8_27 as u8, config.get_drive().get_left_encoder().unwrap().get_b() as u8);
+        assert_eq!(DRIVE_MAX_VELOCITY, config.get_drive().get_speed_control().get_max_velocity());
+
+        assert!(RobotConfig::from_toml("[drive]\nleft_encoder = { a = \"P8_26\", b = \"P8_27\" }").is_err());
+        assert!(RobotConfig::from_toml(r#"
+            [drive]
+            left_encoder = { a = "P8_26", b = "P8_27" }
+            right_encoder = { a = "P8_28", b = "P8_26" }
+        "#).is_err());
+    }
+
+    #[test]
+    fn limit_check() {
+        let config = RobotConfig::from_toml("[intake.limit_check]\nmin_travel_millis = 2000").unwrap();
+        assert_eq!(Duration::from_millis(2000), config.get_intake().get_limit_check().get_min_travel());
+        assert_eq!(Duration::from_millis(LIMIT_CLEAR_MILLIS), config.get_intake().get_limit_check().get_clear_time());
+
+        assert!(RobotConfig::from_toml("[dumper.limit_check]\nclear_millis = 0").is_err());
+        assert!(RobotConfig::from_toml("[dumper]\ntimeout_millis = 0").is_err());
+        assert_eq!(Duration::from_millis(DUMPER_TIMEOUT_MILLIS), config.get_dumper().get_timeout());
+    }
+
+    #[test]
+    fn shared_adc_channel() {
+        assert!(RobotConfig::from_toml("[intake.digger_stall]\nadc_channel = 0").is_err());
+        assert!(RobotConfig::from_toml(
+            "[intake.actuator_stall]\nadc_channel = 2\n[intake.actuator_position]\nadc_channel = 2").is_err());
+        assert!(RobotConfig::from_toml(
+            "[intake.actuator_stall]\nadc_channel = 2\n[intake.actuator_position]\nadc_channel = 3").is_ok());
+
+        // Channels only clash on the same ADC.
+        assert!(RobotConfig::from_toml("[intake.actuator_position]\nadc_device = 1\nadc_channel = 0").is_ok());
+    }
+
+    #[test]
+    fn potentiometer_span() {
+        let config = RobotConfig::from_toml("[intake.actuator_position]\nupper_volts = 1.0\nlower_volts = 0.5").unwrap();
+        assert_eq!(1.0, config.get_intake().get_actuator_position().get_upper_volts());
+
+        assert!(RobotConfig::from_toml("[intake.actuator_position]\nupper_volts = 1.0\nlower_volts = 1.0").is_err());
+        assert!(RobotConfig::from_toml("[intake.actuator_position]\nupper_volts = 1.0\nlower_volts = 0.95").is_err());
+    }
+
+    #[test]
+    fn limit_debounce() {
+        let config = RobotConfig::from_toml(r#"
+            [limits]
+            dumper_upper = { samples = 3, millis = 20 }
+        "#).unwrap();
+        assert_eq!(3, config.get_limits().get_dumper_upper().get_samples());
+        assert_eq!(20, config.get_limits().get_dumper_upper().get_millis());
+        assert_eq!(LIMIT_DEBOUNCE_SAMPLES, config.get_limits().get_dumper_lower().get_samples());
+
+        assert!(RobotConfig::from_toml("[limits]\nintake_left_upper = { samples = 0 }").is_err());
+        assert!(RobotConfig::from_toml("[limits]\nintake_left_upper = { samples = 0, millis = 20 }").is_ok());
+    }
+
+    #[test]
+    fn pin_names() {
+        assert_eq!(Pin::GPIO_P9_12 as u8, parse_pin("P9_12").unwrap() as u8);
+        assert_eq!(Pin::GPIO_P9_12 as u8, parse_pin("gpio_p9_12").unwrap() as u8);
+        assert!(parse_pin("P9_1").is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use libbeaglebone::pins::Pin;
 use slog::Level;
 
+use crate::mechatronics::load_shedding::Load;
+
 // P9.14
 pub const FRONT_LEFT_PWM_CHIP: u8 = 3;
 pub const FRONT_LEFT_PWM_NUMBER: u8 = 0;
@@ -42,6 +44,40 @@ pub const RIGHT_LOWER_ACTUATOR_LIMIT: Pin = Pin::GPIO_P8_8;
 pub const DUMPER_UPPER_ACTUATOR_LIMIT: Pin = Pin::GPIO_P8_15;
 pub const DUMPER_LOWER_ACTUATOR_LIMIT: Pin = Pin::GPIO_P8_16;
 
+/// The sysfs class directory containing the `gpio` and `pwm` devices.
+pub const SYSFS_ROOT: &str = "/sys/class";
+
+/// The sysfs directory containing the IIO devices, such as the ADC.
+pub const IIO_ROOT: &str = "/sys/bus/iio/devices";
+
+/// The number of input channels on the BeagleBone's ADC.
+pub const ADC_CHANNELS: u8 = 7;
+
+/// The largest raw reading of the BeagleBone's 12 bit ADC.
+pub const ADC_MAX_RAW: u16 = 4095;
+
+/// The voltage at which the BeagleBone's ADC reads `ADC_MAX_RAW`.
+pub const ADC_REFERENCE_VOLTS: f32 = 1.8;
+
+/// The port the telemetry WebSocket server listens on.
+pub const TELEMETRY_PORT: u16 = 8001;
+
+/// How often telemetry snapshots are pushed to clients when nothing significant changes.
+pub const TELEMETRY_PERIOD_MILLIS: u64 = 100;
+
+/// How often the telemetry publisher checks the robot state for significant changes.
+pub const TELEMETRY_POLL_MILLIS: u64 = 5;
+
+/// The folder which recorded runs are written to. Each launch creates a new folder inside it.
+pub const RECORDING_PATH: &str = "./runs";
+
+/// How often the robot state is recorded.
+pub const RECORDING_PERIOD_MILLIS: u64 = 100;
+
+/// The path of the configuration file which is loaded at startup.
+/// If the file does not exist, the values in this file are used instead.
+pub const CONFIG_PATH: &str = "./robot.toml";
+
 /// The path of the folder which logs will be kept in.
 /// If the folder does not exist, the program will create it.
 pub const LOG_PATH: &str = "./rmc.log";
@@ -49,19 +85,150 @@ pub const LOG_PATH: &str = "./rmc.log";
 /// The lowest level of logs which will be displayed to the user.
 pub const LOG_FILTER_LEVEL: Level = Level::Warning;
 
+/// How quickly the drive motors may speed up and slow down, as a fraction of full speed per second.
+pub const DRIVE_ACCELERATION: f32 = 2.0;
+pub const DRIVE_DECELERATION: f32 = 4.0;
+
+/// The gains used to hold the drive motors at their commanded speed (Currently placeholder).
+pub const SPEED_CONTROL_KP: f32 = 0.5;
+pub const SPEED_CONTROL_KI: f32 = 0.5;
+pub const SPEED_CONTROL_KD: f32 = 0.0;
+
+/// The speed of the drive wheels at full output, in encoder counts per second (Currently placeholder).
+pub const DRIVE_MAX_VELOCITY: f32 = 1000.0;
+
+/// The radius of the drive wheels, in meters (Currently placeholder).
+pub const WHEEL_RADIUS: f32 = 0.1;
+
+/// The distance between the left and right drive wheels, in meters (Currently placeholder).
+pub const TRACK_WIDTH: f32 = 0.5;
+
+/// The number of drive encoder counts in one turn of a wheel (Currently placeholder).
+pub const DRIVE_COUNTS_PER_REVOLUTION: f32 = 1000.0;
+
 /// The speed given to the motors as they dig.
 pub const DIGGING_RATE: f32 = 1.0;
 
 /// The speed at which the actuators move.
 pub const MH_ACTUATOR_RATE: f32 = 1.0;
 
+/// How long the actuators take to travel between their limits at full speed (Currently placeholder).
+pub const ACTUATOR_STROKE_MILLIS: u64 = 8000;
+
+/// How close, as a fraction of their full stroke, the actuators must come to a requested position.
+pub const ACTUATOR_POSITION_TOLERANCE: f32 = 0.02;
+
+/// The least a potentiometer's reading may change between the actuators' limits, below which its
+/// noise would swamp the position.
+pub const ACTUATOR_MIN_VOLTS_SPAN: f32 = 0.1;
+
+/// How far apart independently driven actuators may get before the one ahead is slowed down.
+pub const ACTUATOR_TILT_TOLERANCE: f32 = 0.05;
+
+/// The fraction of its usual speed given to an actuator which is ahead of the other.
+pub const ACTUATOR_TILT_SCALE: f32 = 0.5;
+
 /// The speed used by the motors to dump material.
 pub const DUMPING_RATE: f32 = 1.0;
 
 /// The speed used by the dumper to reset it's position.
 pub const DUMPER_RESET_RATE: f32 = -1.0;
 
+/// How long a dump cycle holds the dumper at its upper limit so material can fall out.
+pub const DUMPER_CYCLE_DWELL_MILLIS: u64 = 1000;
+
 // Thresholds for current levels (Currently placeholder)
 pub const NORMAL_CURRENT: f32 = 1.8;
 pub const BROWN_CURRENT: f32 = 2.0;
-pub const CRITICAL_CURRENT: f32 = 3.3;
\ No newline at end of file
+pub const CRITICAL_CURRENT: f32 = 3.3;
+
+/// Whether critical current kills the robot. Off until the current sensor has been calibrated, since
+/// the placeholder calibration would kill the robot on readings which mean nothing.
+pub const KILL_ON_CRITICAL_CURRENT: bool = false;
+
+/// The IIO device and channel of the ADC input the current sensor is wired to.
+pub const CURRENT_ADC_DEVICE: u8 = 0;
+pub const CURRENT_ADC_CHANNEL: u8 = 0;
+
+/// The current sensor's output voltage at zero current, and its sensitivity (Currently placeholder).
+pub const CURRENT_ZERO_VOLTS: f32 = 0.0;
+pub const CURRENT_AMPS_PER_VOLT: f32 = 2.0;
+
+/// The current above which a running motor is considered to be straining (Currently placeholder).
+pub const MOTOR_STALL_CURRENT: f32 = 1.5;
+
+/// How long a motor may strain before it is considered stalled and stopped.
+pub const MOTOR_STALL_MILLIS: u64 = 500;
+
+/// How long a limit switch may stay asserted once a mechanism starts moving away from it before
+/// the switch is considered stuck (Currently placeholder).
+pub const LIMIT_CLEAR_MILLIS: u64 = 1000;
+
+/// The least time a mechanism can take to travel from one limit to the other. Reaching a limit
+/// any sooner means a switch is misreading (Currently placeholder).
+pub const LIMIT_MIN_TRAVEL_MILLIS: u64 = 500;
+
+/// The longest the intake actuators may run at full speed without reaching the limit they are
+/// heading for (Currently placeholder).
+pub const ACTUATOR_TIMEOUT_MILLIS: u64 = 12000;
+
+/// The longest the dumper may run at full speed without reaching the limit it is heading for
+/// (Currently placeholder).
+pub const DUMPER_TIMEOUT_MILLIS: u64 = 5000;
+
+/// The order in which loads are throttled during a brownout, the first to be throttled first.
+pub const LOAD_SHED_ORDER: [Load; 4] = [Load::Digger, Load::Actuators, Load::Dumper, Load::Drive];
+
+/// The fraction of their commanded output which throttled loads are allowed.
+pub const LOAD_SHED_SCALE: f32 = 0.5;
+
+/// How long to wait between throttling or restoring successive loads.
+pub const LOAD_SHED_INTERVAL_MILLIS: u64 = 500;
+
+/// Driver inputs smaller than this are treated as zero. Defaults leave inputs unshaped.
+pub const INPUT_DEADBAND: f32 = 0.0;
+
+/// How far the response to driver inputs is bent from linear towards cubic.
+pub const INPUT_EXPO: f32 = 0.0;
+
+/// The fraction of full speed given at full input.
+pub const INPUT_MAX_SPEED: f32 = 1.0;
+
+/// The fraction of the usual maximum speed allowed in precision mode.
+pub const PRECISION_SCALE: f32 = 0.3;
+
+/// How long the controller waits for a command from the driver station before it brakes the
+/// drive train and stops the intake and dumper.
+pub const WATCHDOG_TIMEOUT_MILLIS: u64 = 1000;
+
+/// How often the controller loop runs, giving it 200 Hz.
+pub const CONTROLLER_PERIOD_MICROS: u64 = 5000;
+
+/// How often the sensor monitor loop runs, giving it 1 kHz so limit switches are read promptly.
+pub const MONITOR_PERIOD_MICROS: u64 = 1000;
+
+/// How many consecutive reads a limit switch must hold a new value for before it is believed.
+/// (Currently placeholder)
+pub const LIMIT_DEBOUNCE_SAMPLES: u32 = 1;
+
+/// How long a limit switch must hold a new value for before it is believed.
+/// (Currently placeholder)
+pub const LIMIT_DEBOUNCE_MILLIS: u64 = 0;
+
+/// The top speed of the simulated robot's wheels, in meters per second.
+pub const SIM_MAX_DRIVE_SPEED: f32 = 1.5;
+
+/// How quickly the simulated wheels can change speed, as a fraction of full speed per second.
+pub const SIM_DRIVE_ACCELERATION: f32 = 4.0;
+
+/// How long the simulated intake actuators take to travel between their limits at full speed.
+pub const SIM_ACTUATOR_TRAVEL_SECONDS: f32 = 2.0;
+
+/// How long the simulated dumper takes to travel between its limits at full speed.
+pub const SIM_DUMPER_TRAVEL_SECONDS: f32 = 1.5;
+
+/// How long the dump routine holds the dumper at its upper limit before lowering it again.
+pub const ROUTINE_DUMP_DWELL_MILLIS: u64 = 1000;
+
+/// How long the dig routine runs the digger once the intake is lowered.
+pub const ROUTINE_DIG_MILLIS: u64 = 5000;
@@ -2,23 +2,29 @@ use std::sync::Arc;
 
 use crate::framework::Runnable;
 use crate::pinouts::analog::input::AnalogInput;
+use crate::robot_config::CurrentConfig;
 use crate::status::current::GlobalCurrentState;
-use rocket::config::Array;
-use crate::robot_map::BROWN_CURRENT;
 
 /// Monitors current and updates it's state accordingly.
+///
+/// The sensor's voltage is converted to amps and averaged over the last three readings. Failed
+/// readings are skipped, leaving the last average in place.
 pub struct CurrentMonitor {
     input: Box<AnalogInput>,
     current: Arc<GlobalCurrentState>,
     old_values: [f32; 3],
+    zero_volts: f32,
+    amps_per_volt: f32,
 }
 
 impl CurrentMonitor {
-    pub fn new(input: Box<AnalogInput>, current: Arc<GlobalCurrentState>) -> Self {
+    pub fn new(input: Box<AnalogInput>, current: Arc<GlobalCurrentState>, config: &CurrentConfig) -> Self {
         Self {
             input,
             current,
             old_values: [0.0; 3],
+            zero_volts: config.get_zero_volts(),
+            amps_per_volt: config.get_amps_per_volt(),
         }
     }
 }
@@ -29,10 +35,73 @@ impl Runnable for CurrentMonitor {
     }
 
     fn run(&mut self) {
-        self.old_values[2] = self.old_values[1];
-        self.old_values[1] = self.old_values[0];
-        self.old_values[0] = self.input.get_value().unwrap();
-        let avg_current : f32 = (self.old_values[0] + self.old_values[1] + self.old_values[2]) / 3.0;
-//        self.current.update_current(avg_current);
+        if let Some(volts) = self.input.get_value() {
+            self.old_values[2] = self.old_values[1];
+            self.old_values[1] = self.old_values[0];
+            self.old_values[0] = (volts - self.zero_volts) * self.amps_per_volt;
+            let avg_current: f32 = (self.old_values[0] + self.old_values[1] + self.old_values[2]) / 3.0;
+            self.current.update_current(avg_current);
+        }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use atomic::Atomic;
+    use atomic::Ordering;
+
+    use crate::pinouts::analog::input::TestAnalogInput;
+    use crate::robot_config::CurrentConfig;
+    use crate::status::current::CurrentUsageLevel;
+
+    use super::*;
+
+    fn setup(config: CurrentConfig) -> (Arc<Atomic<Option<f32>>>, Arc<GlobalCurrentState>, CurrentMonitor) {
+        let value = Arc::new(Atomic::new(Some(0.0)));
+        let current = Arc::new(GlobalCurrentState::new());
+        let monitor = CurrentMonitor::new(Box::new(TestAnalogInput::new(value.clone())), current.clone(), &config);
+
+        (value, current, monitor)
+    }
+
+    #[test]
+    fn average() {
+        let (value, current, mut monitor) = setup(CurrentConfig::default());
+        monitor.init();
+
+        value.store(Some(1.5), Ordering::SeqCst);
+        monitor.run();
+        assert_eq!(1.0, current.get_current());
+
+        monitor.run();
+        monitor.run();
+        assert_eq!(3.0, current.get_current());
+        assert_eq!(CurrentUsageLevel::Brownout, current.get_level());
+    }
+
+    #[test]
+    fn scaling() {
+        let (value, current, mut monitor) = setup(CurrentConfig::default().with_zero_volts(0.5).with_amps_per_volt(10.0));
+
+        value.store(Some(0.75), Ordering::SeqCst);
+        monitor.run();
+        monitor.run();
+        monitor.run();
+
+        assert_eq!(2.5, current.get_current());
+    }
+
+    #[test]
+    fn failed_reading() {
+        let (value, current, mut monitor) = setup(CurrentConfig::default());
+
+        value.store(Some(0.75), Ordering::SeqCst);
+        monitor.run();
+        monitor.run();
+        monitor.run();
+        value.store(None, Ordering::SeqCst);
+        monitor.run();
+
+        assert_eq!(1.5, current.get_current());
+    }
+}
@@ -1,31 +1,172 @@
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
+use std::time::Instant;
 
 use atomic::Ordering;
 
 use crate::framework::Runnable;
 use crate::pinouts::digital::input::DigitalInput;
+use crate::status::limits::GlobalInputState;
 
+/// Copies the value of a digital input into the robot state, ignoring changes which do not last
+/// long enough to pass the debounce settings in `debounce`. A change is accepted as soon as it has
+/// lasted for either the configured number of reads or the configured time.
 pub struct DigitalInputMonitor {
     input: Box<DigitalInput>,
     update_field: Arc<AtomicBool>,
+    debounce: Arc<GlobalInputState>,
+    accepted: bool,
+    /// How many reads the input has differed from the accepted value for, and since when.
+    pending: Option<(usize, Instant)>,
 }
 
 impl Runnable for DigitalInputMonitor {
     fn init(&mut self) {
-        self.update_field.store(self.input.get_value(), Ordering::SeqCst)
+        self.accepted = self.input.get_value();
+        self.pending = None;
+        self.update_field.store(self.accepted, Ordering::SeqCst)
     }
 
     fn run(&mut self) {
-        self.update_field.store(self.input.get_value(), Ordering::SeqCst)
+        self.sample(Instant::now())
     }
 }
 
 impl DigitalInputMonitor {
-    pub fn new(input: Box<DigitalInput>, update_field: Arc<AtomicBool>) -> Self {
+    pub fn new(input: Box<DigitalInput>, update_field: Arc<AtomicBool>, debounce: Arc<GlobalInputState>) -> Self {
         Self {
             input,
             update_field,
+            debounce,
+            accepted: false,
+            pending: None,
         }
     }
-}
\ No newline at end of file
+
+    fn sample(&mut self, now: Instant) {
+        let value = self.input.get_value();
+        if value == self.accepted {
+            if self.pending.take().is_some() {
+                self.debounce.add_glitch();
+            }
+            return;
+        }
+
+        let (reads, since) = match self.pending {
+            Some((reads, since)) => (reads + 1, since),
+            None => (1, now),
+        };
+        let samples = self.debounce.get_samples();
+        let enough_reads = samples > 0 && reads >= samples;
+        let enough_time = self.debounce.get_time().map_or(false, |time| now.duration_since(since) >= time);
+        if enough_reads || enough_time {
+            self.accepted = value;
+            self.pending = None;
+            self.update_field.store(value, Ordering::SeqCst);
+        } else {
+            self.pending = Some((reads, since));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::pinouts::digital::TestPin;
+    use crate::robot_config::DebounceConfig;
+
+    use super::*;
+
+    fn setup(debounce: DebounceConfig) -> (Arc<AtomicBool>, Arc<AtomicBool>, Arc<GlobalInputState>, DigitalInputMonitor) {
+        let pin = Arc::new(AtomicBool::new(false));
+        let field = Arc::new(AtomicBool::new(false));
+        let state = Arc::new(GlobalInputState::new());
+        state.set_debounce(&debounce);
+
+        let mut monitor = DigitalInputMonitor::new(Box::new(TestPin::new(pin.clone())), field.clone(), state.clone());
+        monitor.init();
+
+        (pin, field, state, monitor)
+    }
+
+    #[test]
+    fn undebounced() {
+        let (pin, field, state, mut monitor) = setup(DebounceConfig::default());
+
+        pin.store(true, Ordering::SeqCst);
+        monitor.run();
+        assert_eq!(true, field.load(Ordering::SeqCst));
+
+        pin.store(false, Ordering::SeqCst);
+        monitor.run();
+        assert_eq!(false, field.load(Ordering::SeqCst));
+        assert_eq!(0, state.get_glitches());
+    }
+
+    #[test]
+    fn samples() {
+        let (pin, field, state, mut monitor) = setup(DebounceConfig::default().with_samples(3));
+        let start = Instant::now();
+
+        pin.store(true, Ordering::SeqCst);
+        monitor.sample(start);
+        monitor.sample(start);
+        assert_eq!(false, field.load(Ordering::SeqCst));
+
+        monitor.sample(start);
+        assert_eq!(true, field.load(Ordering::SeqCst));
+        assert_eq!(0, state.get_glitches());
+    }
+
+    #[test]
+    fn time() {
+        let (pin, field, _, mut monitor) = setup(DebounceConfig::default().with_samples(0).with_millis(20));
+        let start = Instant::now();
+
+        pin.store(true, Ordering::SeqCst);
+        monitor.sample(start);
+        monitor.sample(start + Duration::from_millis(10));
+        assert_eq!(false, field.load(Ordering::SeqCst));
+
+        monitor.sample(start + Duration::from_millis(20));
+        assert_eq!(true, field.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn samples_or_time() {
+        let (pin, field, _, mut monitor) = setup(DebounceConfig::default().with_samples(3).with_millis(20));
+        let start = Instant::now();
+
+        // Three quick reads are enough on their own.
+        pin.store(true, Ordering::SeqCst);
+        monitor.sample(start);
+        monitor.sample(start);
+        monitor.sample(start);
+        assert_eq!(true, field.load(Ordering::SeqCst));
+
+        // So are two slow ones.
+        pin.store(false, Ordering::SeqCst);
+        monitor.sample(start);
+        assert_eq!(true, field.load(Ordering::SeqCst));
+        monitor.sample(start + Duration::from_millis(20));
+        assert_eq!(false, field.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn glitches_are_counted() {
+        let (pin, field, state, mut monitor) = setup(DebounceConfig::default().with_samples(2));
+        let start = Instant::now();
+
+        pin.store(true, Ordering::SeqCst);
+        monitor.sample(start);
+        pin.store(false, Ordering::SeqCst);
+        monitor.sample(start);
+        monitor.sample(start);
+
+        assert_eq!(false, field.load(Ordering::SeqCst));
+        assert_eq!(1, state.get_glitches());
+        assert_eq!(1, state.get_current_state().get_glitches());
+        assert_eq!(2, state.get_current_state().get_samples());
+    }
+}
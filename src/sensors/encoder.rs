@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::framework::Runnable;
+use crate::pinouts::encoder::Encoder;
+use crate::pinouts::encoder::GlobalEncoderState;
+
+/// Samples an encoder on every run of the monitor and publishes its count, so that encoders decoded
+/// in software are read far more often than the controller runs.
+pub struct EncoderMonitor {
+    encoder: Box<Encoder>,
+    state: Arc<GlobalEncoderState>,
+}
+
+impl Runnable for EncoderMonitor {
+    fn init(&mut self) {
+        self.publish();
+    }
+
+    fn run(&mut self) {
+        self.encoder.update();
+        self.publish();
+    }
+}
+
+impl EncoderMonitor {
+    pub fn new(encoder: Box<Encoder>, state: Arc<GlobalEncoderState>) -> Self {
+        Self {
+            encoder,
+            state,
+        }
+    }
+
+    fn publish(&self) {
+        self.state.set_count(self.encoder.get_count());
+        self.state.set_missed(self.encoder.get_missed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+
+    use crate::pinouts::digital::TestPin;
+    use crate::pinouts::encoder::quadrature::QuadratureEncoder;
+
+    use super::*;
+
+    #[test]
+    fn publishes_count() {
+        let a = Arc::new(AtomicBool::new(false));
+        let b = Arc::new(AtomicBool::new(false));
+        let state = Arc::new(GlobalEncoderState::new());
+        let encoder = QuadratureEncoder::new(Box::new(TestPin::new(a.clone())), Box::new(TestPin::new(b.clone())));
+        let mut monitor = EncoderMonitor::new(Box::new(encoder), state.clone());
+        monitor.init();
+
+        a.store(true, Ordering::SeqCst);
+        monitor.run();
+        b.store(true, Ordering::SeqCst);
+        monitor.run();
+        assert_eq!(2, state.get_count());
+        assert_eq!(0, state.get_missed());
+
+        a.store(false, Ordering::SeqCst);
+        b.store(false, Ordering::SeqCst);
+        monitor.run();
+        assert_eq!(2, state.get_count());
+        assert_eq!(1, state.get_missed());
+        assert_eq!(1, state.get_current_state().get_missed());
+    }
+}
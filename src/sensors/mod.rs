@@ -1,2 +1,3 @@
 pub mod current;
-pub mod digital;
\ No newline at end of file
+pub mod digital;
+pub mod encoder;
\ No newline at end of file
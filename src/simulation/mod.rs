@@ -0,0 +1,149 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+use crate::framework::Runnable;
+use crate::framework::seconds;
+use crate::robot_config::OdometryConfig;
+use crate::robot_map::*;
+use crate::status::robot_state::GlobalRobotState;
+
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+/// Moves a simulated robot according to the speeds commanded to its motors.
+///
+/// The wheels accelerate towards their commanded speeds rather than jumping to them, the robot's
+/// pose is moved with the same skid-steer model the drive train's odometry uses, and the drive
+/// motors report the positions their encoders would. The intake actuators and dumper travel between
+/// their limits, setting the limit flags in the robot state when they reach either end. When each
+/// intake actuator has its own motor, each side travels at its own motor's speed and sets only its
+/// own limits.
+pub struct PhysicsSimulation {
+    state: Arc<GlobalRobotState>,
+    odometry: OdometryConfig,
+    dual_actuators: bool,
+    /// How far each side's wheels have travelled, in meters.
+    wheel_travel: (f64, f64),
+    last_step: Instant,
+}
+
+impl Runnable for PhysicsSimulation {
+    fn init(&mut self) {
+        self.last_step = Instant::now();
+        self.update_limits();
+    }
+
+    fn run(&mut self) {
+        let now = Instant::now();
+        let elapsed = now - self.last_step;
+        self.last_step = now;
+        self.step(seconds(elapsed));
+    }
+}
+
+impl PhysicsSimulation {
+    pub fn new(state: Arc<GlobalRobotState>, odometry: OdometryConfig, dual_actuators: bool) -> Self {
+        Self {
+            state,
+            odometry,
+            dual_actuators,
+            wheel_travel: (0.0, 0.0),
+            last_step: Instant::now(),
+        }
+    }
+
+    /// Advances the simulation by `dt` seconds.
+    pub fn step(&mut self, dt: f32) {
+        self.step_drive(dt);
+        self.step_actuators(dt);
+        self.step_dumper(dt);
+        self.update_limits();
+    }
+
+    fn step_drive(&mut self, dt: f32) {
+        let simulation = self.state.get_simulation();
+        let drive = self.state.get_drive();
+
+        let left = approach(simulation.get_left_velocity(), drive.get_left().get_speed(), SIM_DRIVE_ACCELERATION * dt);
+        let right = approach(simulation.get_right_velocity(), drive.get_right().get_speed(), SIM_DRIVE_ACCELERATION * dt);
+        simulation.set_velocities(left, right);
+
+        let left_speed = left * SIM_MAX_DRIVE_SPEED;
+        let right_speed = right * SIM_MAX_DRIVE_SPEED;
+        let pose = simulation.get_pose().drive(left_speed * dt, right_speed * dt, self.odometry.get_track_width());
+        simulation.set_pose(pose);
+
+        // The travel is kept as f64 so the encoder counts don't stop changing on a long run.
+        self.wheel_travel.0 += f64::from(left_speed * dt);
+        self.wheel_travel.1 += f64::from(right_speed * dt);
+
+        let meters_per_count = self.odometry.get_meters_per_count();
+        drive.get_left().set_velocity(Some(left_speed / meters_per_count));
+        drive.get_right().set_velocity(Some(right_speed / meters_per_count));
+        drive.get_left().set_position(Some(to_counts(self.wheel_travel.0, meters_per_count)));
+        drive.get_right().set_position(Some(to_counts(self.wheel_travel.1, meters_per_count)));
+    }
+
+    fn step_actuators(&self, dt: f32) {
+        let simulation = self.state.get_simulation();
+        let intake = self.state.get_intake();
+        let left_speed = intake.get_actuator().get_speed();
+        let right_speed = if self.dual_actuators {
+            intake.get_right_actuator_motor().get_speed()
+        } else {
+            left_speed
+        };
+
+        let left = simulation.get_left_actuator_position() + left_speed * dt / SIM_ACTUATOR_TRAVEL_SECONDS;
+        let right = simulation.get_right_actuator_position() + right_speed * dt / SIM_ACTUATOR_TRAVEL_SECONDS;
+        simulation.set_actuator_positions(clamp_position(left), clamp_position(right));
+    }
+
+    fn step_dumper(&self, dt: f32) {
+        let simulation = self.state.get_simulation();
+        let speed = self.state.get_dumper().get_motor().get_speed();
+        let position = simulation.get_dumper_position() + speed * dt / SIM_DUMPER_TRAVEL_SECONDS;
+        simulation.set_dumper_position(clamp_position(position));
+    }
+
+    fn update_limits(&self) {
+        let simulation = self.state.get_simulation();
+        let intake = self.state.get_intake();
+        let dumper = self.state.get_dumper();
+
+        let sides = [
+            (intake.get_left_actuator(), simulation.get_left_actuator_position()),
+            (intake.get_right_actuator(), simulation.get_right_actuator_position()),
+        ];
+        for (limits, position) in sides.iter() {
+            limits.set_upper(*position >= 1.0);
+            limits.set_lower(*position <= 0.0);
+        }
+
+        let dumper_position = simulation.get_dumper_position();
+        dumper.get_upper_limit().store(dumper_position >= 1.0, Ordering::SeqCst);
+        dumper.get_lower_limit().store(dumper_position <= 0.0, Ordering::SeqCst);
+    }
+}
+
+/// Moves `current` towards `target` by at most `max_change`.
+fn approach(current: f32, target: f32, max_change: f32) -> f32 {
+    if (target - current).abs() <= max_change {
+        target
+    } else if target > current {
+        current + max_change
+    } else {
+        current - max_change
+    }
+}
+
+fn to_counts(travel: f64, meters_per_count: f32) -> i64 {
+    (travel / f64::from(meters_per_count)).round() as i64
+}
+
+fn clamp_position(position: f32) -> f32 {
+    position.max(0.0).min(1.0)
+}
@@ -0,0 +1,126 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use atomic::Atomic;
+use atomic::Ordering as AtOrd;
+
+use crate::mechatronics::drive_train::odometry::Pose;
+
+/// The simulated physical state of the robot: where it is on the field, how fast its wheels are
+/// actually turning, and how far the intake actuators and dumper have travelled.
+pub struct GlobalSimulationState {
+    enabled: AtomicBool,
+    pose: Atomic<Pose>,
+    left_velocity: Atomic<f32>,
+    right_velocity: Atomic<f32>,
+    left_actuator_position: Atomic<f32>,
+    right_actuator_position: Atomic<f32>,
+    dumper_position: Atomic<f32>,
+}
+
+impl GlobalSimulationState {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            pose: Atomic::new(Pose::default()),
+            left_velocity: Atomic::new(0.0),
+            right_velocity: Atomic::new(0.0),
+            left_actuator_position: Atomic::new(0.0),
+            right_actuator_position: Atomic::new(0.0),
+            dumper_position: Atomic::new(0.0),
+        }
+    }
+
+    pub fn get_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Where the simulated robot actually is, relative to where it started.
+    pub fn get_pose(&self) -> Pose {
+        self.pose.load(AtOrd::Relaxed)
+    }
+
+    pub fn set_pose(&self, pose: Pose) {
+        self.pose.store(pose, AtOrd::Relaxed);
+    }
+
+    pub fn get_left_velocity(&self) -> f32 {
+        self.left_velocity.load(AtOrd::Relaxed)
+    }
+
+    pub fn get_right_velocity(&self) -> f32 {
+        self.right_velocity.load(AtOrd::Relaxed)
+    }
+
+    pub fn set_velocities(&self, left: f32, right: f32) {
+        self.left_velocity.store(left, AtOrd::Relaxed);
+        self.right_velocity.store(right, AtOrd::Relaxed);
+    }
+
+    /// Returns how far the left intake actuator has extended, from 0 (fully lowered) to 1 (fully
+    /// raised).
+    pub fn get_left_actuator_position(&self) -> f32 {
+        self.left_actuator_position.load(AtOrd::Relaxed)
+    }
+
+    pub fn get_right_actuator_position(&self) -> f32 {
+        self.right_actuator_position.load(AtOrd::Relaxed)
+    }
+
+    pub fn set_actuator_positions(&self, left: f32, right: f32) {
+        self.left_actuator_position.store(left, AtOrd::Relaxed);
+        self.right_actuator_position.store(right, AtOrd::Relaxed);
+    }
+
+    /// Returns how far the dumper has travelled, from 0 (reset) to 1 (fully dumped).
+    pub fn get_dumper_position(&self) -> f32 {
+        self.dumper_position.load(AtOrd::Relaxed)
+    }
+
+    pub fn set_dumper_position(&self, position: f32) {
+        self.dumper_position.store(position, AtOrd::Relaxed);
+    }
+
+    pub fn get_current_state(&self) -> SimulationStateInstance {
+        SimulationStateInstance {
+            pose: self.get_pose(),
+            left_velocity: self.get_left_velocity(),
+            right_velocity: self.get_right_velocity(),
+            left_actuator_position: self.get_left_actuator_position(),
+            right_actuator_position: self.get_right_actuator_position(),
+            dumper_position: self.get_dumper_position(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SimulationStateInstance {
+    pose: Pose,
+    left_velocity: f32,
+    right_velocity: f32,
+    left_actuator_position: f32,
+    right_actuator_position: f32,
+    dumper_position: f32,
+}
+
+impl SimulationStateInstance {
+    pub fn get_pose(&self) -> Pose {
+        self.pose
+    }
+
+    pub fn get_left_actuator_position(&self) -> f32 {
+        self.left_actuator_position
+    }
+
+    pub fn get_right_actuator_position(&self) -> f32 {
+        self.right_actuator_position
+    }
+
+    pub fn get_dumper_position(&self) -> f32 {
+        self.dumper_position
+    }
+}
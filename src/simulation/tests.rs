@@ -0,0 +1,132 @@
+use std::f32::consts::PI;
+
+use super::*;
+
+fn setup() -> (Arc<GlobalRobotState>, PhysicsSimulation) {
+    setup_actuators(false)
+}
+
+fn setup_actuators(dual_actuators: bool) -> (Arc<GlobalRobotState>, PhysicsSimulation) {
+    let state = Arc::new(GlobalRobotState::new());
+    let mut simulation = PhysicsSimulation::new(state.clone(), OdometryConfig::default(), dual_actuators);
+    simulation.init();
+    (state, simulation)
+}
+
+#[test]
+fn initial_limits() {
+    let (state, _simulation) = setup();
+    assert_eq!(true, state.get_intake().get_left_actuator().get_current_state().get_lower());
+    assert_eq!(true, state.get_intake().get_right_actuator().get_current_state().get_lower());
+    assert_eq!(true, state.get_dumper().get_lower_limit().load(Ordering::SeqCst));
+    assert_eq!(false, state.get_dumper().get_upper_limit().load(Ordering::SeqCst));
+}
+
+#[test]
+fn drive_inertia() {
+    let (state, mut simulation) = setup();
+    state.get_drive().get_left().set_speed(1.0);
+    state.get_drive().get_right().set_speed(1.0);
+
+    simulation.step(0.1);
+    let velocity = state.get_simulation().get_left_velocity();
+    assert!(velocity > 0.0 && velocity < 1.0);
+
+    for _ in 0..10 {
+        simulation.step(0.1);
+    }
+    assert_eq!(1.0, state.get_simulation().get_left_velocity());
+    assert_eq!(1.0, state.get_simulation().get_right_velocity());
+}
+
+#[test]
+fn drive_straight() {
+    let (state, mut simulation) = setup();
+    state.get_simulation().set_velocities(1.0, 1.0);
+    state.get_drive().get_left().set_speed(1.0);
+    state.get_drive().get_right().set_speed(1.0);
+
+    simulation.step(1.0);
+    let pose = state.get_simulation().get_pose();
+    assert_eq!(SIM_MAX_DRIVE_SPEED, pose.get_x());
+    assert_eq!(0.0, pose.get_y());
+    assert_eq!(0.0, pose.get_heading());
+}
+
+#[test]
+fn encoders_follow_wheels() {
+    let (state, mut simulation) = setup();
+    state.get_simulation().set_velocities(1.0, -1.0);
+    state.get_drive().get_left().set_speed(1.0);
+    state.get_drive().get_right().set_speed(-1.0);
+
+    simulation.step(1.0);
+    simulation.step(1.0);
+    let counts = 2.0 * SIM_MAX_DRIVE_SPEED / OdometryConfig::default().get_meters_per_count();
+    assert_eq!(Some(counts.round() as i64), state.get_drive().get_left().get_position());
+    assert_eq!(Some(-counts.round() as i64), state.get_drive().get_right().get_position());
+    assert_eq!(Some(counts / 2.0), state.get_drive().get_left().get_velocity());
+}
+
+#[test]
+fn turn_in_place() {
+    let (state, mut simulation) = setup();
+    state.get_simulation().set_velocities(-1.0, 1.0);
+    state.get_drive().get_left().set_speed(-1.0);
+    state.get_drive().get_right().set_speed(1.0);
+
+    let steps = (PI * TRACK_WIDTH / (2.0 * SIM_MAX_DRIVE_SPEED) / 0.01) as usize;
+    for _ in 0..steps {
+        simulation.step(0.01);
+    }
+
+    let pose = state.get_simulation().get_pose();
+    assert_eq!(0.0, pose.get_x());
+    assert_eq!(0.0, pose.get_y());
+    assert!((pose.get_heading() - PI).abs() < 0.1);
+}
+
+#[test]
+fn actuators_reach_upper_limit() {
+    let (state, mut simulation) = setup();
+    state.get_intake().get_actuator().set_speed(1.0);
+
+    simulation.step(SIM_ACTUATOR_TRAVEL_SECONDS / 2.0);
+    assert_eq!(false, state.get_intake().get_left_actuator().get_current_state().get_lower());
+    assert_eq!(false, state.get_intake().get_left_actuator().get_current_state().get_upper());
+
+    simulation.step(SIM_ACTUATOR_TRAVEL_SECONDS);
+    assert_eq!(1.0, state.get_simulation().get_left_actuator_position());
+    assert_eq!(1.0, state.get_simulation().get_right_actuator_position());
+    assert_eq!(true, state.get_intake().get_left_actuator().get_current_state().get_upper());
+    assert_eq!(true, state.get_intake().get_right_actuator().get_current_state().get_upper());
+}
+
+#[test]
+fn dual_actuators_travel_separately() {
+    let (state, mut simulation) = setup_actuators(true);
+    state.get_intake().get_right_actuator_motor().set_speed(1.0);
+
+    simulation.step(SIM_ACTUATOR_TRAVEL_SECONDS);
+    assert_eq!(0.0, state.get_simulation().get_left_actuator_position());
+    assert_eq!(1.0, state.get_simulation().get_right_actuator_position());
+    assert_eq!(true, state.get_intake().get_left_actuator().get_current_state().get_lower());
+    assert_eq!(false, state.get_intake().get_left_actuator().get_current_state().get_upper());
+    assert_eq!(true, state.get_intake().get_right_actuator().get_current_state().get_upper());
+    assert_eq!(false, state.get_intake().get_right_actuator().get_current_state().get_lower());
+}
+
+#[test]
+fn dumper_travels() {
+    let (state, mut simulation) = setup();
+    state.get_dumper().get_motor().set_speed(DUMPING_RATE);
+
+    simulation.step(SIM_DUMPER_TRAVEL_SECONDS * 2.0);
+    assert_eq!(true, state.get_dumper().get_upper_limit().load(Ordering::SeqCst));
+    assert_eq!(false, state.get_dumper().get_lower_limit().load(Ordering::SeqCst));
+
+    state.get_dumper().get_motor().set_speed(DUMPER_RESET_RATE);
+    simulation.step(SIM_DUMPER_TRAVEL_SECONDS * 2.0);
+    assert_eq!(0.0, state.get_simulation().get_dumper_position());
+    assert_eq!(true, state.get_dumper().get_lower_limit().load(Ordering::SeqCst));
+}
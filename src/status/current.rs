@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
 
 use atomic::{Atomic, Ordering};
 
@@ -16,6 +17,9 @@ pub enum CurrentUsageLevel {
 pub struct GlobalCurrentState {
     current: Arc<Atomic<f32>>,
     level: Arc<Atomic<CurrentUsageLevel>>,
+    brownout: Arc<Atomic<f32>>,
+    critical: Arc<Atomic<f32>>,
+    shed: Arc<AtomicUsize>,
 }
 
 impl GlobalCurrentState {
@@ -23,9 +27,35 @@ impl GlobalCurrentState {
         Self {
             current: Arc::new(Atomic::new(0.0)),
             level: Arc::new(Atomic::new(CurrentUsageLevel::Normal)),
+            brownout: Arc::new(Atomic::new(BROWN_CURRENT)),
+            critical: Arc::new(Atomic::new(CRITICAL_CURRENT)),
+            shed: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// Sets the currents at which the usage level becomes `Brownout` and `Critical`.
+    pub fn set_thresholds(&self, brownout: f32, critical: f32) {
+        self.brownout.store(brownout, Ordering::Relaxed);
+        self.critical.store(critical, Ordering::Relaxed);
+    }
+
+    pub fn get_brownout(&self) -> f32 {
+        self.brownout.load(Ordering::Relaxed)
+    }
+
+    pub fn get_critical(&self) -> f32 {
+        self.critical.load(Ordering::Relaxed)
+    }
+
+    /// Records how many loads are being throttled to bring the current out of brownout.
+    pub fn set_shed(&self, shed: usize) {
+        self.shed.store(shed, Ordering::Relaxed);
+    }
+
+    pub fn get_shed(&self) -> usize {
+        self.shed.load(Ordering::Relaxed)
+    }
+
     pub fn get_current(&self) -> f32 {
         self.current.load(Ordering::Relaxed)
     }
@@ -39,11 +69,11 @@ impl GlobalCurrentState {
         CurrentUsageLevel::Normal == self.get_level()
     }
 
-    pub fn update_current(&mut self, current: f32) {
+    pub fn update_current(&self, current: f32) {
         self.current.store(current, Ordering::Relaxed);
-        if current >= CRITICAL_CURRENT {
+        if current >= self.critical.load(Ordering::Relaxed) {
             self.level.store(Critical, Ordering::Relaxed);
-        } else if current >= BROWN_CURRENT {
+        } else if current >= self.brownout.load(Ordering::Relaxed) {
             self.level.store(Brownout, Ordering::Relaxed);
         } else {
             self.level.store(Normal, Ordering::Relaxed);
@@ -52,7 +82,8 @@ impl GlobalCurrentState {
 
     pub fn get_json(&self) -> CurrentStateJson {
         CurrentStateJson::new(self.current.load(Ordering::Relaxed),
-                              self.level.load(Ordering::Relaxed))
+                              self.level.load(Ordering::Relaxed),
+                              self.shed.load(Ordering::Relaxed))
     }
 }
 
@@ -60,13 +91,15 @@ impl GlobalCurrentState {
 pub struct CurrentStateJson {
     current: f32,
     level: CurrentUsageLevel,
+    shed: usize,
 }
 
 impl CurrentStateJson {
-    fn new(current: f32, level: CurrentUsageLevel) -> Self {
+    fn new(current: f32, level: CurrentUsageLevel, shed: usize) -> Self {
         Self {
             current,
             level,
+            shed,
         }
     }
 }
@@ -87,7 +120,7 @@ mod tests {
 
     #[test]
     fn update_normal() {
-        let mut state = GlobalCurrentState::new();
+        let state = GlobalCurrentState::new();
 
         state.update_current(CRITICAL_CURRENT);
         state.update_current(NORMAL_CURRENT);
@@ -99,7 +132,7 @@ mod tests {
 
     #[test]
     fn update_brownout() {
-        let mut state = GlobalCurrentState::new();
+        let state = GlobalCurrentState::new();
 
         state.update_current(BROWN_CURRENT);
 
@@ -110,7 +143,7 @@ mod tests {
 
     #[test]
     fn update_critical() {
-        let mut state = GlobalCurrentState::new();
+        let state = GlobalCurrentState::new();
 
         state.update_current(CRITICAL_CURRENT);
 
@@ -118,4 +151,19 @@ mod tests {
         assert_eq!(CRITICAL_CURRENT, state.get_current());
         assert_eq!(false, state.is_normal());
     }
+
+    #[test]
+    fn custom_thresholds() {
+        let state = GlobalCurrentState::new();
+        state.set_thresholds(10.0, 20.0);
+
+        state.update_current(CRITICAL_CURRENT);
+        assert_eq!(CurrentUsageLevel::Normal, state.get_level());
+
+        state.update_current(15.0);
+        assert_eq!(CurrentUsageLevel::Brownout, state.get_level());
+
+        state.update_current(20.0);
+        assert_eq!(CurrentUsageLevel::Critical, state.get_level());
+    }
 }
\ No newline at end of file
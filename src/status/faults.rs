@@ -0,0 +1,200 @@
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use crate::recording::unix_millis;
+
+/// How serious a fault is.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub enum Severity {
+    /// Something is wrong, but the robot can carry on as normal.
+    Warning,
+    /// Part of the robot is not working.
+    Error,
+    /// The robot cannot be operated safely, and is killed if the controller is configured to.
+    Critical,
+}
+
+/// The part of the robot a fault was raised by.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Subsystem {
+    /// The GPIO pins and PWM channels.
+    Pinouts,
+    /// The power supply and the current drawn from it.
+    Power,
+    /// The link to the driver station.
+    Comms,
+    /// The drive train and its encoders.
+    Drive,
+    /// The digger and the actuators which raise and lower it.
+    Intake,
+    /// The dumper.
+    Dumper,
+}
+
+/// A fault which has been raised at least once since it was last cleared.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct Fault {
+    code: String,
+    subsystem: Subsystem,
+    severity: Severity,
+    /// The message given the last time the fault was raised.
+    message: String,
+    /// When the fault was first raised, in milliseconds since the Unix epoch.
+    first_seen: u64,
+    /// When the fault was last raised, in milliseconds since the Unix epoch.
+    last_seen: u64,
+    count: usize,
+}
+
+impl Fault {
+    pub fn get_code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn get_subsystem(&self) -> Subsystem {
+        self.subsystem
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn get_first_seen(&self) -> u64 {
+        self.first_seen
+    }
+
+    pub fn get_last_seen(&self) -> u64 {
+        self.last_seen
+    }
+
+    /// How many times the fault has been raised since it was last cleared.
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Every fault raised anywhere on the robot, keyed by a code which identifies what went wrong.
+///
+/// Faults stay in the registry until they are cleared, so a fault which happened once while
+/// nobody was watching can still be seen later.
+pub struct GlobalFaultState {
+    faults: Mutex<Vec<Fault>>,
+    kill_on_critical: AtomicBool,
+}
+
+impl GlobalFaultState {
+    pub fn new() -> Self {
+        Self {
+            faults: Mutex::new(Vec::new()),
+            kill_on_critical: AtomicBool::new(false),
+        }
+    }
+
+    /// Records that the fault `code` has happened.
+    ///
+    /// Raising a fault which is already in the registry counts another occurrence of it; its
+    /// severity is only ever raised.
+    pub fn raise(&self, code: &str, subsystem: Subsystem, severity: Severity, message: &str) {
+        let now = unix_millis();
+        let mut faults = self.lock();
+
+        match faults.iter_mut().find(|fault| fault.code == code) {
+            Some(fault) => {
+                fault.subsystem = subsystem;
+                if severity > fault.severity {
+                    fault.severity = severity;
+                }
+                fault.message = message.to_owned();
+                fault.last_seen = now;
+                fault.count += 1;
+            }
+            None => faults.push(Fault {
+                code: code.to_owned(),
+                subsystem,
+                severity,
+                message: message.to_owned(),
+                first_seen: now,
+                last_seen: now,
+                count: 1,
+            }),
+        }
+    }
+
+    /// Removes the fault `code` from the registry, returning whether it was there.
+    pub fn clear(&self, code: &str) -> bool {
+        let mut faults = self.lock();
+        let count = faults.len();
+        faults.retain(|fault| fault.code != code);
+        faults.len() != count
+    }
+
+    pub fn get_fault(&self, code: &str) -> Option<Fault> {
+        self.lock().iter().find(|fault| fault.code == code).cloned()
+    }
+
+    /// Whether any critical fault has been raised and not yet cleared.
+    pub fn has_critical(&self) -> bool {
+        self.lock().iter().any(|fault| fault.severity == Severity::Critical)
+    }
+
+    /// Whether the controller kills the robot while a critical fault is in the registry.
+    pub fn get_kill_on_critical(&self) -> bool {
+        self.kill_on_critical.load(Ordering::Relaxed)
+    }
+
+    pub fn set_kill_on_critical(&self, kill: bool) {
+        self.kill_on_critical.store(kill, Ordering::Relaxed)
+    }
+
+    /// Returns every fault in the registry, in the order they were first raised.
+    pub fn get_current_state(&self) -> Vec<Fault> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> MutexGuard<Vec<Fault>> {
+        // A panic while holding the lock cannot leave the list half updated, so it is still usable.
+        self.faults.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raise() {
+        let faults = GlobalFaultState::new();
+
+        faults.raise("gpio45", Subsystem::Pinouts, Severity::Error, "first");
+        faults.raise("gpio45", Subsystem::Pinouts, Severity::Warning, "second");
+        faults.raise("watchdog", Subsystem::Comms, Severity::Warning, "");
+
+        let fault = faults.get_fault("gpio45").unwrap();
+        assert_eq!(2, fault.get_count());
+        assert_eq!(Severity::Error, fault.get_severity());
+        assert_eq!("second", fault.get_message());
+        assert!(fault.get_first_seen() <= fault.get_last_seen());
+
+        let codes: Vec<String> = faults.get_current_state().iter().map(|fault| fault.get_code().to_owned()).collect();
+        assert_eq!(vec!["gpio45", "watchdog"], codes);
+    }
+
+    #[test]
+    fn clear() {
+        let faults = GlobalFaultState::new();
+        faults.raise("critical_current", Subsystem::Power, Severity::Critical, "");
+        assert!(faults.has_critical());
+
+        assert!(faults.clear("critical_current"));
+        assert!(!faults.clear("critical_current"));
+        assert!(!faults.has_critical());
+        assert_eq!(None, faults.get_fault("critical_current"));
+    }
+}
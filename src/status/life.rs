@@ -1,14 +1,29 @@
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use atomic::Atomic;
+
+/// Why the robot was last killed.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum KillReason {
+    /// The driver asked for the robot to be killed.
+    Operator,
+    /// The robot drew a critical amount of current.
+    CriticalCurrent,
+    /// A critical fault was raised and has not been cleared.
+    CriticalFault,
+}
+
 pub struct GlobalLifeState {
-    life: AtomicBool
+    life: AtomicBool,
+    reason: Atomic<Option<KillReason>>,
 }
 
 impl GlobalLifeState {
     pub fn new() -> Self {
         Self {
-            life: AtomicBool::new(true)
+            life: AtomicBool::new(true),
+            reason: Atomic::new(None),
         }
     }
 
@@ -17,33 +32,50 @@ impl GlobalLifeState {
     }
 
     pub fn kill(&self) {
+        self.kill_because(KillReason::Operator)
+    }
+
+    pub fn kill_because(&self, reason: KillReason) {
+        self.reason.store(Some(reason), atomic::Ordering::SeqCst);
         self.life.store(false, atomic::Ordering::SeqCst)
     }
 
     pub fn revive(&self) {
+        self.reason.store(None, atomic::Ordering::SeqCst);
         self.life.store(true, atomic::Ordering::SeqCst)
     }
 
+    /// Returns why the robot was killed, or `None` if it is alive.
+    pub fn get_reason(&self) -> Option<KillReason> {
+        self.reason.load(Ordering::Relaxed)
+    }
+
     pub fn get_current_state(&self) -> LifeStateInstance {
-        LifeStateInstance::new(self.life.load(Ordering::Relaxed))
+        LifeStateInstance::new(self.life.load(Ordering::Relaxed), self.reason.load(Ordering::Relaxed))
     }
 }
 
 #[derive(Serialize)]
 pub struct LifeStateInstance {
-    life: bool
+    life: bool,
+    reason: Option<KillReason>,
 }
 
 impl LifeStateInstance {
-    fn new(life: bool) -> Self {
+    fn new(life: bool, reason: Option<KillReason>) -> Self {
         Self {
-            life
+            life,
+            reason,
         }
     }
 
     pub fn get_life(&self) -> bool {
         self.life
     }
+
+    pub fn get_reason(&self) -> Option<KillReason> {
+        self.reason
+    }
 }
 
 #[cfg(test)]
@@ -53,7 +85,8 @@ mod tests {
     impl GlobalLifeState {
         fn create_dead() -> Self {
             Self {
-                life: AtomicBool::new(false)
+                life: AtomicBool::new(false),
+                reason: Atomic::new(Some(KillReason::Operator)),
             }
         }
     }
@@ -81,5 +114,16 @@ mod tests {
         status.revive();
 
         assert!(status.is_alive());
+        assert_eq!(None, status.get_reason());
+    }
+
+    #[test]
+    fn test_kill_reason() {
+        let status = GlobalLifeState::new();
+
+        status.kill_because(KillReason::CriticalCurrent);
+
+        assert!(!status.is_alive());
+        assert_eq!(Some(KillReason::CriticalCurrent), status.get_current_state().get_reason());
     }
 }
\ No newline at end of file
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use atomic::Atomic;
+
+use crate::robot_config::DebounceConfig;
+use crate::robot_config::LimitsConfig;
+use crate::robot_map::LIMIT_DEBOUNCE_MILLIS;
+use crate::robot_map::LIMIT_DEBOUNCE_SAMPLES;
+
+/// How the reading of a single limit switch is debounced, and how many glitches have been
+/// rejected on it.
+///
+/// A glitch is a change in the raw reading which reverted before it had been stable for long
+/// enough to be accepted.
+pub struct GlobalInputState {
+    samples: AtomicUsize,
+    millis: Atomic<u64>,
+    glitches: AtomicUsize,
+}
+
+impl GlobalInputState {
+    pub fn new() -> Self {
+        Self {
+            samples: AtomicUsize::new(LIMIT_DEBOUNCE_SAMPLES as usize),
+            millis: Atomic::new(LIMIT_DEBOUNCE_MILLIS),
+            glitches: AtomicUsize::new(0),
+        }
+    }
+
+    /// How many consecutive reads a new value must last before it is accepted, or 0 if it may last
+    /// any number of reads.
+    pub fn get_samples(&self) -> usize {
+        self.samples.load(Ordering::Relaxed)
+    }
+
+    /// How long a new value must last before it is accepted, or `None` if it may last any time.
+    pub fn get_time(&self) -> Option<Duration> {
+        match self.millis.load(Ordering::Relaxed) {
+            0 => None,
+            millis => Some(Duration::from_millis(millis)),
+        }
+    }
+
+    pub fn set_debounce(&self, config: &DebounceConfig) {
+        self.samples.store(config.get_samples() as usize, Ordering::Relaxed);
+        self.millis.store(config.get_millis(), Ordering::Relaxed);
+    }
+
+    pub fn get_glitches(&self) -> usize {
+        self.glitches.load(Ordering::Relaxed)
+    }
+
+    pub fn add_glitch(&self) {
+        self.glitches.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn get_current_state(&self) -> InputStateInstance {
+        InputStateInstance::new(
+            self.samples.load(Ordering::Relaxed),
+            self.millis.load(Ordering::Relaxed),
+            self.glitches.load(Ordering::Relaxed),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct InputStateInstance {
+    samples: usize,
+    millis: u64,
+    glitches: usize,
+}
+
+impl InputStateInstance {
+    fn new(samples: usize, millis: u64, glitches: usize) -> Self {
+        Self {
+            samples,
+            millis,
+            glitches,
+        }
+    }
+
+    pub fn get_samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn get_millis(&self) -> u64 {
+        self.millis
+    }
+
+    pub fn get_glitches(&self) -> usize {
+        self.glitches
+    }
+}
+
+/// The debouncing of every limit switch on the robot.
+pub struct GlobalLimitsState {
+    intake_left_upper: Arc<GlobalInputState>,
+    intake_left_lower: Arc<GlobalInputState>,
+    intake_right_upper: Arc<GlobalInputState>,
+    intake_right_lower: Arc<GlobalInputState>,
+    dumper_upper: Arc<GlobalInputState>,
+    dumper_lower: Arc<GlobalInputState>,
+}
+
+impl GlobalLimitsState {
+    pub fn new() -> Self {
+        Self {
+            intake_left_upper: Arc::new(GlobalInputState::new()),
+            intake_left_lower: Arc::new(GlobalInputState::new()),
+            intake_right_upper: Arc::new(GlobalInputState::new()),
+            intake_right_lower: Arc::new(GlobalInputState::new()),
+            dumper_upper: Arc::new(GlobalInputState::new()),
+            dumper_lower: Arc::new(GlobalInputState::new()),
+        }
+    }
+
+    /// Applies the debounce settings of every limit switch from `config`.
+    pub fn configure(&self, config: &LimitsConfig) {
+        self.intake_left_upper.set_debounce(config.get_intake_left_upper());
+        self.intake_left_lower.set_debounce(config.get_intake_left_lower());
+        self.intake_right_upper.set_debounce(config.get_intake_right_upper());
+        self.intake_right_lower.set_debounce(config.get_intake_right_lower());
+        self.dumper_upper.set_debounce(config.get_dumper_upper());
+        self.dumper_lower.set_debounce(config.get_dumper_lower());
+    }
+
+    pub fn get_intake_left_upper(&self) -> Arc<GlobalInputState> {
+        self.intake_left_upper.clone()
+    }
+
+    pub fn get_intake_left_lower(&self) -> Arc<GlobalInputState> {
+        self.intake_left_lower.clone()
+    }
+
+    pub fn get_intake_right_upper(&self) -> Arc<GlobalInputState> {
+        self.intake_right_upper.clone()
+    }
+
+    pub fn get_intake_right_lower(&self) -> Arc<GlobalInputState> {
+        self.intake_right_lower.clone()
+    }
+
+    pub fn get_dumper_upper(&self) -> Arc<GlobalInputState> {
+        self.dumper_upper.clone()
+    }
+
+    pub fn get_dumper_lower(&self) -> Arc<GlobalInputState> {
+        self.dumper_lower.clone()
+    }
+
+    pub fn get_current_state(&self) -> LimitsStateInstance {
+        LimitsStateInstance::new(
+            self.intake_left_upper.get_current_state(),
+            self.intake_left_lower.get_current_state(),
+            self.intake_right_upper.get_current_state(),
+            self.intake_right_lower.get_current_state(),
+            self.dumper_upper.get_current_state(),
+            self.dumper_lower.get_current_state(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct LimitsStateInstance {
+    intake_left_upper: InputStateInstance,
+    intake_left_lower: InputStateInstance,
+    intake_right_upper: InputStateInstance,
+    intake_right_lower: InputStateInstance,
+    dumper_upper: InputStateInstance,
+    dumper_lower: InputStateInstance,
+}
+
+impl LimitsStateInstance {
+    fn new(intake_left_upper: InputStateInstance, intake_left_lower: InputStateInstance,
+           intake_right_upper: InputStateInstance, intake_right_lower: InputStateInstance,
+           dumper_upper: InputStateInstance, dumper_lower: InputStateInstance) -> Self {
+        Self {
+            intake_left_upper,
+            intake_left_lower,
+            intake_right_upper,
+            intake_right_lower,
+            dumper_upper,
+            dumper_lower,
+        }
+    }
+
+    pub fn get_intake_left_upper(&self) -> &InputStateInstance {
+        &self.intake_left_upper
+    }
+
+    pub fn get_intake_left_lower(&self) -> &InputStateInstance {
+        &self.intake_left_lower
+    }
+
+    pub fn get_intake_right_upper(&self) -> &InputStateInstance {
+        &self.intake_right_upper
+    }
+
+    pub fn get_intake_right_lower(&self) -> &InputStateInstance {
+        &self.intake_right_lower
+    }
+
+    pub fn get_dumper_upper(&self) -> &InputStateInstance {
+        &self.dumper_upper
+    }
+
+    pub fn get_dumper_lower(&self) -> &InputStateInstance {
+        &self.dumper_lower
+    }
+}
@@ -1,3 +1,7 @@
 pub mod life;
 pub mod robot_state;
-pub mod current;
\ No newline at end of file
+pub mod current;
+pub mod watchdog;
+pub mod timing;
+pub mod limits;
+pub mod faults;
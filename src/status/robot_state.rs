@@ -1,38 +1,73 @@
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::thread::current;
 
+use atomic::Atomic;
+
 use crate::mechatronics::bucket_ladder::state::GlobalIntakeState;
 use crate::mechatronics::bucket_ladder::state::IntakeStateInstance;
+use crate::mechatronics::controller::MechState;
 use crate::mechatronics::drive_train::state::DriveTrainStateInstance;
 use crate::mechatronics::drive_train::state::GlobalDriveTrainState;
 use crate::mechatronics::dumper::state::DumperStateInstance;
 use crate::mechatronics::dumper::state::GlobalDumperState;
+use crate::mechatronics::routines::state::GlobalRoutineState;
+use crate::mechatronics::routines::state::RoutineStateInstance;
+use crate::simulation::state::GlobalSimulationState;
+use crate::simulation::state::SimulationStateInstance;
 use crate::status::current::{CurrentStateJson, GlobalCurrentState};
+use crate::status::faults::GlobalFaultState;
 use crate::status::life::GlobalLifeState;
 use crate::status::life::LifeStateInstance;
+use crate::status::limits::GlobalLimitsState;
+use crate::status::limits::LimitsStateInstance;
+use crate::status::timing::GlobalLoopState;
+use crate::status::timing::LoopStateInstance;
+use crate::status::watchdog::GlobalWatchdogState;
+use crate::status::watchdog::WatchdogStateInstance;
 
 pub struct GlobalRobotState {
     life: Arc<GlobalLifeState>,
+    mode: Arc<Atomic<MechState>>,
     current: Arc<GlobalCurrentState>,
     drive: Arc<GlobalDriveTrainState>,
     dumper: Arc<GlobalDumperState>,
     intake: Arc<GlobalIntakeState>,
+    limits: Arc<GlobalLimitsState>,
+    watchdog: Arc<GlobalWatchdogState>,
+    faults: Arc<GlobalFaultState>,
+    simulation: Arc<GlobalSimulationState>,
     cycles_per_sec: Arc<AtomicUsize>,
     cycle_counter: Arc<AtomicUsize>,
+    controller_timing: Arc<GlobalLoopState>,
+    monitor_timing: Arc<GlobalLoopState>,
+    routine: Arc<GlobalRoutineState>,
+    precision: Arc<AtomicBool>,
+    telemetry_port: Arc<Atomic<Option<u16>>>,
 }
 
 impl GlobalRobotState {
     pub fn new() -> Self {
         Self {
             life: Arc::new(GlobalLifeState::new()),
+            mode: Arc::new(Atomic::new(MechState::Idle)),
             current: Arc::new(GlobalCurrentState::new()),
             drive: Arc::new(GlobalDriveTrainState::new()),
             dumper: Arc::new(GlobalDumperState::new()),
             intake: Arc::new(GlobalIntakeState::new()),
+            limits: Arc::new(GlobalLimitsState::new()),
+            watchdog: Arc::new(GlobalWatchdogState::new()),
+            faults: Arc::new(GlobalFaultState::new()),
+            simulation: Arc::new(GlobalSimulationState::new()),
             cycles_per_sec: Arc::new(AtomicUsize::new(0)),
             cycle_counter: Arc::new(AtomicUsize::new(0)),
+            controller_timing: Arc::new(GlobalLoopState::new()),
+            monitor_timing: Arc::new(GlobalLoopState::new()),
+            routine: Arc::new(GlobalRoutineState::new()),
+            precision: Arc::new(AtomicBool::new(false)),
+            telemetry_port: Arc::new(Atomic::new(None)),
         }
     }
 
@@ -40,6 +75,11 @@ impl GlobalRobotState {
         self.life.clone()
     }
 
+    /// The mode the controller is operating in.
+    pub fn get_mode(&self) -> Arc<Atomic<MechState>> {
+        self.mode.clone()
+    }
+
     pub fn get_current(&self) -> Arc<GlobalCurrentState> {
         self.current.clone()
     }
@@ -56,16 +96,43 @@ impl GlobalRobotState {
         self.intake.clone()
     }
 
+    /// The debounce settings and glitch counts of the limit switches.
+    pub fn get_limits(&self) -> Arc<GlobalLimitsState> {
+        self.limits.clone()
+    }
+
+    pub fn get_watchdog(&self) -> Arc<GlobalWatchdogState> {
+        self.watchdog.clone()
+    }
+
+    /// Every fault raised on the robot and not yet cleared.
+    pub fn get_faults(&self) -> Arc<GlobalFaultState> {
+        self.faults.clone()
+    }
+
+    pub fn get_simulation(&self) -> Arc<GlobalSimulationState> {
+        self.simulation.clone()
+    }
+
     pub fn get_current_state(&self) -> RobotStateInstance {
-        RobotStateInstance::new(
-            self.life.get_current_state(),
-            self.current.get_json(),
-            self.drive.get_current_state(),
-            self.dumper.get_current_state(),
-            self.intake.get_current_state(),
-            self.cycles_per_sec.load(Ordering::SeqCst),
-            self.cycle_counter.load(Ordering::SeqCst),
-        )
+        RobotStateInstance {
+            life: self.life.get_current_state(),
+            mode: self.mode.load(Ordering::SeqCst),
+            current: self.current.get_json(),
+            drive: self.drive.get_current_state(),
+            dumper: self.dumper.get_current_state(),
+            intake: self.intake.get_current_state(),
+            limits: self.limits.get_current_state(),
+            watchdog: self.watchdog.get_current_state(),
+            simulation: if self.simulation.get_enabled() { Some(self.simulation.get_current_state()) } else { None },
+            cycles_per_sec: self.cycles_per_sec.load(Ordering::SeqCst),
+            cycle_counter: self.cycle_counter.load(Ordering::SeqCst),
+            controller_timing: self.controller_timing.get_current_state(),
+            monitor_timing: self.monitor_timing.get_current_state(),
+            routine: self.routine.get_current_state(),
+            precision: self.precision.load(Ordering::SeqCst),
+            telemetry_port: self.telemetry_port.load(Ordering::SeqCst),
+        }
     }
 
     pub fn get_cycles_per_second(&self) -> Arc<AtomicUsize> {
@@ -75,37 +142,60 @@ impl GlobalRobotState {
     pub fn get_cycle_counter(&self) -> Arc<AtomicUsize> {
         self.cycle_counter.clone()
     }
+
+    pub fn get_controller_timing(&self) -> Arc<GlobalLoopState> {
+        self.controller_timing.clone()
+    }
+
+    pub fn get_monitor_timing(&self) -> Arc<GlobalLoopState> {
+        self.monitor_timing.clone()
+    }
+
+    pub fn get_routine(&self) -> Arc<GlobalRoutineState> {
+        self.routine.clone()
+    }
+
+    /// Whether the mechanisms are slowed down for precise maneuvering.
+    pub fn get_precision(&self) -> Arc<AtomicBool> {
+        self.precision.clone()
+    }
+
+    /// The port telemetry is streamed on, if it is being streamed.
+    pub fn get_telemetry_port(&self) -> Arc<Atomic<Option<u16>>> {
+        self.telemetry_port.clone()
+    }
 }
 
 #[derive(Serialize)]
 pub struct RobotStateInstance {
     life: LifeStateInstance,
+    mode: MechState,
     current: CurrentStateJson,
     drive: DriveTrainStateInstance,
     dumper: DumperStateInstance,
     intake: IntakeStateInstance,
+    limits: LimitsStateInstance,
+    watchdog: WatchdogStateInstance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    simulation: Option<SimulationStateInstance>,
     cycles_per_sec: usize,
     cycle_counter: usize,
+    controller_timing: LoopStateInstance,
+    monitor_timing: LoopStateInstance,
+    routine: RoutineStateInstance,
+    precision: bool,
+    telemetry_port: Option<u16>,
 }
 
 impl RobotStateInstance {
-    pub fn new(life: LifeStateInstance, current: CurrentStateJson, drive: DriveTrainStateInstance, dumper: DumperStateInstance,
-               intake: IntakeStateInstance, cycles_per_sec: usize, cycle_counter: usize) -> Self {
-        Self {
-            life,
-            current,
-            drive,
-            dumper,
-            intake,
-            cycles_per_sec,
-            cycle_counter,
-        }
-    }
-
     pub fn get_life(&self) -> &LifeStateInstance {
         &self.life
     }
 
+    pub fn get_mode(&self) -> MechState {
+        self.mode
+    }
+
     pub fn get_drive(&self) -> &DriveTrainStateInstance {
         &self.drive
     }
@@ -117,4 +207,36 @@ impl RobotStateInstance {
     pub fn get_intake(&self) -> &IntakeStateInstance {
         &self.intake
     }
+
+    pub fn get_limits(&self) -> &LimitsStateInstance {
+        &self.limits
+    }
+
+    pub fn get_watchdog(&self) -> &WatchdogStateInstance {
+        &self.watchdog
+    }
+
+    pub fn get_simulation(&self) -> Option<&SimulationStateInstance> {
+        self.simulation.as_ref()
+    }
+
+    pub fn get_controller_timing(&self) -> &LoopStateInstance {
+        &self.controller_timing
+    }
+
+    pub fn get_monitor_timing(&self) -> &LoopStateInstance {
+        &self.monitor_timing
+    }
+
+    pub fn get_routine(&self) -> &RoutineStateInstance {
+        &self.routine
+    }
+
+    pub fn get_precision(&self) -> bool {
+        self.precision
+    }
+
+    pub fn get_telemetry_port(&self) -> Option<u16> {
+        self.telemetry_port
+    }
 }
\ No newline at end of file
@@ -0,0 +1,185 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Tracks how well a fixed-rate loop is keeping to its schedule.
+///
+/// Cycle times are measured from the start of `run()` to its end, while jitter is how far the start
+/// of each cycle was from the time it was scheduled for. All times are in microseconds.
+pub struct GlobalLoopState {
+    period_micros: AtomicUsize,
+    cycles: AtomicUsize,
+    overruns: AtomicUsize,
+    last_cycle_micros: AtomicUsize,
+    worst_cycle_micros: AtomicUsize,
+    total_jitter_micros: AtomicUsize,
+    worst_jitter_micros: AtomicUsize,
+}
+
+impl GlobalLoopState {
+    pub fn new() -> Self {
+        Self {
+            period_micros: AtomicUsize::new(0),
+            cycles: AtomicUsize::new(0),
+            overruns: AtomicUsize::new(0),
+            last_cycle_micros: AtomicUsize::new(0),
+            worst_cycle_micros: AtomicUsize::new(0),
+            total_jitter_micros: AtomicUsize::new(0),
+            worst_jitter_micros: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the target period of the loop. A period of zero means the loop is free-running.
+    pub fn set_period(&self, period: Duration) {
+        self.period_micros.store(to_micros(period), Ordering::Relaxed);
+    }
+
+    pub fn get_period(&self) -> Duration {
+        Duration::from_micros(self.period_micros.load(Ordering::Relaxed) as u64)
+    }
+
+    /// Records a completed cycle which took `cycle` to run and started `jitter` away from its
+    /// scheduled time.
+    pub fn record_cycle(&self, cycle: Duration, jitter: Duration) {
+        let cycle = to_micros(cycle);
+        let jitter = to_micros(jitter);
+
+        self.cycles.fetch_add(1, Ordering::Relaxed);
+        self.last_cycle_micros.store(cycle, Ordering::Relaxed);
+        self.total_jitter_micros.fetch_add(jitter, Ordering::Relaxed);
+        store_max(&self.worst_cycle_micros, cycle);
+        store_max(&self.worst_jitter_micros, jitter);
+
+        if cycle > self.period_micros.load(Ordering::Relaxed) {
+            self.overruns.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn get_cycles(&self) -> usize {
+        self.cycles.load(Ordering::Relaxed)
+    }
+
+    pub fn get_overruns(&self) -> usize {
+        self.overruns.load(Ordering::Relaxed)
+    }
+
+    pub fn get_current_state(&self) -> LoopStateInstance {
+        let cycles = self.cycles.load(Ordering::Relaxed);
+        let mean_jitter_micros = if cycles == 0 {
+            0
+        } else {
+            self.total_jitter_micros.load(Ordering::Relaxed) / cycles
+        };
+
+        LoopStateInstance::new(
+            self.period_micros.load(Ordering::Relaxed),
+            cycles,
+            self.overruns.load(Ordering::Relaxed),
+            self.last_cycle_micros.load(Ordering::Relaxed),
+            self.worst_cycle_micros.load(Ordering::Relaxed),
+            mean_jitter_micros,
+            self.worst_jitter_micros.load(Ordering::Relaxed),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct LoopStateInstance {
+    period_micros: usize,
+    cycles: usize,
+    overruns: usize,
+    last_cycle_micros: usize,
+    worst_cycle_micros: usize,
+    mean_jitter_micros: usize,
+    worst_jitter_micros: usize,
+}
+
+impl LoopStateInstance {
+    fn new(period_micros: usize, cycles: usize, overruns: usize, last_cycle_micros: usize, worst_cycle_micros: usize,
+           mean_jitter_micros: usize, worst_jitter_micros: usize) -> Self {
+        Self {
+            period_micros,
+            cycles,
+            overruns,
+            last_cycle_micros,
+            worst_cycle_micros,
+            mean_jitter_micros,
+            worst_jitter_micros,
+        }
+    }
+
+    pub fn get_cycles(&self) -> usize {
+        self.cycles
+    }
+
+    pub fn get_overruns(&self) -> usize {
+        self.overruns
+    }
+
+    pub fn get_worst_cycle_micros(&self) -> usize {
+        self.worst_cycle_micros
+    }
+
+    pub fn get_mean_jitter_micros(&self) -> usize {
+        self.mean_jitter_micros
+    }
+
+    pub fn get_worst_jitter_micros(&self) -> usize {
+        self.worst_jitter_micros
+    }
+}
+
+fn to_micros(duration: Duration) -> usize {
+    duration.as_secs() as usize * 1_000_000 + duration.subsec_micros() as usize
+}
+
+fn store_max(field: &AtomicUsize, value: usize) {
+    let mut current = field.load(Ordering::Relaxed);
+    while value > current {
+        match field.compare_exchange_weak(current, value, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => break,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record() {
+        let state = GlobalLoopState::new();
+        state.set_period(Duration::from_millis(5));
+
+        state.record_cycle(Duration::from_millis(1), Duration::from_micros(100));
+        state.record_cycle(Duration::from_millis(7), Duration::from_micros(300));
+        state.record_cycle(Duration::from_millis(2), Duration::from_micros(200));
+
+        let instance = state.get_current_state();
+        assert_eq!(3, instance.get_cycles());
+        assert_eq!(1, instance.get_overruns());
+        assert_eq!(7_000, instance.get_worst_cycle_micros());
+        assert_eq!(200, instance.get_mean_jitter_micros());
+        assert_eq!(300, instance.get_worst_jitter_micros());
+    }
+
+    #[test]
+    fn overrun_boundary() {
+        let state = GlobalLoopState::new();
+        state.set_period(Duration::from_millis(5));
+
+        state.record_cycle(Duration::from_millis(5), Duration::from_micros(0));
+        assert_eq!(0, state.get_overruns());
+
+        state.record_cycle(Duration::from_micros(5_001), Duration::from_micros(0));
+        assert_eq!(1, state.get_overruns());
+    }
+
+    #[test]
+    fn empty() {
+        let state = GlobalLoopState::new();
+        assert_eq!(0, state.get_current_state().get_mean_jitter_micros());
+        assert_eq!(Duration::from_micros(0), state.get_period());
+    }
+}
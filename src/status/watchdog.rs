@@ -0,0 +1,118 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::robot_map::WATCHDOG_TIMEOUT_MILLIS;
+
+/// Tracks whether the communication watchdog has tripped, and how long the controller waits for
+/// a command from the driver station before tripping it.
+pub struct GlobalWatchdogState {
+    tripped: AtomicBool,
+    trips: AtomicUsize,
+    timeout_millis: AtomicUsize,
+}
+
+impl GlobalWatchdogState {
+    pub fn new() -> Self {
+        Self {
+            tripped: AtomicBool::new(false),
+            trips: AtomicUsize::new(0),
+            timeout_millis: AtomicUsize::new(WATCHDOG_TIMEOUT_MILLIS as usize),
+        }
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.tripped.load(Ordering::Relaxed)
+    }
+
+    /// Marks the watchdog as tripped, counting the trip.
+    pub fn trip(&self) {
+        self.tripped.store(true, Ordering::SeqCst);
+        self.trips.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Clears the tripped flag after communication has been restored.
+    pub fn reset(&self) {
+        self.tripped.store(false, Ordering::SeqCst);
+    }
+
+    pub fn get_trips(&self) -> usize {
+        self.trips.load(Ordering::Relaxed)
+    }
+
+    pub fn get_timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_millis.load(Ordering::Relaxed) as u64)
+    }
+
+    pub fn set_timeout(&self, timeout: Duration) {
+        let millis = timeout.as_secs() as usize * 1000 + timeout.subsec_millis() as usize;
+        self.timeout_millis.store(millis, Ordering::Relaxed);
+    }
+
+    pub fn get_current_state(&self) -> WatchdogStateInstance {
+        WatchdogStateInstance::new(
+            self.tripped.load(Ordering::Relaxed),
+            self.trips.load(Ordering::Relaxed),
+            self.timeout_millis.load(Ordering::Relaxed),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct WatchdogStateInstance {
+    tripped: bool,
+    trips: usize,
+    timeout_millis: usize,
+}
+
+impl WatchdogStateInstance {
+    fn new(tripped: bool, trips: usize, timeout_millis: usize) -> Self {
+        Self {
+            tripped,
+            trips,
+            timeout_millis,
+        }
+    }
+
+    pub fn get_tripped(&self) -> bool {
+        self.tripped
+    }
+
+    pub fn get_trips(&self) -> usize {
+        self.trips
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial() {
+        let state = GlobalWatchdogState::new();
+        assert_eq!(false, state.is_tripped());
+        assert_eq!(0, state.get_trips());
+        assert_eq!(Duration::from_millis(WATCHDOG_TIMEOUT_MILLIS), state.get_timeout());
+    }
+
+    #[test]
+    fn trip_and_reset() {
+        let state = GlobalWatchdogState::new();
+
+        state.trip();
+        assert_eq!(true, state.is_tripped());
+        assert_eq!(true, state.get_current_state().get_tripped());
+
+        state.reset();
+        assert_eq!(false, state.is_tripped());
+        assert_eq!(1, state.get_current_state().get_trips());
+    }
+
+    #[test]
+    fn set_timeout() {
+        let state = GlobalWatchdogState::new();
+        state.set_timeout(Duration::from_millis(1_250));
+        assert_eq!(Duration::from_millis(1_250), state.get_timeout());
+    }
+}
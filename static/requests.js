@@ -1,4 +1,57 @@
-setInterval(get_state, 500);
+let state_poller = null;
+setInterval(heartbeat, 250);
+setInterval(get_faults, 1000);
+find_telemetry();
+
+/// Asks the robot which port it streams telemetry on, and connects to it if there is one.
+function find_telemetry() {
+    fetch("/robot", {method: "GET", headers: {Accept: 'application/json'}})
+        .then(response => response.json())
+        .then(state => {
+            show_state(state);
+            if (state.telemetry_port === null) {
+                poll_state();
+            } else {
+                connect_telemetry(state.telemetry_port);
+            }
+        })
+        .catch(error => {
+            console.log("Fetching state failed: ", JSON.stringify(error));
+            setTimeout(find_telemetry, 2000);
+        });
+}
+
+function poll_state() {
+    if (state_poller === null) {
+        state_poller = setInterval(get_state, 500);
+    }
+}
+
+/// Streams the robot state from the telemetry server, falling back to polling while it is unavailable.
+function connect_telemetry(port) {
+    let socket = new WebSocket("ws://" + window.location.hostname + ":" + port);
+
+    socket.onopen = function () {
+        if (state_poller !== null) {
+            clearInterval(state_poller);
+            state_poller = null;
+        }
+    };
+
+    socket.onmessage = function (event) {
+        show_state(JSON.parse(event.data));
+    };
+
+    socket.onclose = function () {
+        poll_state();
+        setTimeout(() => connect_telemetry(port), 2000);
+    };
+}
+
+function show_state(state) {
+    // TODO find a better way to display state.
+    $("#state-view").text(JSON.stringify(state));
+}
 
 function drive_from_form() {
     let left = parseFloat($("#left-drive").val());
@@ -16,10 +69,7 @@ function get_state() {
         },
     )
         .then(response => {
-            // TODO find a better way to display state.
-            response.json().then(value => {
-                $("#state-view").text(JSON.stringify(value));
-            })
+            response.json().then(show_state)
 
         })
         .catch(function (reason) {
@@ -27,6 +77,36 @@ function get_state() {
         });
 }
 
+function get_faults() {
+    fetch("/robot/faults", {method: "GET", headers: {Accept: 'application/json'}})
+        .then(response => response.json().then(show_faults))
+        .catch(error => console.log("Fetching faults failed: ", JSON.stringify(error)));
+}
+
+/// Lists every uncleared fault, each with a button to clear it.
+function show_faults(faults) {
+    let rows = faults.map(fault => $("<tr>").append(
+        $("<td>").text(fault.severity),
+        $("<td>").text(fault.subsystem),
+        $("<td>").text(fault.code),
+        $("<td>").text(fault.message),
+        $("<td>").text(fault.count),
+        $("<td>").append($("<button>").text("Clear").click(() => clear_fault(fault.code))),
+    ));
+    $("#fault-view").empty().append(rows);
+}
+
+function clear_fault(code) {
+    fetch("/robot/faults/" + encodeURIComponent(code), {method: "DELETE", cache: "no-cache"})
+        .then(get_faults)
+        .catch(error => console.log("Clearing fault failed: ", JSON.stringify(error)));
+}
+
+function heartbeat() {
+    fetch("/robot/heartbeat", {method: "PUT", cache: "no-cache"})
+        .catch(error => console.log("Heartbeat failed: ", JSON.stringify(error)));
+}
+
 function kill() {
     putRobot({life: "Dead"});
 }
@@ -35,6 +115,10 @@ function revive() {
     putRobot({life: "Alive"});
 }
 
+function switch_to_idle() {
+    putRobot({mode: "Idle"});
+}
+
 function switch_to_drive() {
     putRobot({mode: "Driving"});
 }
@@ -51,6 +135,10 @@ function brake() {
     putData("/robot/drive", "Brake")
 }
 
+function resetPose() {
+    putData("/robot/drive", "ResetPose")
+}
+
 function dig() {
     putIntake({digger: "Dig"})
 }
@@ -67,10 +155,18 @@ function reset_dumper() {
     putDumper("Reset")
 }
 
+function cycle_dumper() {
+    putDumper("Cycle")
+}
+
 function stop_dumping() {
     putDumper("Stop")
 }
 
+function clear_dumper_fault() {
+    putDumper("ClearFault")
+}
+
 function raise_actuators() {
     putIntake({actuator: "Raise"});
 }
@@ -83,6 +179,34 @@ function stop_actuators() {
     putIntake({actuator: "Stop"});
 }
 
+function move_actuators(fraction) {
+    putIntake({actuator: {MoveTo: {fraction: fraction}}});
+}
+
+function clear_actuator_fault() {
+    putIntake({actuator: "ClearFault"});
+}
+
+function start_routine(routine) {
+    putRoutine({Start: routine});
+}
+
+function pause_routine() {
+    putRoutine("Pause");
+}
+
+function resume_routine() {
+    putRoutine("Resume");
+}
+
+function cancel_routine() {
+    putRoutine("Cancel");
+}
+
+function putRoutine(action) {
+    putData("/robot/routine", action)
+}
+
 function putIntake(action) {
     let url = "/robot/intake";
     putData(url, action);
@@ -106,6 +230,18 @@ function drive(left, right) {
     putData(url, data)
 }
 
+function arcadeDrive(throttle, turn) {
+    putData("/robot/drive", {Arcade: {throttle: throttle, turn: turn}})
+}
+
+function curvatureDrive(throttle, curvature) {
+    putData("/robot/drive", {Curvature: {throttle: throttle, curvature: curvature}})
+}
+
+function setPrecision(precision) {
+    putRobot({precision: precision});
+}
+
 function putRobot(action) {
     putData("/robot", action)
 }